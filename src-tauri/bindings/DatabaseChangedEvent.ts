@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TableChange } from "./TableChange";
+
+/**
+ * Payload des `database-changed`-Events, ein Event pro Commit
+ */
+export type DatabaseChangedEvent = { vaultId: string, changes: Array<TableChange>, };
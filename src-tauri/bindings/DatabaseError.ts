@@ -1,3 +1,6 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type DatabaseError = { "type": "ParseError", "details": { reason: string, sql: string, } } | { "type": "ParameterMismatchError", "details": { expected: number, provided: number, sql: string, } } | { "type": "NoTableError", "details": { sql: string, } } | { "type": "StatementError", "details": { reason: string, } } | { "type": "PrepareError", "details": { reason: string, } } | { "type": "DatabaseError", "details": { reason: string, } } | { "type": "ExecutionError", "details": { sql: string, reason: string, table: string | null, } } | { "type": "TransactionError", "details": { reason: string, } } | { "type": "UnsupportedStatement", "details": { reason: string, sql: string, } } | { "type": "HlcError", "details": { reason: string, } } | { "type": "LockError", "details": { reason: string, } } | { "type": "ConnectionError", "details": { reason: string, } } | { "type": "SerializationError", "details": { reason: string, } } | { "type": "PermissionError", "details": { extension_id: string, operation: string | null, resource: string | null, reason: string, } } | { "type": "QueryError", "details": { reason: string, } } | { "type": "RowProcessingError", "details": { reason: string, } } | { "type": "MutexPoisoned", "details": { reason: string, } } | { "type": "ConnectionFailed", "details": { path: string, reason: string, } } | { "type": "PragmaError", "details": { pragma: string, reason: string, } } | { "type": "PathResolutionError", "details": { reason: string, } } | { "type": "IoError", "details": { path: string, reason: string, } } | { "type": "CrdtSetup", "details": string };
+/**
+ * Wird zusammen mit `code` serialisiert, siehe `SerializedDatabaseError`
+ */
+export type DatabaseError = { "type": "ParseError", "details": { reason: string, sql: string, } } | { "type": "ParameterMismatchError", "details": { expected: number, provided: number, sql: string, } } | { "type": "ParameterError", "details": { name: string, reason: string, sql: string, } } | { "type": "NoTableError", "details": { sql: string, } } | { "type": "StatementError", "details": { reason: string, } } | { "type": "PrepareError", "details": { reason: string, } } | { "type": "DatabaseError", "details": { reason: string, } } | { "type": "ExecutionError", "details": { sql: string, reason: string, table: string | null, } } | { "type": "TransactionError", "details": { reason: string, } } | { "type": "UnsupportedStatement", "details": { reason: string, sql: string, } } | { "type": "HlcError", "details": { reason: string, } } | { "type": "LockError", "details": { reason: string, } } | { "type": "ConnectionError", "details": { reason: string, } } | { "type": "SerializationError", "details": { reason: string, } } | { "type": "PermissionError", "details": { extension_id: string, operation: string | null, resource: string | null, reason: string, } } | { "type": "QueryError", "details": { reason: string, } } | { "type": "RowProcessingError", "details": { reason: string, } } | { "type": "MutexPoisoned", "details": { reason: string, } } | { "type": "ConnectionFailed", "details": { path: string, reason: string, } } | { "type": "PragmaError", "details": { pragma: string, reason: string, } } | { "type": "PathResolutionError", "details": { reason: string, } } | { "type": "IoError", "details": { path: string, reason: string, } } | { "type": "CrdtSetup", "details": string } | { "type": "MigrationError", "details": { migration: string, reason: string, } } | { "type": "VaultSchemaTooNew", "details": { vault_version: bigint, app_version: bigint | null, } } | { "type": "ImportError", "details": { path: string, line: number | null, reason: string, } } | { "type": "SearchIndexError", "details": { table: string, reason: string, } } | { "type": "VaultNotOpen", "details": { vault_id: string, } } | { "type": "UniqueViolation", "details": { table: string | null, columns: Array<string>, constraint: string | null, reason: string, } } | { "type": "PrimaryKeyViolation", "details": { table: string | null, columns: Array<string>, reason: string, } } | { "type": "ForeignKeyViolation", "details": { table: string | null, reason: string, } } | { "type": "NotNullViolation", "details": { table: string | null, column: string | null, reason: string, } } | { "type": "CheckViolation", "details": { table: string | null, constraint: string | null, reason: string, } } | { "type": "ConstraintViolation", "details": { table: string | null, reason: string, } } | { "type": "InvalidVaultKey", "details": { reason: string, } } | { "type": "Busy", "details": { reason: string, } } | { "type": "Locked", "details": { reason: string, } } | { "type": "DiskFull", "details": { reason: string, } } | { "type": "ReadOnly", "details": { reason: string, } } | { "type": "Corrupt", "details": { reason: string, } };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Stabile Fehlercodes für das Frontend, ergänzend zu `ExtensionErrorCode`.
+ * Bereiche: 6000 allgemein, 6100 Constraints, 6200 Zustand der Datenbankdatei.
+ */
+export enum DatabaseErrorCode { "Parse" = 6000, "ParameterMismatch" = 6001, "Parameter" = 6002, "NoTable" = 6003, "Statement" = 6004, "Prepare" = 6005, "Database" = 6006, "Execution" = 6007, "Transaction" = 6008, "UnsupportedStatement" = 6009, "Hlc" = 6010, "Lock" = 6011, "Connection" = 6012, "Serialization" = 6013, "Permission" = 6014, "Query" = 6015, "RowProcessing" = 6016, "MutexPoisoned" = 6017, "ConnectionFailed" = 6018, "Pragma" = 6019, "PathResolution" = 6020, "Io" = 6021, "CrdtSetup" = 6022, "Migration" = 6023, "VaultSchemaTooNew" = 6024, "Import" = 6025, "SearchIndex" = 6026, "VaultNotOpen" = 6027, "UniqueViolation" = 6100, "PrimaryKeyViolation" = 6101, "ForeignKeyViolation" = 6102, "NotNullViolation" = 6103, "CheckViolation" = 6104, "ConstraintViolation" = 6105, "InvalidVaultKey" = 6200, "Busy" = 6201, "Locked" = 6202, "DiskFull" = 6203, "ReadOnly" = 6204, "Corrupt" = 6205 }
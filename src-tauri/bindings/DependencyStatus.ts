@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Stand einer Abhängigkeit für Vorschau und Installation
+ */
+export type DependencyStatus = { publicKey: string, name: string, requirement: string, 
+/**
+ * Installierte Version, falls die Abhängigkeit installiert ist
+ */
+installedVersion: string | null, 
+/**
+ * Ob die installierte Abhängigkeit aktiviert ist
+ */
+enabled: boolean, 
+/**
+ * Installiert, aktiviert und in passender Version
+ */
+satisfied: boolean, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DataFormat } from "./DataFormat";
+
+export type ExportRequest = { 
+/**
+ * Zu exportierende Tabellen
+ */
+tables: Array<string>, 
+/**
+ * Exportiert zusätzlich alle eigenen Tabellen dieser Extension
+ */
+extensionId: string | null, format: DataFormat, 
+/**
+ * Zielverzeichnis, pro Tabelle wird `{table}.{jsonl|csv}` geschrieben
+ */
+directory: string, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Abhängigkeit auf eine andere Extension
+ */
+export type ExtensionDependency = { public_key: string, name: string, 
+/**
+ * semver-Bereich, z.B. `^1.2.0` oder `>=1.0.0 <2.0.0`
+ */
+version: string, };
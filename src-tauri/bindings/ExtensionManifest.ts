@@ -1,5 +1,31 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { DisplayMode } from "./DisplayMode";
+import type { ExtensionDependency } from "./ExtensionDependency";
 import type { ExtensionPermissions } from "./ExtensionPermissions";
+import type { KeyRotation } from "./KeyRotation";
+import type { TableExport } from "./TableExport";
 
-export type ExtensionManifest = { name: string, version: string, author: string | null, entry: string | null, icon: string | null, public_key: string, signature: string, permissions: ExtensionPermissions, homepage: string | null, description: string | null, single_instance: boolean | null, display_mode: DisplayMode | null, };
+export type ExtensionManifest = { name: string, version: string, author: string | null, entry: string | null, icon: string | null, public_key: string, signature: string, permissions: ExtensionPermissions, homepage: string | null, description: string | null, single_instance: boolean | null, display_mode: DisplayMode | null, 
+/**
+ * Ordner (relativ zum haextension-Ordner) mit Schema-Migrationen `NNNN_name.sql` und optional
+ * `NNNN_name.down.sql`. Ausstehende Migrationen werden bei Installation und Update angewendet.
+ */
+migrations: string | null, 
+/**
+ * SQL-Skript (relativ zum haextension-Ordner), das die Schemaänderungen dieser Version
+ * rückgängig macht. Wird beim Rollback auf eine ältere Version ausgeführt.
+ */
+down_migration: string | null, 
+/**
+ * Schlüsselwechsel des Publishers: der bisherige Schlüssel bestätigt `public_key` als Nachfolger.
+ * Nur damit darf eine installierte Extension auf einen anderen Schlüssel aktualisiert werden.
+ */
+key_rotation: KeyRotation | null, 
+/**
+ * Extensions, die vor dieser Extension in passender Version installiert sein müssen
+ */
+dependencies: Array<ExtensionDependency> | null, 
+/**
+ * Tabellen (ohne Präfix), auf die abhängige Extensions ohne eigene Freigabe zugreifen dürfen
+ */
+exported_tables: Array<TableExport> | null, };
@@ -1,5 +1,23 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DependencyStatus } from "./DependencyStatus";
 import type { ExtensionManifest } from "./ExtensionManifest";
 import type { ExtensionPermissions } from "./ExtensionPermissions";
+import type { PublisherTrust } from "./PublisherTrust";
 
-export type ExtensionPreview = { manifest: ExtensionManifest, is_valid_signature: boolean, editable_permissions: ExtensionPermissions, };
+export type ExtensionPreview = { manifest: ExtensionManifest, is_valid_signature: boolean, 
+/**
+ * Bei einem Update nur die Berechtigungen, die noch nicht entschieden wurden
+ */
+editable_permissions: ExtensionPermissions, 
+/**
+ * Version der bereits installierten Extension, falls die Installation ein Update ist
+ */
+installed_version: string | null, 
+/**
+ * Vertrauensstufe des Publishers und Entscheidung der Signatur-Policy (nur mit geöffnetem Vault)
+ */
+publisher_trust: PublisherTrust | null, 
+/**
+ * Stand der Abhängigkeiten gegenüber den installierten Extensions (nur mit geöffnetem Vault)
+ */
+dependency_status: Array<DependencyStatus>, };
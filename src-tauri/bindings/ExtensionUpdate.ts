@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Verfügbare neuere Version einer installierten Extension
+ */
+export type ExtensionUpdate = { publicKey: string, name: string, installedVersion: string, latestVersion: string, };
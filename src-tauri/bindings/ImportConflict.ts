@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ImportConflict = { 
+/**
+ * Zeile in der Importdatei (1-basiert)
+ */
+line: number, 
+/**
+ * Primärschlüssel als JSON-Objekt, wie in haex_crdt_logs.row_pks
+ */
+rowPks: string, };
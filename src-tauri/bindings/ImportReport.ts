@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ImportConflict } from "./ImportConflict";
+
+export type ImportReport = { table: string, dryRun: boolean, totalRows: number, imported: number, 
+/**
+ * Zeilen, deren Primärschlüssel bereits existiert (oder in der Datei doppelt vorkommt).
+ * Sie werden beim Import übersprungen.
+ */
+conflicts: Array<ImportConflict>, };
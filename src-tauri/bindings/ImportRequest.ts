@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DataFormat } from "./DataFormat";
+
+export type ImportRequest = { path: string, table: string, format: DataFormat, 
+/**
+ * Spalte in der Datei -> Spalte in der Tabelle. Nicht aufgeführte Spalten behalten ihren Namen.
+ */
+columnMapping: { [key in string]?: string }, 
+/**
+ * Nur prüfen und Konflikte melden, nichts schreiben
+ */
+dryRun: boolean, };
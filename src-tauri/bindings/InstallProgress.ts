@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { InstallStage } from "./InstallStage";
+
+/**
+ * Payload von `extension-install-progress`
+ */
+export type InstallProgress = { installId: string, stage: InstallStage, };
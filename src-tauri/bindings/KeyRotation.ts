@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Vom bisherigen Schlüssel signierte Erklärung über den neuen Publisher-Schlüssel
+ */
+export type KeyRotation = { previous_public_key: string, 
+/**
+ * Signatur des bisherigen Schlüssels über `haex-key-rotation:{previous_public_key}:{public_key}`
+ */
+signature: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QueryResult } from "./QueryResult";
+
+/**
+ * Payload des `live-query-result`-Events
+ */
+export type LiveQueryResultEvent = { vaultId: string, subscriptionId: string, result: QueryResult, };
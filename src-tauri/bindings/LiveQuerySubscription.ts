@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QueryResult } from "./QueryResult";
+
+export type LiveQuerySubscription = { subscriptionId: string, 
+/**
+ * Sofortiges erstes Ergebnis
+ */
+result: QueryResult, };
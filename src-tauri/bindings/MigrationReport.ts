@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type MigrationReport = { 
+/**
+ * Letzte angewendete Migration vor dem Lauf (None = leere Vault)
+ */
+previousVersion: bigint | null, 
+/**
+ * Letzte angewendete Migration nach dem Lauf
+ */
+currentVersion: bigint | null, 
+/**
+ * Tags der in diesem Lauf angewendeten Migrationen
+ */
+applied: Array<string>, 
+/**
+ * Tabellen, deren CRDT-Trigger neu erzeugt wurden
+ */
+recreatedTriggers: Array<string>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type OrphanedLogEntries = { 
+/**
+ * Tabellenname aus haex_crdt_logs, zu dem keine Tabelle existiert
+ */
+tableName: string, count: bigint, };
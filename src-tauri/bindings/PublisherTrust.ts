@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SignaturePolicy } from "./SignaturePolicy";
+import type { TrustedPublisher } from "./TrustedPublisher";
+
+/**
+ * Bewertung des Publishers einer Extension vor der Installation
+ */
+export type PublisherTrust = { publicKey: string, 
+/**
+ * Eintrag des Publishers - bei einem Schlüsselwechsel ggf. der des bisherigen Schlüssels
+ */
+publisher: TrustedPublisher | null, policy: SignaturePolicy, 
+/**
+ * `false`, wenn die Policy die Installation verbietet
+ */
+allowed: boolean, 
+/**
+ * Warnung bzw. Ablehnungsgrund für die Anzeige
+ */
+reason: string | null, 
+/**
+ * Bisheriger Schlüssel, falls das Manifest einen gültigen Schlüsselwechsel enthält
+ */
+rotatedFrom: string | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type QueryColumn = { name: string, 
+/**
+ * Deklarierter Typ aus dem Tabellenschema, `null` bei Ausdrücken oder ohne `includeColumnTypes`
+ */
+declType: string | null, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QueryPlanNode } from "./QueryPlanNode";
+
+export type QueryDiagnostics = { plan: Array<QueryPlanNode>, durationMs: number, rowsReturned: number, 
+/**
+ * Schritte in Full-Table-Scans (SQLITE_STMTSTATUS_FULLSCAN_STEP), Index-Zugriffe zählen nicht
+ */
+rowsScanned: number, 
+/**
+ * Sortierungen ohne passenden Index
+ */
+sorts: number, 
+/**
+ * Automatisch zur Laufzeit erzeugte Indizes - meist ein Hinweis auf einen fehlenden Index
+ */
+autoIndexes: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type QueryPlanNode = { id: bigint, 
+/**
+ * Beschreibung von SQLite, z.B. `SCAN notes` oder `SEARCH notes USING INDEX ...`
+ */
+detail: string, children: Array<QueryPlanNode>, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QueryColumn } from "./QueryColumn";
+
+export type QueryResult = { 
+/**
+ * Spalten in der Reihenfolge des SELECT
+ */
+columns: Array<QueryColumn>, rows: Array<Record<string, unknown>>, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RegistryVersion } from "./RegistryVersion";
+
+export type RegistryExtension = { name: string, publicKey: string, description: string | null, author: string | null, homepage: string | null, 
+/**
+ * URL des Icons, relativ zur Index-URL möglich
+ */
+icon: string | null, versions: Array<RegistryVersion>, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type RegistryVersion = { version: string, 
+/**
+ * Download-URL des `.haextension`-Pakets, relativ zur Index-URL möglich
+ */
+url: string, 
+/**
+ * SHA-256 des Pakets (hex)
+ */
+sha256: string, size: bigint | null, publishedAt: string | null, };
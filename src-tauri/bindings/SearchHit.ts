@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type SearchHit = { 
+/**
+ * Zeile der Quelltabelle
+ */
+row: Record<string, unknown>, 
+/**
+ * BM25-Relevanz - kleiner ist relevanter, Treffer sind danach sortiert
+ */
+rank: number, 
+/**
+ * Ausschnitt aus der am besten passenden Spalte mit markierten Fundstellen
+ */
+snippet: string, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type SearchIndexInfo = { 
+/**
+ * Quelltabelle
+ */
+table: string, 
+/**
+ * Durchsuchbare Spalten in Index-Reihenfolge
+ */
+columns: Array<string>, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type SearchOptions = { 
+/**
+ * Maximale Trefferzahl (Standard: 50)
+ */
+limit: number | null, offset: number | null, 
+/**
+ * Markierung vor/nach Fundstellen im Snippet (Standard: `<mark>` / `</mark>`)
+ */
+highlightStart: string | null, highlightEnd: string | null, 
+/**
+ * Länge des Snippets in Tokens (Standard: 16, maximal 64)
+ */
+snippetTokens: number | null, 
+/**
+ * Suchbegriff als FTS5-Query-Syntax (`AND`, `OR`, `NEAR`, `col:term`, ...) statt als Freitext
+ */
+rawQuery: boolean, };
@@ -1,6 +1,16 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SerializedDatabaseError } from "./SerializedDatabaseError";
 
 /**
  * Serialized representation of ExtensionError for TypeScript
  */
-export type SerializedExtensionError = { code: number, type: string, message: string, extension_id: string | null, };
+export type SerializedExtensionError = { code: number, type: string, message: string, extension_id: string | null, 
+/**
+ * `DatabaseErrorCode`, wenn `code` = `ExtensionErrorCode::Database`
+ */
+database_code: number | null, 
+/**
+ * Der `DatabaseError` samt Details (Tabelle, Spalten, Constraint), wenn
+ * `code` = `ExtensionErrorCode::Database`
+ */
+database_error: SerializedDatabaseError | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QueryPlanNode } from "./QueryPlanNode";
+
+export type SlowQueryEntry = { sql: string, durationMs: number, plan: Array<QueryPlanNode>, 
+/**
+ * Unix-Zeit in Sekunden
+ */
+recordedAt: bigint, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChangeOperation } from "./ChangeOperation";
+
+export type TableChange = { table: string, op: ChangeOperation, 
+/**
+ * Primärschlüssel der Zeile, bei Tabellen ohne Primärschlüssel `{ rowid }`
+ */
+pks: Record<string, unknown>, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TableAccess } from "./TableAccess";
+
+/**
+ * Für abhängige Extensions freigegebene Tabelle
+ */
+export type TableExport = { 
+/**
+ * Tabellenname ohne das Präfix `{public_key}__{name}__`
+ */
+name: string, access: TableAccess, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type VaultCheckOptions = { 
+/**
+ * Fehlende CRDT-Trigger neu erstellen und den HLC auf den neuesten Log-Eintrag vorziehen
+ */
+repair: boolean, 
+/**
+ * Nach der Prüfung VACUUM ausführen
+ */
+vacuum: boolean, };
@@ -0,0 +1,25 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ForeignKeyViolation } from "./ForeignKeyViolation";
+import type { OrphanedLogEntries } from "./OrphanedLogEntries";
+
+export type VaultCheckReport = { 
+/**
+ * true, wenn nach einer eventuellen Reparatur keine Probleme mehr bestehen
+ */
+ok: boolean, integrityErrors: Array<string>, cipherIntegrityErrors: Array<string>, foreignKeyViolations: Array<ForeignKeyViolation>, 
+/**
+ * Synchronisierte Tabellen ohne vollständige CRDT-Trigger
+ */
+tablesMissingTriggers: Array<string>, orphanedLogEntries: Array<OrphanedLogEntries>, 
+/**
+ * In haex_crdt_configs gespeicherter HLC-Zeitstempel
+ */
+persistedHlc: string | null, 
+/**
+ * Neuester Zeitstempel in haex_crdt_logs
+ */
+newestLogHlc: string | null, 
+/**
+ * Der gespeicherte HLC liegt hinter dem neuesten Log-Eintrag
+ */
+hlcBehindLogs: boolean, repairedTriggers: Array<string>, hlcRepaired: boolean, vacuumed: boolean, };
@@ -1,3 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type VaultInfo = { name: string, lastAccess: bigint, path: string, };
+export type VaultInfo = { name: string, 
+/**
+ * Letztes Öffnen laut Metadaten, sonst Änderungszeit der Datei
+ */
+lastAccess: bigint, path: string, displayName: string | null, icon: string | null, color: string | null, createdAt: bigint | null, lastOpenedAt: bigint | null, schemaVersion: bigint | null, sizeBytes: bigint, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type VaultMetadata = { displayName: string | null, icon: string | null, color: string | null, 
+/**
+ * Unix-Zeit in Sekunden
+ */
+createdAt: bigint | null, 
+/**
+ * Unix-Zeit in Sekunden, wird von initialize_session gesetzt
+ */
+lastOpenedAt: bigint | null, 
+/**
+ * Zuletzt angewendete Migration (siehe migrations::get_applied_version)
+ */
+schemaVersion: bigint | null, };
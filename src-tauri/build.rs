@@ -4,5 +4,6 @@ fn main() {
     generator::event_names::generate_event_names();
     generator::table_names::generate_table_names();
     generator::rust_types::generate_rust_types();
+    generator::migrations::generate_migrations();
     tauri_build::build();
 }
@@ -164,6 +164,10 @@ use serde::{Deserialize, Serialize};
       name: tablesNames.haex.extension_permissions.name,
       table: schema.haexExtensionPermissions,
     },
+    {
+      name: tablesNames.haex.extension_publishers.name,
+      table: schema.haexExtensionPublishers,
+    },
     { name: tablesNames.haex.crdt.logs.name, table: schema.haexCrdtLogs },
     {
       name: tablesNames.haex.crdt.snapshots.name,
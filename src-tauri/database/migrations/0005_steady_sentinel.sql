@@ -0,0 +1,10 @@
+CREATE TABLE `haex_extension_publishers` (
+	`id` text PRIMARY KEY NOT NULL,
+	`public_key` text NOT NULL,
+	`display_name` text,
+	`trust_level` text DEFAULT 'known' NOT NULL,
+	`created_at` text DEFAULT (CURRENT_TIMESTAMP),
+	`haex_timestamp` text
+);
+--> statement-breakpoint
+CREATE UNIQUE INDEX `haex_extension_publishers_public_key_unique` ON `haex_extension_publishers` (`public_key`);
\ No newline at end of file
@@ -0,0 +1,6 @@
+CREATE TABLE `haex_extension_migrations` (
+	`table_prefix` text NOT NULL,
+	`name` text NOT NULL,
+	`applied_at` text DEFAULT (CURRENT_TIMESTAMP) NOT NULL,
+	PRIMARY KEY(`table_prefix`, `name`)
+);
\ No newline at end of file
@@ -9,6 +9,7 @@ use std::path::Path;
 #[derive(Debug, Deserialize)]
 struct EventNames {
     extension: HashMap<String, String>,
+    database: HashMap<String, String>,
 }
 
 pub fn generate_event_names() {
@@ -43,6 +44,17 @@ pub fn generate_event_names() {
     }
     code.push('\n');
 
+    // Database Events
+    code.push_str("// --- Database Events ---\n");
+    for (key, value) in &events.database {
+        let const_name = format!("EVENT_DATABASE_{}", to_screaming_snake_case(key));
+        code.push_str(&format!(
+            "pub const {}: &str = \"{}\";\n",
+            const_name, value
+        ));
+    }
+    code.push('\n');
+
     // --- Datei schreiben ---
     let mut f = File::create(&dest_path).expect("Konnte Zieldatei nicht erstellen");
     f.write_all(code.as_bytes())
@@ -0,0 +1,71 @@
+// src-tauri/generator/migrations.rs
+use serde::Deserialize;
+use std::env;
+use std::fs::File;
+use std::io::{BufReader, Write};
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+struct Journal {
+    entries: Vec<JournalEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JournalEntry {
+    idx: u32,
+    when: i64,
+    tag: String,
+}
+
+/// Bettet alle Drizzle-Migrationen aus dem Journal in das Binary ein,
+/// damit der Migrations-Runner ältere Vaults ohne Ressourcenzugriff aktualisieren kann.
+pub fn generate_migrations() {
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR ist nicht gesetzt.");
+    println!("Generiere Migrationen nach {out_dir}");
+    let migrations_dir = Path::new("database/migrations");
+    let journal_path = migrations_dir.join("meta/_journal.json");
+    let dest_path = Path::new(&out_dir).join("migrations.rs");
+
+    let file = File::open(&journal_path).expect("Konnte _journal.json nicht öffnen");
+    let reader = BufReader::new(file);
+    let mut journal: Journal =
+        serde_json::from_reader(reader).expect("Konnte _journal.json nicht parsen");
+
+    journal.entries.sort_by_key(|entry| entry.idx);
+
+    let mut code = String::from(
+        r#"
+// ==================================================================
+// HINWEIS: Diese Datei wurde automatisch von build.rs generiert.
+// Manuelle Änderungen werden bei der nächsten Kompilierung überschrieben!
+// ==================================================================
+
+pub const MIGRATIONS: &[Migration] = &[
+"#,
+    );
+
+    for entry in &journal.entries {
+        let sql_path = migrations_dir
+            .join(format!("{}.sql", entry.tag))
+            .canonicalize()
+            .unwrap_or_else(|_| panic!("Migration {}.sql nicht gefunden", entry.tag));
+
+        code.push_str(&format!(
+            "    Migration {{ tag: \"{}\", created_at: {}, sql: include_str!({:?}) }},\n",
+            entry.tag,
+            entry.when,
+            sql_path.display().to_string()
+        ));
+
+        println!("cargo:rerun-if-changed={}", sql_path.display());
+    }
+
+    code.push_str("];\n");
+
+    // --- Datei schreiben ---
+    let mut f = File::create(&dest_path).expect("Konnte Zieldatei nicht erstellen");
+    f.write_all(code.as_bytes())
+        .expect("Konnte nicht in Zieldatei schreiben");
+
+    println!("cargo:rerun-if-changed=database/migrations/meta/_journal.json");
+}
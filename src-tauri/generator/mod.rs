@@ -1,4 +1,5 @@
 // build/mod.rs
 pub mod event_names;
+pub mod migrations;
 pub mod rust_types;
 pub mod table_names;
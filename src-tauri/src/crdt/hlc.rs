@@ -104,6 +104,14 @@ impl HlcService {
         })
     }
 
+    /// Initialisierter Dienst mit zufälliger Node-ID für Tests ohne AppHandle.
+    #[cfg(test)]
+    pub fn new_for_tests() -> Self {
+        HlcService {
+            hlc: Arc::new(Mutex::new(Some(HLCBuilder::new().build()))),
+        }
+    }
+
     /// Holt die Geräte-ID aus dem Tauri Store oder erstellt eine neue, wenn keine existiert.
     fn get_or_create_device_id(app_handle: &AppHandle) -> Result<String, HlcError> {
         let store_path = PathBuf::from("instance.json");
@@ -117,7 +125,7 @@ impl HlcService {
                 if let Some(s) = value.as_str() {
                     // Das ist unser Erfolgsfall. Wir haben einen &str und können
                     // eine Kopie davon zurückgeben.
-                    println!("Gefundene und validierte Geräte-ID: {s}");
+                    tracing::debug!("Gefundene und validierte Geräte-ID: {s}");
                     if Uuid::parse_str(s).is_ok() {
                         // Erfolgsfall: Der Wert ist ein String UND eine gültige UUID.
                         // Wir können die Funktion direkt mit dem Wert verlassen.
@@ -185,7 +193,7 @@ impl HlcService {
     }
 
     /// Lädt den letzten persistierten Zeitstempel aus der Datenbank.
-    fn load_last_timestamp(conn: &Connection) -> Result<Option<Timestamp>, HlcError> {
+    pub fn load_last_timestamp(conn: &Connection) -> Result<Option<Timestamp>, HlcError> {
         let query = format!("SELECT value FROM {TABLE_CRDT_CONFIGS} WHERE key = ?1");
 
         match conn.query_row(&query, params![HLC_TIMESTAMP_TYPE], |row| {
@@ -203,7 +211,7 @@ impl HlcService {
     }
 
     /// Persistiert einen Zeitstempel in der Datenbank innerhalb einer Transaktion.
-    fn persist_timestamp(tx: &Transaction, timestamp: &Timestamp) -> Result<(), HlcError> {
+    pub fn persist_timestamp(tx: &Transaction, timestamp: &Timestamp) -> Result<(), HlcError> {
         let timestamp_str = timestamp.to_string();
         tx.execute(
             &format!(
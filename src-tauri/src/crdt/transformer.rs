@@ -3,6 +3,7 @@
 use crate::crdt::insert_transformer::InsertTransformer;
 use crate::crdt::trigger::HLC_TIMESTAMP_COLUMN;
 use crate::database::error::DatabaseError;
+use crate::database::fts;
 use crate::table_names::{TABLE_CRDT_CONFIGS, TABLE_CRDT_LOGS};
 use sqlparser::ast::{
     Assignment, AssignmentTarget, ColumnDef, DataType, Expr, Ident, ObjectName, ObjectNamePart,
@@ -65,7 +66,7 @@ impl CrdtTransformer {
     /// Prüft, ob eine Tabelle CRDT-Synchronisation unterstützen soll
     fn is_crdt_sync_table(&self, name: &ObjectName) -> bool {
         let table_name = self.normalize_table_name(name);
-        !self.excluded_tables.contains(table_name.as_ref())
+        !self.excluded_tables.contains(table_name.as_ref()) && !fts::is_fts_table(&table_name)
     }
 
     /// Normalisiert Tabellennamen (entfernt Anführungszeichen)
@@ -1,4 +1,5 @@
 // src-tauri/src/crdt/trigger.rs
+use crate::database::fts;
 use crate::table_names::TABLE_CRDT_LOGS;
 use rusqlite::{Connection, Result as RusqliteResult, Row, Transaction};
 use serde::Serialize;
@@ -26,6 +27,8 @@ pub enum CrdtSetupError {
     },
     /// Die Tabelle hat keinen Primärschlüssel, was eine CRDT-Voraussetzung ist.
     PrimaryKeyMissing { table_name: String },
+    /// Die Trigger des Suchindex konnten nicht wiederhergestellt werden.
+    SearchIndex { table_name: String, reason: String },
 }
 
 // Implementierung, damit unser Error-Typ schön formatiert werden kann.
@@ -43,6 +46,9 @@ impl Display for CrdtSetupError {
             CrdtSetupError::PrimaryKeyMissing { table_name } => {
                 write!(f, "Table '{table_name}' has no primary key")
             }
+            CrdtSetupError::SearchIndex { table_name, reason } => {
+                write!(f, "Search index of table '{table_name}' could not be set up: {reason}")
+            }
         }
     }
 }
@@ -135,6 +141,14 @@ pub fn setup_triggers_for_table(
     tx.execute_batch(&update_trigger_sql)?;
     tx.execute_batch(&delete_trigger_sql)?;
 
+    // Ein vorhandener Suchindex hängt an denselben Zeilen und braucht seine Trigger ebenfalls
+    fts::setup_triggers_for_table(tx, table_name, recreate).map_err(|e| {
+        CrdtSetupError::SearchIndex {
+            table_name: table_name.to_string(),
+            reason: e.to_string(),
+        }
+    })?;
+
     Ok(TriggerSetupResult::Success)
 }
 
@@ -180,6 +194,30 @@ pub fn drop_triggers_for_table(
     Ok(())
 }
 
+/// Prüft, ob alle drei CRDT-Trigger (INSERT, UPDATE, DELETE) für eine Tabelle existieren.
+pub fn triggers_exist_for_table(conn: &Connection, table_name: &str) -> RusqliteResult<bool> {
+    let trigger_names = [
+        INSERT_TRIGGER_TPL.replace("{TABLE_NAME}", table_name),
+        UPDATE_TRIGGER_TPL.replace("{TABLE_NAME}", table_name),
+        DELETE_TRIGGER_TPL.replace("{TABLE_NAME}", table_name),
+    ];
+
+    let mut stmt = conn.prepare_cached(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'trigger' AND tbl_name = ?1 AND name IN (?2, ?3, ?4)",
+    )?;
+    let count: i64 = stmt.query_row(
+        rusqlite::params![
+            table_name,
+            trigger_names[0],
+            trigger_names[1],
+            trigger_names[2]
+        ],
+        |row| row.get(0),
+    )?;
+
+    Ok(count == 3)
+}
+
 /* pub fn recreate_triggers_for_table(
     conn: &mut Connection,
     table_name: &str,
@@ -0,0 +1,356 @@
+// src-tauri/src/database/changes.rs
+// Änderungs-Feed der Schreib-Verbindung: Der Update-Hook sammelt geänderte Zeilen, Commit- und
+// Rollback-Hook entscheiden, ob sie gemeldet werden. Nach dem Commit werden die rowids in
+// Primärschlüssel aufgelöst und an einen Listener (Events an Frontend/Extensions) übergeben.
+
+use crate::crdt::trigger;
+use crate::database::core::convert_value_ref_to_json;
+use crate::database::error::DatabaseError;
+use crate::database::fts;
+use crate::table_names::{TABLE_CRDT_CONFIGS, TABLE_CRDT_LOGS};
+use rusqlite::hooks::Action;
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use ts_rs::TS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeOperation {
+    Insert,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct TableChange {
+    pub table: String,
+    pub op: ChangeOperation,
+    /// Primärschlüssel der Zeile, bei Tabellen ohne Primärschlüssel `{ rowid }`
+    #[ts(type = "Record<string, unknown>")]
+    pub pks: Map<String, JsonValue>,
+}
+
+/// Payload des `database-changed`-Events, ein Event pro Commit
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseChangedEvent {
+    pub vault_id: String,
+    pub changes: Vec<TableChange>,
+}
+
+pub type ChangeListener = Box<dyn Fn(Vec<TableChange>) + Send + Sync>;
+
+#[derive(Debug, Clone)]
+struct RawChange {
+    table: String,
+    op: ChangeOperation,
+    rowid: i64,
+}
+
+/// Hält die Änderungen der laufenden Transaktion und die bereits committeten, noch nicht
+/// gemeldeten Änderungen. Im Hook selbst darf nicht auf die Verbindung zugegriffen werden,
+/// daher werden die Primärschlüssel erst in `flush` aufgelöst.
+#[derive(Default)]
+pub struct ChangeFeed {
+    pending: Mutex<Vec<RawChange>>,
+    committed: Mutex<Vec<RawChange>>,
+    listener: Mutex<Option<ChangeListener>>,
+}
+
+/// Interne Tabellen, deren Änderungen nicht gemeldet werden. Die CRDT-Logs werden trotzdem
+/// mitgeschrieben, weil sie die Primärschlüssel gelöschter Zeilen liefern.
+fn is_reported_table(table_name: &str) -> bool {
+    table_name != TABLE_CRDT_LOGS
+        && table_name != TABLE_CRDT_CONFIGS
+        && !table_name.starts_with("sqlite_")
+        && !fts::is_fts_table(table_name)
+}
+
+impl ChangeFeed {
+    /// Registriert Update-, Commit- und Rollback-Hook auf der Schreib-Verbindung
+    pub fn attach(self: &Arc<Self>, conn: &Connection) {
+        let feed = self.clone();
+        conn.update_hook(Some(
+            move |action: Action, _db: &str, table: &str, rowid: i64| {
+                let op = match action {
+                    Action::SQLITE_INSERT => ChangeOperation::Insert,
+                    Action::SQLITE_UPDATE => ChangeOperation::Update,
+                    Action::SQLITE_DELETE => ChangeOperation::Delete,
+                    _ => return,
+                };
+                if let Ok(mut pending) = feed.pending.lock() {
+                    pending.push(RawChange {
+                        table: table.to_string(),
+                        op,
+                        rowid,
+                    });
+                }
+            },
+        ));
+
+        let feed = self.clone();
+        conn.commit_hook(Some(move || {
+            if let (Ok(mut pending), Ok(mut committed)) =
+                (feed.pending.lock(), feed.committed.lock())
+            {
+                committed.append(&mut pending);
+            }
+            // false = Commit nicht abbrechen
+            false
+        }));
+
+        let feed = self.clone();
+        conn.rollback_hook(Some(move || {
+            if let Ok(mut pending) = feed.pending.lock() {
+                pending.clear();
+            }
+        }));
+    }
+
+    pub fn set_listener(&self, listener: Option<ChangeListener>) {
+        if let Ok(mut current) = self.listener.lock() {
+            *current = listener;
+        }
+    }
+
+    /// Löst die committeten Änderungen auf und übergibt sie an den Listener.
+    /// Muss mit der Schreib-Verbindung aufgerufen werden, nachdem ein Zugriff abgeschlossen ist.
+    pub fn flush(&self, conn: &Connection) -> Result<(), DatabaseError> {
+        let raw = {
+            let mut committed = self
+                .committed
+                .lock()
+                .map_err(|e| DatabaseError::LockError {
+                    reason: e.to_string(),
+                })?;
+            std::mem::take(&mut *committed)
+        };
+        if raw.is_empty() {
+            return Ok(());
+        }
+
+        let listener = self.listener.lock().map_err(|e| DatabaseError::LockError {
+            reason: e.to_string(),
+        })?;
+        let Some(listener) = listener.as_ref() else {
+            return Ok(());
+        };
+
+        let changes = resolve_changes(conn, raw)?;
+        if !changes.is_empty() {
+            listener(changes);
+        }
+        Ok(())
+    }
+}
+
+/// Wandelt rowids in Primärschlüssel um. Eingefügte und geänderte Zeilen werden nachgelesen,
+/// gelöschte existieren nicht mehr - ihre Schlüssel kommen aus den CRDT-Logs derselben
+/// Transaktion (nur synchronisierte Tabellen), sonst bleibt die rowid.
+fn resolve_changes(
+    conn: &Connection,
+    raw: Vec<RawChange>,
+) -> Result<Vec<TableChange>, DatabaseError> {
+    let mut deleted_pks: HashMap<String, VecDeque<Map<String, JsonValue>>> = HashMap::new();
+    let mut seen_log_rows = HashSet::new();
+    for change in raw
+        .iter()
+        .filter(|c| c.table == TABLE_CRDT_LOGS && c.op == ChangeOperation::Insert)
+    {
+        let entry: Option<(String, String)> = conn
+            .prepare_cached(&format!(
+                "SELECT table_name, row_pks FROM {TABLE_CRDT_LOGS} WHERE rowid = ?1 AND op_type = 'DELETE'"
+            ))?
+            .query_row([change.rowid], |row| Ok((row.get(0)?, row.get(1)?)))
+            .optional()?;
+
+        // Ein Delete erzeugt einen Log-Eintrag pro Spalte
+        if let Some((table, row_pks)) = entry {
+            if seen_log_rows.insert((table.clone(), row_pks.clone())) {
+                if let Ok(JsonValue::Object(pks)) = serde_json::from_str(&row_pks) {
+                    deleted_pks.entry(table).or_default().push_back(pks);
+                }
+            }
+        }
+    }
+
+    let mut pk_columns: HashMap<String, Vec<String>> = HashMap::new();
+    let mut changes: Vec<TableChange> = Vec::new();
+
+    for change in raw.into_iter().filter(|c| is_reported_table(&c.table)) {
+        let pks = match change.op {
+            ChangeOperation::Delete => deleted_pks
+                .get_mut(&change.table)
+                .and_then(|pks| pks.pop_front())
+                .unwrap_or_else(|| rowid_pks(change.rowid)),
+            ChangeOperation::Insert | ChangeOperation::Update => {
+                if !pk_columns.contains_key(&change.table) {
+                    let columns = trigger::get_table_schema(conn, &change.table)
+                        .map(|schema| {
+                            schema
+                                .into_iter()
+                                .filter(|c| c.is_pk)
+                                .map(|c| c.name)
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    pk_columns.insert(change.table.clone(), columns);
+                }
+
+                // Innerhalb der Transaktion wieder gelöscht - das Delete wird separat gemeldet
+                match read_pks(
+                    conn,
+                    &change.table,
+                    &pk_columns[&change.table],
+                    change.rowid,
+                )? {
+                    Some(pks) => pks,
+                    None => continue,
+                }
+            }
+        };
+
+        let change = TableChange {
+            table: change.table,
+            op: change.op,
+            pks,
+        };
+        if !changes.contains(&change) {
+            changes.push(change);
+        }
+    }
+
+    Ok(changes)
+}
+
+fn rowid_pks(rowid: i64) -> Map<String, JsonValue> {
+    let mut pks = Map::new();
+    pks.insert("rowid".to_string(), rowid.into());
+    pks
+}
+
+fn read_pks(
+    conn: &Connection,
+    table_name: &str,
+    pk_columns: &[String],
+    rowid: i64,
+) -> Result<Option<Map<String, JsonValue>>, DatabaseError> {
+    if pk_columns.is_empty() {
+        return Ok(Some(rowid_pks(rowid)));
+    }
+
+    let column_list = pk_columns
+        .iter()
+        .map(|c| format!("\"{}\"", c.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut stmt = conn.prepare_cached(&format!(
+        "SELECT {column_list} FROM \"{}\" WHERE rowid = ?1",
+        table_name.replace('"', "\"\"")
+    ))?;
+    let mut rows = stmt.query([rowid])?;
+
+    let Some(row) = rows.next()? else {
+        return Ok(None);
+    };
+    let mut pks = Map::new();
+    for (i, column) in pk_columns.iter().enumerate() {
+        pks.insert(column.clone(), convert_value_ref_to_json(row.get_ref(i)?)?);
+    }
+    Ok(Some(pks))
+}
+
+/// Betroffene Tabellen einer Änderungsliste
+pub fn changed_tables(changes: &[TableChange]) -> HashSet<&str> {
+    changes.iter().map(|c| c.table.as_str()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn setup() -> (Connection, Arc<ChangeFeed>, Arc<Mutex<Vec<TableChange>>>) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "CREATE TABLE notes (id TEXT PRIMARY KEY, title TEXT);
+             CREATE TABLE {TABLE_CRDT_LOGS} (id TEXT PRIMARY KEY, table_name TEXT, op_type TEXT, row_pks TEXT);
+             CREATE TRIGGER notes_delete BEFORE DELETE ON notes BEGIN
+                INSERT INTO {TABLE_CRDT_LOGS} VALUES (OLD.id || '-log', 'notes', 'DELETE', json_object('id', OLD.id));
+             END;"
+        ))
+        .unwrap();
+
+        let feed = Arc::new(ChangeFeed::default());
+        feed.attach(&conn);
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        feed.set_listener(Some(Box::new(move |changes| {
+            sink.lock().unwrap().extend(changes)
+        })));
+        (conn, feed, received)
+    }
+
+    #[test]
+    fn test_reports_committed_changes_with_primary_keys() {
+        let (mut conn, feed, received) = setup();
+
+        conn.execute("INSERT INTO notes VALUES ('a', 'eins')", [])
+            .unwrap();
+        feed.flush(&conn).unwrap();
+
+        // Mehrfache Updates derselben Zeile werden zusammengefasst
+        let tx = conn.transaction().unwrap();
+        tx.execute("INSERT INTO notes VALUES ('b', 'zwei')", [])
+            .unwrap();
+        tx.execute("UPDATE notes SET title = 'drei' WHERE id = 'b'", [])
+            .unwrap();
+        tx.execute("UPDATE notes SET title = 'vier' WHERE id = 'b'", [])
+            .unwrap();
+        tx.execute("DELETE FROM notes WHERE id = 'a'", []).unwrap();
+        tx.commit().unwrap();
+        feed.flush(&conn).unwrap();
+
+        let changes = received.lock().unwrap().clone();
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.table.as_str(), c.op, JsonValue::Object(c.pks.clone())))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("notes", ChangeOperation::Insert, json!({"id": "a"})),
+                ("notes", ChangeOperation::Insert, json!({"id": "b"})),
+                ("notes", ChangeOperation::Update, json!({"id": "b"})),
+                ("notes", ChangeOperation::Delete, json!({"id": "a"})),
+            ]
+        );
+        assert_eq!(changed_tables(&changes), HashSet::from(["notes"]));
+    }
+
+    #[test]
+    fn test_rolled_back_changes_are_not_reported() {
+        let (mut conn, feed, received) = setup();
+
+        let tx = conn.transaction().unwrap();
+        tx.execute("INSERT INTO notes VALUES ('a', 'eins')", [])
+            .unwrap();
+        tx.rollback().unwrap();
+        feed.flush(&conn).unwrap();
+        assert!(received.lock().unwrap().is_empty());
+
+        // Ohne Listener wird verworfen statt gesammelt
+        feed.set_listener(None);
+        conn.execute("INSERT INTO notes VALUES ('b', 'zwei')", [])
+            .unwrap();
+        feed.flush(&conn).unwrap();
+        assert!(feed.committed.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,361 @@
+// src-tauri/src/database/check.rs
+// Integritätsprüfung und Reparatur einer geöffneten Vault
+
+use crate::crdt::hlc::HlcService;
+use crate::crdt::trigger::{self, HLC_TIMESTAMP_COLUMN};
+use crate::database::error::DatabaseError;
+use crate::database::fts;
+use crate::database::init::CRDT_TABLES;
+use crate::table_names::TABLE_CRDT_LOGS;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use ts_rs::TS;
+use uhlc::Timestamp;
+
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultCheckOptions {
+    /// Fehlende CRDT-Trigger neu erstellen und den HLC auf den neuesten Log-Eintrag vorziehen
+    #[serde(default)]
+    pub repair: bool,
+    /// Nach der Prüfung VACUUM ausführen
+    #[serde(default)]
+    pub vacuum: bool,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ForeignKeyViolation {
+    pub table: String,
+    pub rowid: Option<i64>,
+    pub parent: String,
+    pub fk_index: i64,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedLogEntries {
+    /// Tabellenname aus haex_crdt_logs, zu dem keine Tabelle existiert
+    pub table_name: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultCheckReport {
+    /// true, wenn nach einer eventuellen Reparatur keine Probleme mehr bestehen
+    pub ok: bool,
+    pub integrity_errors: Vec<String>,
+    pub cipher_integrity_errors: Vec<String>,
+    pub foreign_key_violations: Vec<ForeignKeyViolation>,
+    /// Synchronisierte Tabellen ohne vollständige CRDT-Trigger
+    pub tables_missing_triggers: Vec<String>,
+    pub orphaned_log_entries: Vec<OrphanedLogEntries>,
+    /// In haex_crdt_configs gespeicherter HLC-Zeitstempel
+    pub persisted_hlc: Option<String>,
+    /// Neuester Zeitstempel in haex_crdt_logs
+    pub newest_log_hlc: Option<String>,
+    /// Der gespeicherte HLC liegt hinter dem neuesten Log-Eintrag
+    pub hlc_behind_logs: bool,
+    pub repaired_triggers: Vec<String>,
+    pub hlc_repaired: bool,
+    pub vacuumed: bool,
+}
+
+/// Prüft die geöffnete Vault auf Beschädigungen und verletzte CRDT-Invarianten.
+///
+/// Ohne `repair` ist die Prüfung rein lesend. Mit `repair` werden fehlende Trigger
+/// neu erstellt und ein zurückliegender HLC in haex_crdt_configs vorgezogen - den
+/// HLC im Speicher muss der Aufrufer selbst aktualisieren.
+pub fn check_vault(
+    conn: &mut Connection,
+    options: &VaultCheckOptions,
+) -> Result<VaultCheckReport, DatabaseError> {
+    let mut report = VaultCheckReport {
+        integrity_errors: run_integrity_check(conn)?,
+        cipher_integrity_errors: run_cipher_integrity_check(conn)?,
+        foreign_key_violations: run_foreign_key_check(conn)?,
+        tables_missing_triggers: find_tables_missing_triggers(conn)?,
+        orphaned_log_entries: find_orphaned_log_entries(conn)?,
+        ..Default::default()
+    };
+
+    let persisted_hlc =
+        HlcService::load_last_timestamp(conn).map_err(|e| DatabaseError::HlcError {
+            reason: e.to_string(),
+        })?;
+    let newest_log_hlc = find_newest_log_timestamp(conn)?;
+
+    report.hlc_behind_logs = match (&persisted_hlc, &newest_log_hlc) {
+        (Some(persisted), Some(newest)) => persisted < newest,
+        (None, Some(_)) => true,
+        _ => false,
+    };
+    report.persisted_hlc = persisted_hlc.map(|ts| ts.to_string());
+    report.newest_log_hlc = newest_log_hlc.map(|ts| ts.to_string());
+
+    if options.repair && (!report.tables_missing_triggers.is_empty() || report.hlc_behind_logs) {
+        let tx = conn.transaction()?;
+
+        for table_name in &report.tables_missing_triggers {
+            tracing::info!("Recreating CRDT triggers for: {table_name}");
+            trigger::setup_triggers_for_table(&tx, table_name, true)?;
+            report.repaired_triggers.push(table_name.clone());
+        }
+
+        if let (true, Some(newest)) = (report.hlc_behind_logs, &newest_log_hlc) {
+            tracing::info!("Advancing persisted HLC to {newest}");
+            HlcService::persist_timestamp(&tx, newest).map_err(|e| DatabaseError::HlcError {
+                reason: e.to_string(),
+            })?;
+            report.hlc_repaired = true;
+        }
+
+        tx.commit()?;
+    }
+
+    if options.vacuum {
+        conn.execute_batch("VACUUM;")
+            .map_err(|e| DatabaseError::ExecutionError {
+                sql: "VACUUM".to_string(),
+                reason: e.to_string(),
+                table: None,
+            })?;
+        report.vacuumed = true;
+
+        // VACUUM darf rowids ohne INTEGER PRIMARY KEY neu vergeben - die Suchindizes zeigen dann ins Leere
+        let tx = conn.transaction()?;
+        fts::rebuild_all_search_indexes(&tx)?;
+        tx.commit()?;
+    }
+
+    report.ok = report.integrity_errors.is_empty()
+        && report.cipher_integrity_errors.is_empty()
+        && report.foreign_key_violations.is_empty()
+        && report.orphaned_log_entries.is_empty()
+        && report.tables_missing_triggers.len() == report.repaired_triggers.len()
+        && (!report.hlc_behind_logs || report.hlc_repaired);
+
+    Ok(report)
+}
+
+fn run_integrity_check(conn: &Connection) -> Result<Vec<String>, DatabaseError> {
+    let rows = collect_pragma_strings(conn, "integrity_check")?;
+    Ok(rows.into_iter().filter(|row| row != "ok").collect())
+}
+
+/// Bei SQLCipher liefert das PRAGMA nur Zeilen für beschädigte Seiten (HMAC-Fehler).
+/// Ohne SQLCipher ist es unbekannt und liefert gar keine Zeilen.
+fn run_cipher_integrity_check(conn: &Connection) -> Result<Vec<String>, DatabaseError> {
+    collect_pragma_strings(conn, "cipher_integrity_check")
+}
+
+fn collect_pragma_strings(conn: &Connection, pragma: &str) -> Result<Vec<String>, DatabaseError> {
+    let map_err = |e: rusqlite::Error| DatabaseError::PragmaError {
+        pragma: pragma.to_string(),
+        reason: e.to_string(),
+    };
+
+    let mut stmt = conn.prepare(&format!("PRAGMA {pragma}")).map_err(map_err)?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(map_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(map_err)?;
+
+    Ok(rows)
+}
+
+fn run_foreign_key_check(conn: &Connection) -> Result<Vec<ForeignKeyViolation>, DatabaseError> {
+    let map_err = |e: rusqlite::Error| DatabaseError::PragmaError {
+        pragma: "foreign_key_check".to_string(),
+        reason: e.to_string(),
+    };
+
+    let mut stmt = conn.prepare("PRAGMA foreign_key_check").map_err(map_err)?;
+    let violations = stmt
+        .query_map([], |row| {
+            Ok(ForeignKeyViolation {
+                table: row.get(0)?,
+                rowid: row.get(1)?,
+                parent: row.get(2)?,
+                fk_index: row.get(3)?,
+            })
+        })
+        .map_err(map_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(map_err)?;
+
+    Ok(violations)
+}
+
+/// Alle Tabellen, deren Änderungen über die CRDT-Logs synchronisiert werden:
+/// die Core-Tabellen aus CRDT_TABLES und alle Extension-Tabellen
+/// (`{public_key}__{name}__{table}`) mit HLC-Spalte.
+pub fn list_synced_tables(conn: &Connection) -> Result<Vec<String>, DatabaseError> {
+    let mut stmt = conn.prepare(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )?;
+    let table_names = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut synced = Vec::new();
+    for table_name in table_names {
+        // Suchindizes sind lokal und werden nach dem Sync neu aufgebaut
+        if fts::is_fts_table(&table_name) {
+            continue;
+        }
+
+        let is_core_table = CRDT_TABLES.contains(&table_name.as_str());
+        let is_extension_table = !table_name.starts_with("__") && table_name.contains("__");
+
+        if !is_core_table && !is_extension_table {
+            continue;
+        }
+
+        let has_hlc_column = trigger::get_table_schema(conn, &table_name)
+            .map(|columns| columns.iter().any(|c| c.name == HLC_TIMESTAMP_COLUMN))
+            .unwrap_or(false);
+
+        if is_core_table || has_hlc_column {
+            synced.push(table_name);
+        }
+    }
+
+    Ok(synced)
+}
+
+fn find_tables_missing_triggers(conn: &Connection) -> Result<Vec<String>, DatabaseError> {
+    let mut missing = Vec::new();
+    for table_name in list_synced_tables(conn)? {
+        if !trigger::triggers_exist_for_table(conn, &table_name)? {
+            missing.push(table_name);
+        }
+    }
+    Ok(missing)
+}
+
+fn find_orphaned_log_entries(conn: &Connection) -> Result<Vec<OrphanedLogEntries>, DatabaseError> {
+    let sql = format!(
+        "SELECT table_name, COUNT(*) FROM {TABLE_CRDT_LOGS}
+         WHERE table_name NOT IN (SELECT name FROM sqlite_master WHERE type = 'table')
+         GROUP BY table_name ORDER BY table_name"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let entries = stmt
+        .query_map([], |row| {
+            Ok(OrphanedLogEntries {
+                table_name: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(entries)
+}
+
+/// Der Zeitanteil eines HLC-Strings ist eine Dezimalzahl ("<ntp64>/<id>"), daher wird
+/// zuerst nach seiner Länge und dann lexikografisch sortiert.
+fn find_newest_log_timestamp(conn: &Connection) -> Result<Option<Timestamp>, DatabaseError> {
+    let sql = format!(
+        "SELECT {HLC_TIMESTAMP_COLUMN} FROM {TABLE_CRDT_LOGS}
+         WHERE {HLC_TIMESTAMP_COLUMN} IS NOT NULL
+         ORDER BY instr({HLC_TIMESTAMP_COLUMN}, '/') DESC, {HLC_TIMESTAMP_COLUMN} DESC
+         LIMIT 1"
+    );
+    let newest: Option<String> = conn.query_row(&sql, [], |row| row.get(0)).optional()?;
+
+    newest
+        .map(|ts| {
+            Timestamp::from_str(&ts).map_err(|e| DatabaseError::HlcError {
+                reason: format!("Invalid timestamp '{ts}' in {TABLE_CRDT_LOGS}: {e:?}"),
+            })
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table_names::{TABLE_CRDT_CONFIGS, TABLE_SETTINGS};
+
+    const TS_OLD: &str = "7386690599959157260/33";
+    const TS_NEW: &str = "7386690599959157261/33";
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "CREATE TABLE {TABLE_CRDT_LOGS} (id TEXT PRIMARY KEY, haex_timestamp TEXT, op_type TEXT, table_name TEXT, row_pks TEXT, column_name TEXT, new_value TEXT, old_value TEXT);
+             CREATE TABLE {TABLE_CRDT_CONFIGS} (key TEXT PRIMARY KEY, value TEXT);
+             CREATE TABLE {TABLE_SETTINGS} (id TEXT PRIMARY KEY, key TEXT, type TEXT, value TEXT, haex_timestamp TEXT);
+             CREATE TABLE \"abc__ext__items\" (id TEXT PRIMARY KEY, title TEXT, haex_timestamp TEXT);"
+        ))
+        .unwrap();
+        conn
+    }
+
+    fn insert_log(conn: &Connection, id: &str, table_name: &str, timestamp: &str) {
+        conn.execute(
+            &format!(
+                "INSERT INTO {TABLE_CRDT_LOGS} (id, haex_timestamp, op_type, table_name, row_pks) VALUES (?1, ?2, 'INSERT', ?3, '{{}}')"
+            ),
+            rusqlite::params![id, timestamp, table_name],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_reports_missing_triggers_and_orphaned_logs() {
+        let mut conn = setup_db();
+        insert_log(&conn, "1", "haex_gone", TS_OLD);
+
+        let report = check_vault(&mut conn, &VaultCheckOptions::default()).unwrap();
+
+        assert!(!report.ok);
+        assert!(report.integrity_errors.is_empty());
+        assert_eq!(
+            report.tables_missing_triggers,
+            vec!["abc__ext__items".to_string(), TABLE_SETTINGS.to_string()]
+        );
+        assert_eq!(report.orphaned_log_entries.len(), 1);
+        assert_eq!(report.orphaned_log_entries[0].table_name, "haex_gone");
+        assert!(report.hlc_behind_logs);
+        assert!(report.repaired_triggers.is_empty());
+    }
+
+    #[test]
+    fn test_repair_recreates_triggers_and_advances_hlc() {
+        let mut conn = setup_db();
+        insert_log(&conn, "1", TABLE_SETTINGS, TS_NEW);
+        insert_log(&conn, "2", TABLE_SETTINGS, TS_OLD);
+        conn.execute(
+            &format!("INSERT INTO {TABLE_CRDT_CONFIGS} (key, value) VALUES ('hlc_timestamp', ?1)"),
+            [TS_OLD],
+        )
+        .unwrap();
+
+        let options = VaultCheckOptions {
+            repair: true,
+            vacuum: true,
+        };
+        let report = check_vault(&mut conn, &options).unwrap();
+
+        assert!(report.ok);
+        assert_eq!(report.repaired_triggers.len(), 2);
+        assert!(report.hlc_repaired);
+        assert_eq!(report.newest_log_hlc.as_deref(), Some(TS_NEW));
+        assert!(report.vacuumed);
+
+        let second = check_vault(&mut conn, &VaultCheckOptions::default()).unwrap();
+        assert!(second.ok);
+        assert!(second.tables_missing_triggers.is_empty());
+        assert!(!second.hlc_behind_logs);
+        assert_eq!(second.persisted_hlc.as_deref(), Some(TS_NEW));
+    }
+}
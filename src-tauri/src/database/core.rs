@@ -2,19 +2,19 @@
 
 use crate::crdt::trigger::UUID_FUNCTION_NAME;
 use crate::database::error::DatabaseError;
+use crate::database::pool::ReadPool;
 use crate::database::DbConnection;
 use crate::extension::database::executor::SqlExecutor;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use rusqlite::functions::FunctionFlags;
 use rusqlite::types::Value as SqlValue;
-use rusqlite::{
-    types::{Value as RusqliteValue, ValueRef},
-    Connection, OpenFlags, ToSql,
-};
-use serde_json::Value as JsonValue;
+use rusqlite::{types::ValueRef, Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value as JsonValue};
 use sqlparser::ast::{Expr, Query, Select, SetExpr, Statement, TableFactor, TableObject};
 use sqlparser::dialect::SQLiteDialect;
 use sqlparser::parser::Parser;
+use std::collections::HashSet;
 use uuid::Uuid;
 
 /// Öffnet und initialisiert eine Datenbank mit Verschlüsselung
@@ -52,17 +52,18 @@ pub fn open_and_init_db(path: &str, key: &str, create: bool) -> Result<Connectio
 
     let journal_mode: String = conn
         .query_row("PRAGMA journal_mode=WAL;", [], |row| row.get(0))
-        .map_err(|e| DatabaseError::PragmaError {
-            pragma: "journal_mode=WAL".to_string(),
-            reason: e.to_string(),
+        .map_err(|e| {
+            // Erster Lesezugriff: mit falschem Schlüssel meldet SQLCipher hier "file is not a database"
+            DatabaseError::from_sqlite(&e, None).unwrap_or_else(|| DatabaseError::PragmaError {
+                pragma: "journal_mode=WAL".to_string(),
+                reason: e.to_string(),
+            })
         })?;
 
     if journal_mode.eq_ignore_ascii_case("wal") {
-        println!("WAL mode successfully enabled.");
+        tracing::debug!("WAL mode successfully enabled.");
     } else {
-        eprintln!(
-            "Failed to enable WAL mode, journal_mode is '{journal_mode}'."
-        );
+        tracing::warn!("Failed to enable WAL mode, journal_mode is '{journal_mode}'.");
     }
 
     Ok(conn)
@@ -111,6 +112,34 @@ pub fn statement_has_returning(statement: &Statement) -> bool {
     }
 }
 
+/// Schlüssel für Werte, die JSON nicht verlustfrei transportieren kann
+pub const BLOB_TAG: &str = "$blob";
+pub const BIGINT_TAG: &str = "$bigint";
+
+/// Größte Ganzzahl, die JavaScript ohne Präzisionsverlust darstellt (2^53 - 1)
+const MAX_SAFE_INTEGER: i64 = 9_007_199_254_740_991;
+
+/// Parameter einer SQL-Anweisung: positionsbasiert als Array (`?`, `?NNN`) oder
+/// benannt als Objekt (`:name`, `@name`, `$name`, `?NNN`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SqlParams {
+    Positional(Vec<JsonValue>),
+    Named(Map<String, JsonValue>),
+}
+
+impl Default for SqlParams {
+    fn default() -> Self {
+        SqlParams::Positional(Vec::new())
+    }
+}
+
+impl From<Vec<JsonValue>> for SqlParams {
+    fn from(params: Vec<JsonValue>) -> Self {
+        SqlParams::Positional(params)
+    }
+}
+
 pub struct ValueConverter;
 
 impl ValueConverter {
@@ -124,6 +153,11 @@ impl ValueConverter {
             JsonValue::Number(n) => {
                 if let Some(i) = n.as_i64() {
                     Ok(SqlValue::Integer(i))
+                } else if n.is_u64() {
+                    // Als REAL würden Stellen verloren gehen
+                    Err(DatabaseError::SerializationError {
+                        reason: format!("Integer {n} exceeds the 64-bit range of SQLite"),
+                    })
                 } else if let Some(f) = n.as_f64() {
                     Ok(SqlValue::Real(f))
                 } else {
@@ -132,6 +166,24 @@ impl ValueConverter {
                 }
             }
             JsonValue::String(s) => Ok(SqlValue::Text(s.clone())),
+            JsonValue::Object(map) if map.len() == 1 && map.contains_key(BLOB_TAG) => {
+                let encoded = map[BLOB_TAG].as_str().unwrap_or_default();
+                STANDARD
+                    .decode(encoded)
+                    .map(SqlValue::Blob)
+                    .map_err(|e| DatabaseError::SerializationError {
+                        reason: format!("Invalid base64 in {BLOB_TAG} param: {e}"),
+                    })
+            }
+            JsonValue::Object(map) if map.len() == 1 && map.contains_key(BIGINT_TAG) => {
+                let digits = map[BIGINT_TAG].as_str().unwrap_or_default();
+                digits
+                    .parse::<i64>()
+                    .map(SqlValue::Integer)
+                    .map_err(|e| DatabaseError::SerializationError {
+                        reason: format!("Invalid {BIGINT_TAG} param '{digits}': {e}"),
+                    })
+            }
             JsonValue::Array(_) | JsonValue::Object(_) => {
                 // Arrays/Objects als JSON-Text speichern
                 serde_json::to_string(json_val)
@@ -146,13 +198,75 @@ impl ValueConverter {
     pub fn convert_params(params: &[JsonValue]) -> Result<Vec<SqlValue>, DatabaseError> {
         params.iter().map(Self::json_to_rusqlite_value).collect()
     }
+
+    /// Bindet Parameter an ein vorbereitetes Statement. Anzahl und Namen werden gegen die
+    /// Platzhalter geprüft, die SQLite im Statement erkannt hat - nicht gegen den SQL-Text.
+    pub fn bind_params(
+        stmt: &mut rusqlite::Statement,
+        params: &SqlParams,
+        sql: &str,
+    ) -> Result<(), DatabaseError> {
+        let expected = stmt.parameter_count();
+
+        match params {
+            SqlParams::Positional(values) => {
+                if values.len() != expected {
+                    return Err(DatabaseError::ParameterMismatchError {
+                        expected,
+                        provided: values.len(),
+                        sql: sql.to_string(),
+                    });
+                }
+                for (i, value) in values.iter().enumerate() {
+                    stmt.raw_bind_parameter(i + 1, Self::json_to_rusqlite_value(value)?)?;
+                }
+            }
+            SqlParams::Named(values) => {
+                let mut used = HashSet::new();
+
+                for index in 1..=expected {
+                    let placeholder = stmt
+                        .parameter_name(index)
+                        .map(str::to_string)
+                        .ok_or_else(|| DatabaseError::ParameterError {
+                            name: "?".to_string(),
+                            reason: "Anonymous placeholders need positional params".to_string(),
+                            sql: sql.to_string(),
+                        })?;
+
+                    // `:id` im SQL passt auf die Schlüssel ":id" und "id", `?2` auf "?2" und "2"
+                    let (key, value) = values
+                        .get_key_value(&placeholder)
+                        .or_else(|| values.get_key_value(&placeholder[1..]))
+                        .ok_or_else(|| DatabaseError::ParameterError {
+                            name: placeholder.clone(),
+                            reason: "No value provided".to_string(),
+                            sql: sql.to_string(),
+                        })?;
+                    used.insert(key.as_str());
+
+                    stmt.raw_bind_parameter(index, Self::json_to_rusqlite_value(value)?)?;
+                }
+
+                if let Some(unknown) = values.keys().find(|key| !used.contains(key.as_str())) {
+                    return Err(DatabaseError::ParameterError {
+                        name: unknown.clone(),
+                        reason: "Parameter does not appear in the statement".to_string(),
+                        sql: sql.to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Execute SQL mit CRDT-Transformation (für Drizzle-Integration)
 /// Diese Funktion sollte von Drizzle verwendet werden, um CRDT-Support zu erhalten
 pub fn execute_with_crdt(
     sql: String,
-    params: Vec<JsonValue>,
+    params: SqlParams,
     connection: &DbConnection,
     hlc_service: &std::sync::MutexGuard<crate::crdt::hlc::HlcService>,
 ) -> Result<Vec<Vec<JsonValue>>, DatabaseError> {
@@ -169,21 +283,16 @@ pub fn execute_with_crdt(
 /// Execute SQL OHNE CRDT-Transformation (für spezielle Fälle)
 pub fn execute(
     sql: String,
-    params: Vec<JsonValue>,
+    params: SqlParams,
     connection: &DbConnection,
 ) -> Result<Vec<Vec<JsonValue>>, DatabaseError> {
-    // Konvertiere Parameter
-    let params_converted: Vec<RusqliteValue> = params
-        .iter()
-        .map(ValueConverter::json_to_rusqlite_value)
-        .collect::<Result<Vec<_>, _>>()?;
-    let params_sql: Vec<&dyn ToSql> = params_converted.iter().map(|v| v as &dyn ToSql).collect();
-
     with_connection(connection, |conn| {
+        let mut stmt = conn.prepare(&sql)?;
+        ValueConverter::bind_params(&mut stmt, &params, &sql)?;
+
         if sql.to_uppercase().contains("RETURNING") {
-            let mut stmt = conn.prepare(&sql)?;
             let num_columns = stmt.column_count();
-            let mut rows = stmt.query(&params_sql[..])?;
+            let mut rows = stmt.raw_query();
             let mut result_vec: Vec<Vec<JsonValue>> = Vec::new();
 
             while let Some(row) = rows.next()? {
@@ -197,13 +306,9 @@ pub fn execute(
             }
             Ok(result_vec)
         } else {
-            conn.execute(&sql, &params_sql[..]).map_err(|e| {
+            stmt.raw_execute().map_err(|e| {
                 let table_name = extract_primary_table_name_from_sql(&sql).unwrap_or(None);
-                DatabaseError::ExecutionError {
-                    sql: sql.clone(),
-                    reason: e.to_string(),
-                    table: table_name,
-                }
+                DatabaseError::execution(e, &sql, table_name)
             })?;
             Ok(vec![])
         }
@@ -212,8 +317,8 @@ pub fn execute(
 
 pub fn select(
     sql: String,
-    params: Vec<JsonValue>,
-    connection: &DbConnection,
+    params: SqlParams,
+    readers: &ReadPool,
 ) -> Result<Vec<Vec<JsonValue>>, DatabaseError> {
     // Validiere SQL-Statement
     let statement = parse_single_statement(&sql)?;
@@ -225,18 +330,12 @@ pub fn select(
         });
     }
 
-    // Konvertiere Parameter
-    let params_converted: Vec<RusqliteValue> = params
-        .iter()
-        .map(ValueConverter::json_to_rusqlite_value)
-        .collect::<Result<Vec<_>, _>>()?;
+    readers.with_reader(|conn| {
+        let mut stmt = conn.prepare_cached(&sql)?;
+        ValueConverter::bind_params(&mut stmt, &params, &sql)?;
 
-    let params_sql: Vec<&dyn ToSql> = params_converted.iter().map(|v| v as &dyn ToSql).collect();
-
-    with_connection(connection, |conn| {
-        let mut stmt = conn.prepare(&sql)?;
         let num_columns = stmt.column_count();
-        let mut rows = stmt.query(&params_sql[..])?;
+        let mut rows = stmt.raw_query();
         let mut result_vec: Vec<Vec<JsonValue>> = Vec::new();
 
         while let Some(row) = rows.next()? {
@@ -254,10 +353,10 @@ pub fn select(
 
 pub fn select_with_crdt(
     sql: String,
-    params: Vec<JsonValue>,
-    connection: &DbConnection,
+    params: SqlParams,
+    readers: &ReadPool,
 ) -> Result<Vec<Vec<JsonValue>>, DatabaseError> {
-    with_connection(connection, |conn| {
+    readers.with_reader(|conn| {
         SqlExecutor::query_select(conn, &sql, &params)
     })
 }
@@ -266,6 +365,10 @@ pub fn select_with_crdt(
 pub fn convert_value_ref_to_json(value_ref: ValueRef) -> Result<JsonValue, DatabaseError> {
     let json_val = match value_ref {
         ValueRef::Null => JsonValue::Null,
+        ValueRef::Integer(i) if !(-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&i) => {
+            // Als JS-Number würden Stellen verloren gehen - als String getaggt zurückgeben
+            json!({ BIGINT_TAG: i.to_string() })
+        }
         ValueRef::Integer(i) => JsonValue::Number(i.into()),
         ValueRef::Real(f) => JsonValue::Number(
             serde_json::Number::from_f64(f).unwrap_or_else(|| serde_json::Number::from(0)),
@@ -275,8 +378,8 @@ pub fn convert_value_ref_to_json(value_ref: ValueRef) -> Result<JsonValue, Datab
             JsonValue::String(s)
         }
         ValueRef::Blob(b) => {
-            // BLOBs als Base64-String zurückgeben
-            JsonValue::String(STANDARD.encode(b))
+            // BLOBs getaggt als Base64 zurückgeben, damit sie als BLOB zurückgeschrieben werden können
+            json!({ BLOB_TAG: STANDARD.encode(b) })
         }
     };
     Ok(json_val)
@@ -469,7 +572,14 @@ where
         reason: "Connection to vault failed".to_string(),
     })?;
 
-    f(conn)
+    let result = f(conn);
+
+    // Was in f committet wurde, jetzt melden - solange die Verbindung noch gesperrt ist
+    if let Err(e) = connection.1.flush(conn) {
+        tracing::warn!("Failed to report database changes: {e}");
+    }
+
+    result
 }
 
 #[cfg(test)]
@@ -584,6 +694,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tagged_values_round_trip() {
+        let blob = json!({ BLOB_TAG: "AAEC/w==" });
+        let big = json!({ BIGINT_TAG: "9007199254740993" });
+
+        let blob_value = ValueConverter::json_to_rusqlite_value(&blob).unwrap();
+        assert_eq!(blob_value, SqlValue::Blob(vec![0, 1, 2, 255]));
+        let big_value = ValueConverter::json_to_rusqlite_value(&big).unwrap();
+        assert_eq!(big_value, SqlValue::Integer(9_007_199_254_740_993));
+
+        assert_eq!(
+            convert_value_ref_to_json(ValueRef::Blob(&[0, 1, 2, 255])).unwrap(),
+            blob
+        );
+        assert_eq!(
+            convert_value_ref_to_json(ValueRef::Integer(9_007_199_254_740_993)).unwrap(),
+            big
+        );
+        // Sichere Ganzzahlen bleiben normale Zahlen
+        assert_eq!(
+            convert_value_ref_to_json(ValueRef::Integer(-MAX_SAFE_INTEGER)).unwrap(),
+            json!(-MAX_SAFE_INTEGER)
+        );
+
+        // Andere Objekte werden weiterhin als JSON-Text gespeichert
+        assert_eq!(
+            ValueConverter::json_to_rusqlite_value(&json!({ "a": 1 })).unwrap(),
+            SqlValue::Text(r#"{"a":1}"#.to_string())
+        );
+        assert!(ValueConverter::json_to_rusqlite_value(&json!(u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_bind_named_params() {
+        let conn = Connection::open_in_memory().unwrap();
+        let sql = "SELECT :name || '-' || $suffix || '-' || ?3";
+        let query = |params: JsonValue| -> Result<String, DatabaseError> {
+            let params: SqlParams = serde_json::from_value(params).unwrap();
+            let mut stmt = conn.prepare(sql)?;
+            ValueConverter::bind_params(&mut stmt, &params, sql)?;
+            let mut rows = stmt.raw_query();
+            Ok(rows.next()?.unwrap().get(0)?)
+        };
+
+        // Schlüssel mit und ohne Präfix
+        assert_eq!(
+            query(json!({ "name": "a", "$suffix": "b", "3": "c" })).unwrap(),
+            "a-b-c"
+        );
+        assert_eq!(query(json!(["a", "b", "c"])).unwrap(), "a-b-c");
+
+        assert!(matches!(
+            query(json!({ "name": "a", "suffix": "b" })),
+            Err(DatabaseError::ParameterError { name, .. }) if name == "?3"
+        ));
+        assert!(matches!(
+            query(json!({ "name": "a", "suffix": "b", "3": "c", "typo": 1 })),
+            Err(DatabaseError::ParameterError { name, .. }) if name == "typo"
+        ));
+        assert!(matches!(
+            query(json!(["a"])),
+            Err(DatabaseError::ParameterMismatchError { expected: 3, .. })
+        ));
+
+        // Anonyme Platzhalter lassen sich nicht benannt binden
+        let mut stmt = conn.prepare("SELECT ?").unwrap();
+        let named = SqlParams::Named(Map::new());
+        assert!(matches!(
+            ValueConverter::bind_params(&mut stmt, &named, "SELECT ?"),
+            Err(DatabaseError::ParameterError { .. })
+        ));
+    }
+
     // Test für die neuen AST-basierten Funktionen
     #[test]
     fn test_extract_table_names_comprehensive() {
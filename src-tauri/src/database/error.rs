@@ -1,13 +1,75 @@
 // src-tauri/src/database/error.rs
+// Der von ts-rs für `DatabaseErrorCode` (`repr(enum)`) erzeugte Code löst diesen Lint aus
+#![allow(clippy::from_str_radix_10)]
 
 use crate::crdt::trigger::CrdtSetupError;
+use rusqlite::ffi;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use ts_rs::TS;
 
+/// Stabile Fehlercodes für das Frontend, ergänzend zu `ExtensionErrorCode`.
+/// Bereiche: 6000 allgemein, 6100 Constraints, 6200 Zustand der Datenbankdatei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TS)]
+#[ts(export, repr(enum))]
+pub enum DatabaseErrorCode {
+    Parse = 6000,
+    ParameterMismatch = 6001,
+    Parameter = 6002,
+    NoTable = 6003,
+    Statement = 6004,
+    Prepare = 6005,
+    Database = 6006,
+    Execution = 6007,
+    Transaction = 6008,
+    UnsupportedStatement = 6009,
+    Hlc = 6010,
+    Lock = 6011,
+    Connection = 6012,
+    Serialization = 6013,
+    Permission = 6014,
+    Query = 6015,
+    RowProcessing = 6016,
+    MutexPoisoned = 6017,
+    ConnectionFailed = 6018,
+    Pragma = 6019,
+    PathResolution = 6020,
+    Io = 6021,
+    CrdtSetup = 6022,
+    Migration = 6023,
+    VaultSchemaTooNew = 6024,
+    Import = 6025,
+    SearchIndex = 6026,
+    VaultNotOpen = 6027,
+    UniqueViolation = 6100,
+    PrimaryKeyViolation = 6101,
+    ForeignKeyViolation = 6102,
+    NotNullViolation = 6103,
+    CheckViolation = 6104,
+    ConstraintViolation = 6105,
+    InvalidVaultKey = 6200,
+    Busy = 6201,
+    Locked = 6202,
+    DiskFull = 6203,
+    ReadOnly = 6204,
+    Corrupt = 6205,
+}
+
+impl Serialize for DatabaseErrorCode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_u16(*self as u16)
+    }
+}
+
+/// Wird zusammen mit `code` serialisiert, siehe `SerializedDatabaseError`
 #[derive(Error, Debug, Serialize, Deserialize, TS)]
 #[ts(export)]
 #[serde(tag = "type", content = "details")]
+// Die abgeleitete Form steht damit als `DatabaseError::serialize` bereit
+#[serde(remote = "Self")]
 pub enum DatabaseError {
     /// Der SQL-Code konnte nicht geparst werden.
     #[error("Failed to parse SQL: {reason} - SQL: {sql}")]
@@ -21,6 +83,14 @@ pub enum DatabaseError {
         sql: String,
     },
 
+    /// Benannter Parameter fehlt, ist unbekannt oder passt nicht zur Platzhalter-Art
+    #[error("Invalid parameter '{name}': {reason}. SQL Statement: {sql}")]
+    ParameterError {
+        name: String,
+        reason: String,
+        sql: String,
+    },
+
     #[error("No table provided in SQL Statement: {sql}")]
     NoTableError { sql: String },
 
@@ -96,14 +166,160 @@ pub enum DatabaseError {
 
     #[error("CRDT setup failed: {0}")]
     CrdtSetup(String),
+
+    /// Eine Schema-Migration konnte nicht angewendet werden
+    #[error("Migration '{migration}' failed: {reason}")]
+    MigrationError { migration: String, reason: String },
+
+    /// Die Vault wurde bereits von einer neueren App-Version migriert
+    #[error("Vault schema version {vault_version} is newer than the latest version known to this app ({app_version:?}). Please update the app.")]
+    VaultSchemaTooNew {
+        vault_version: i64,
+        app_version: Option<i64>,
+    },
+
+    /// Eine Importdatei konnte nicht gelesen oder der Tabelle zugeordnet werden
+    #[error("Import of '{path}' failed at line {line:?}: {reason}")]
+    ImportError {
+        path: String,
+        line: Option<usize>,
+        reason: String,
+    },
+
+    /// Ein Suchindex konnte nicht angelegt, aktualisiert oder abgefragt werden
+    #[error("Search index error on table '{table}': {reason}")]
+    SearchIndexError { table: String, reason: String },
+
+    /// Die angegebene Vault ist nicht (mehr) geöffnet
+    #[error("Vault '{vault_id}' is not open")]
+    VaultNotOpen { vault_id: String },
+
+    /// UNIQUE-Constraint verletzt. `constraint` ist gesetzt, wenn SQLite nur den Indexnamen meldet.
+    #[error("Unique constraint failed on table {table:?} (columns: {columns:?}): {reason}")]
+    UniqueViolation {
+        table: Option<String>,
+        columns: Vec<String>,
+        constraint: Option<String>,
+        reason: String,
+    },
+
+    /// Ein Datensatz mit diesem Primärschlüssel existiert bereits
+    #[error("Primary key constraint failed on table {table:?} (columns: {columns:?}): {reason}")]
+    PrimaryKeyViolation {
+        table: Option<String>,
+        columns: Vec<String>,
+        reason: String,
+    },
+
+    /// SQLite nennt bei Foreign-Key-Fehlern weder Tabelle noch Spalte; `table` stammt
+    /// daher aus dem ausgeführten Statement, sofern bekannt.
+    #[error("Foreign key constraint failed on table {table:?}: {reason}")]
+    ForeignKeyViolation {
+        table: Option<String>,
+        reason: String,
+    },
+
+    #[error("NOT NULL constraint failed on {table:?}.{column:?}: {reason}")]
+    NotNullViolation {
+        table: Option<String>,
+        column: Option<String>,
+        reason: String,
+    },
+
+    /// `constraint` ist der Name des CHECK-Constraints oder, falls unbenannt, dessen Ausdruck
+    #[error("CHECK constraint {constraint:?} failed on table {table:?}: {reason}")]
+    CheckViolation {
+        table: Option<String>,
+        constraint: Option<String>,
+        reason: String,
+    },
+
+    /// Sonstige Constraint-Verletzung, z.B. RAISE(ABORT) aus einem Trigger
+    #[error("Constraint failed on table {table:?}: {reason}")]
+    ConstraintViolation {
+        table: Option<String>,
+        reason: String,
+    },
+
+    /// Die Datei ist keine (mit diesem Schlüssel lesbare) Vault - meist ein falsches Passwort
+    #[error("Invalid vault key or not a vault file: {reason}")]
+    InvalidVaultKey { reason: String },
+
+    /// Eine andere Verbindung hält gerade eine Sperre auf der Datenbank
+    #[error("Database is busy: {reason}")]
+    Busy { reason: String },
+
+    /// Eine Tabelle ist innerhalb derselben Verbindung gesperrt
+    #[error("Database table is locked: {reason}")]
+    Locked { reason: String },
+
+    #[error("Disk full: {reason}")]
+    DiskFull { reason: String },
+
+    #[error("Database is read-only: {reason}")]
+    ReadOnly { reason: String },
+
+    #[error("Database file is corrupt: {reason}")]
+    Corrupt { reason: String },
+}
+
+/// Serialisierte Form von `DatabaseError`: Variante und Details samt stabilem `code`
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct SerializedDatabaseError<'a> {
+    pub code: DatabaseErrorCode,
+    #[serde(flatten, serialize_with = "DatabaseError::serialize")]
+    #[ts(flatten)]
+    pub error: &'a DatabaseError,
+}
+
+impl Serialize for DatabaseError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        SerializedDatabaseError {
+            code: self.code(),
+            error: self,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DatabaseError {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // `code` wird beim Einlesen ignoriert, er folgt aus der Variante
+        DatabaseError::deserialize(deserializer)
+    }
 }
 
 impl From<rusqlite::Error> for DatabaseError {
     fn from(err: rusqlite::Error) -> Self {
-        DatabaseError::DatabaseError {
+        DatabaseError::from_sqlite(&err, None).unwrap_or_else(|| DatabaseError::DatabaseError {
             reason: err.to_string(),
+        })
+    }
+}
+
+/// Zerlegt die Detailangabe einer Constraint-Meldung, z.B. "notes.id, notes.title" oder
+/// "index 'idx_notes_title'", in Tabelle, Spalten und Indexname.
+fn parse_constraint_target(detail: &str) -> (Option<String>, Vec<String>, Option<String>) {
+    if let Some(index) = detail.strip_prefix("index ") {
+        return (None, Vec::new(), Some(index.trim_matches('\'').to_string()));
+    }
+
+    let mut table = None;
+    let mut columns = Vec::new();
+    for target in detail.split(", ") {
+        if let Some((tbl, column)) = target.rsplit_once('.') {
+            table.get_or_insert_with(|| tbl.to_string());
+            columns.push(column.to_string());
         }
     }
+    (table, columns, None)
 }
 
 impl From<String> for DatabaseError {
@@ -120,6 +336,122 @@ impl From<CrdtSetupError> for DatabaseError {
 }
 
 impl DatabaseError {
+    /// Ordnet einen SQLite-Fehler anhand seines erweiterten Result-Codes einer eigenen
+    /// Variante zu. `table` ergänzt die Tabelle, wenn SQLite sie nicht nennt.
+    /// Liefert `None` für Fehler ohne eigene Variante.
+    pub fn from_sqlite(err: &rusqlite::Error, table: Option<&str>) -> Option<Self> {
+        let rusqlite::Error::SqliteFailure(failure, message) = err else {
+            return None;
+        };
+        let reason = message.clone().unwrap_or_else(|| failure.to_string());
+        let fallback_table = || table.map(str::to_string);
+
+        let error = match failure.code {
+            ffi::ErrorCode::ConstraintViolation => {
+                let detail = reason
+                    .split_once("constraint failed: ")
+                    .map(|(_, detail)| detail)
+                    .unwrap_or("");
+                let (parsed_table, columns, constraint) = parse_constraint_target(detail);
+                let table = parsed_table.or_else(fallback_table);
+
+                match failure.extended_code {
+                    ffi::SQLITE_CONSTRAINT_UNIQUE => DatabaseError::UniqueViolation {
+                        table,
+                        columns,
+                        constraint,
+                        reason,
+                    },
+                    ffi::SQLITE_CONSTRAINT_PRIMARYKEY => DatabaseError::PrimaryKeyViolation {
+                        table,
+                        columns,
+                        reason,
+                    },
+                    ffi::SQLITE_CONSTRAINT_FOREIGNKEY => {
+                        DatabaseError::ForeignKeyViolation { table, reason }
+                    }
+                    ffi::SQLITE_CONSTRAINT_NOTNULL => DatabaseError::NotNullViolation {
+                        table,
+                        column: columns.into_iter().next(),
+                        reason,
+                    },
+                    ffi::SQLITE_CONSTRAINT_CHECK => DatabaseError::CheckViolation {
+                        table,
+                        constraint: (!detail.is_empty()).then(|| detail.to_string()),
+                        reason,
+                    },
+                    _ => DatabaseError::ConstraintViolation { table, reason },
+                }
+            }
+            ffi::ErrorCode::NotADatabase => DatabaseError::InvalidVaultKey { reason },
+            ffi::ErrorCode::DatabaseBusy => DatabaseError::Busy { reason },
+            ffi::ErrorCode::DatabaseLocked => DatabaseError::Locked { reason },
+            ffi::ErrorCode::DiskFull => DatabaseError::DiskFull { reason },
+            ffi::ErrorCode::ReadOnly => DatabaseError::ReadOnly { reason },
+            ffi::ErrorCode::DatabaseCorrupt => DatabaseError::Corrupt { reason },
+            _ => return None,
+        };
+        Some(error)
+    }
+
+    /// Fehler beim Ausführen eines Statements: strukturiert, falls SQLite einen bekannten
+    /// Code liefert, sonst `ExecutionError`.
+    pub fn execution(err: rusqlite::Error, sql: &str, table: Option<String>) -> Self {
+        DatabaseError::from_sqlite(&err, table.as_deref()).unwrap_or_else(|| {
+            DatabaseError::ExecutionError {
+                sql: sql.to_string(),
+                reason: err.to_string(),
+                table,
+            }
+        })
+    }
+
+    /// Stabiler numerischer Code für das Frontend
+    pub fn code(&self) -> DatabaseErrorCode {
+        match self {
+            DatabaseError::ParseError { .. } => DatabaseErrorCode::Parse,
+            DatabaseError::ParameterMismatchError { .. } => DatabaseErrorCode::ParameterMismatch,
+            DatabaseError::ParameterError { .. } => DatabaseErrorCode::Parameter,
+            DatabaseError::NoTableError { .. } => DatabaseErrorCode::NoTable,
+            DatabaseError::StatementError { .. } => DatabaseErrorCode::Statement,
+            DatabaseError::PrepareError { .. } => DatabaseErrorCode::Prepare,
+            DatabaseError::DatabaseError { .. } => DatabaseErrorCode::Database,
+            DatabaseError::ExecutionError { .. } => DatabaseErrorCode::Execution,
+            DatabaseError::TransactionError { .. } => DatabaseErrorCode::Transaction,
+            DatabaseError::UnsupportedStatement { .. } => DatabaseErrorCode::UnsupportedStatement,
+            DatabaseError::HlcError { .. } => DatabaseErrorCode::Hlc,
+            DatabaseError::LockError { .. } => DatabaseErrorCode::Lock,
+            DatabaseError::ConnectionError { .. } => DatabaseErrorCode::Connection,
+            DatabaseError::SerializationError { .. } => DatabaseErrorCode::Serialization,
+            DatabaseError::PermissionError { .. } => DatabaseErrorCode::Permission,
+            DatabaseError::QueryError { .. } => DatabaseErrorCode::Query,
+            DatabaseError::RowProcessingError { .. } => DatabaseErrorCode::RowProcessing,
+            DatabaseError::MutexPoisoned { .. } => DatabaseErrorCode::MutexPoisoned,
+            DatabaseError::ConnectionFailed { .. } => DatabaseErrorCode::ConnectionFailed,
+            DatabaseError::PragmaError { .. } => DatabaseErrorCode::Pragma,
+            DatabaseError::PathResolutionError { .. } => DatabaseErrorCode::PathResolution,
+            DatabaseError::IoError { .. } => DatabaseErrorCode::Io,
+            DatabaseError::CrdtSetup(_) => DatabaseErrorCode::CrdtSetup,
+            DatabaseError::MigrationError { .. } => DatabaseErrorCode::Migration,
+            DatabaseError::VaultSchemaTooNew { .. } => DatabaseErrorCode::VaultSchemaTooNew,
+            DatabaseError::ImportError { .. } => DatabaseErrorCode::Import,
+            DatabaseError::SearchIndexError { .. } => DatabaseErrorCode::SearchIndex,
+            DatabaseError::VaultNotOpen { .. } => DatabaseErrorCode::VaultNotOpen,
+            DatabaseError::UniqueViolation { .. } => DatabaseErrorCode::UniqueViolation,
+            DatabaseError::PrimaryKeyViolation { .. } => DatabaseErrorCode::PrimaryKeyViolation,
+            DatabaseError::ForeignKeyViolation { .. } => DatabaseErrorCode::ForeignKeyViolation,
+            DatabaseError::NotNullViolation { .. } => DatabaseErrorCode::NotNullViolation,
+            DatabaseError::CheckViolation { .. } => DatabaseErrorCode::CheckViolation,
+            DatabaseError::ConstraintViolation { .. } => DatabaseErrorCode::ConstraintViolation,
+            DatabaseError::InvalidVaultKey { .. } => DatabaseErrorCode::InvalidVaultKey,
+            DatabaseError::Busy { .. } => DatabaseErrorCode::Busy,
+            DatabaseError::Locked { .. } => DatabaseErrorCode::Locked,
+            DatabaseError::DiskFull { .. } => DatabaseErrorCode::DiskFull,
+            DatabaseError::ReadOnly { .. } => DatabaseErrorCode::ReadOnly,
+            DatabaseError::Corrupt { .. } => DatabaseErrorCode::Corrupt,
+        }
+    }
+
     /// Extract extension ID if this error is related to an extension
     pub fn extension_id(&self) -> Option<&str> {
         match self {
@@ -201,3 +533,103 @@ impl DatabaseError {
         }
     }
 } */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    #[test]
+    fn test_constraint_errors_are_mapped() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "PRAGMA foreign_keys = ON;
+             CREATE TABLE notes (
+                 id TEXT PRIMARY KEY,
+                 title TEXT NOT NULL,
+                 slug TEXT,
+                 rating INTEGER CONSTRAINT rating_range CHECK (rating BETWEEN 1 AND 5),
+                 UNIQUE (title, slug)
+             );
+             CREATE TABLE tags (note_id TEXT REFERENCES notes(id));
+             INSERT INTO notes (id, title, slug) VALUES ('a', 'eins', 'x');",
+        )
+        .unwrap();
+
+        let err = |sql: &str| {
+            DatabaseError::execution(
+                conn.execute(sql, []).unwrap_err(),
+                sql,
+                Some("notes".to_string()),
+            )
+        };
+
+        match err("INSERT INTO notes (id, title, slug) VALUES ('b', 'eins', 'x')") {
+            DatabaseError::UniqueViolation { table, columns, .. } => {
+                assert_eq!(table.as_deref(), Some("notes"));
+                assert_eq!(columns, vec!["title", "slug"]);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert_eq!(
+            err("INSERT INTO notes (id, title) VALUES ('a', 'zwei')").code(),
+            DatabaseErrorCode::PrimaryKeyViolation
+        );
+        match err("INSERT INTO notes (id) VALUES ('c')") {
+            DatabaseError::NotNullViolation { column, .. } => {
+                assert_eq!(column.as_deref(), Some("title"))
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        match err("INSERT INTO notes (id, title, rating) VALUES ('d', 'vier', 9)") {
+            DatabaseError::CheckViolation { constraint, .. } => {
+                assert_eq!(constraint.as_deref(), Some("rating_range"))
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        match err("INSERT INTO tags VALUES ('missing')") {
+            // Tabelle kommt aus dem Kontext, SQLite nennt sie nicht
+            DatabaseError::ForeignKeyViolation { table, .. } => {
+                assert_eq!(table.as_deref(), Some("notes"))
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        // Unbekannte Fehler bleiben ExecutionError
+        assert!(matches!(
+            err("INSERT INTO missing VALUES (1)"),
+            DatabaseError::ExecutionError { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_unique_index_target() {
+        assert_eq!(
+            parse_constraint_target("index 'idx_notes_title'"),
+            (None, Vec::new(), Some("idx_notes_title".to_string()))
+        );
+        assert_eq!(
+            serde_json::to_string(&DatabaseErrorCode::InvalidVaultKey).unwrap(),
+            "6200"
+        );
+    }
+
+    #[test]
+    fn test_serialized_error_carries_code_and_details() {
+        let error = DatabaseError::UniqueViolation {
+            table: Some("notes".to_string()),
+            columns: vec!["title".to_string()],
+            constraint: None,
+            reason: "UNIQUE constraint failed: notes.title".to_string(),
+        };
+
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], 6100);
+        assert_eq!(json["type"], "UniqueViolation");
+        assert_eq!(json["details"]["table"], "notes");
+        assert_eq!(json["details"]["columns"][0], "title");
+
+        let parsed: DatabaseError = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.code(), DatabaseErrorCode::UniqueViolation);
+    }
+}
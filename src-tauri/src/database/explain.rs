@@ -0,0 +1,293 @@
+// src-tauri/src/database/explain.rs
+// Diagnose für Extension-Queries: Query-Plan (EXPLAIN QUERY PLAN), Laufzeit und gescannte Zeilen,
+// sowie ein Log langsamer Queries pro Extension für Dev-Extensions.
+
+use crate::database::core::{SqlParams, ValueConverter};
+use crate::database::error::DatabaseError;
+use rusqlite::{Connection, StatementStatus};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use ts_rs::TS;
+
+/// Maximale Anzahl gespeicherter langsamer Queries pro Extension (älteste fliegen raus)
+pub const MAX_SLOW_QUERIES_PER_EXTENSION: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPlanNode {
+    pub id: i64,
+    /// Beschreibung von SQLite, z.B. `SCAN notes` oder `SEARCH notes USING INDEX ...`
+    pub detail: String,
+    pub children: Vec<QueryPlanNode>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryDiagnostics {
+    pub plan: Vec<QueryPlanNode>,
+    pub duration_ms: f64,
+    pub rows_returned: usize,
+    /// Schritte in Full-Table-Scans (SQLITE_STMTSTATUS_FULLSCAN_STEP), Index-Zugriffe zählen nicht
+    pub rows_scanned: usize,
+    /// Sortierungen ohne passenden Index
+    pub sorts: usize,
+    /// Automatisch zur Laufzeit erzeugte Indizes - meist ein Hinweis auf einen fehlenden Index
+    pub auto_indexes: usize,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowQueryEntry {
+    pub sql: String,
+    pub duration_ms: f64,
+    pub plan: Vec<QueryPlanNode>,
+    /// Unix-Zeit in Sekunden
+    pub recorded_at: u64,
+}
+
+/// Liefert den Query-Plan als Baum
+pub fn explain_query_plan(
+    conn: &Connection,
+    sql: &str,
+) -> Result<Vec<QueryPlanNode>, DatabaseError> {
+    let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {sql}"))?;
+
+    // Ungebundene Parameter sind NULL - für den Plan spielt der Wert keine Rolle
+    let mut plan_rows = stmt.raw_query();
+    let mut rows = Vec::new();
+    while let Some(row) = plan_rows.next()? {
+        rows.push((
+            row.get::<_, i64>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(3)?,
+        ));
+    }
+
+    fn children_of(parent: i64, rows: &[(i64, i64, String)]) -> Vec<QueryPlanNode> {
+        rows.iter()
+            .filter(|(_, p, _)| *p == parent)
+            .map(|(id, _, detail)| QueryPlanNode {
+                id: *id,
+                detail: detail.clone(),
+                children: children_of(*id, rows),
+            })
+            .collect()
+    }
+
+    Ok(children_of(0, &rows))
+}
+
+/// Führt eine lesende Query aus und misst Laufzeit und Scan-Verhalten
+pub fn profile_query(
+    conn: &Connection,
+    sql: &str,
+    params: &SqlParams,
+) -> Result<QueryDiagnostics, DatabaseError> {
+    let plan = explain_query_plan(conn, sql)?;
+
+    // Frisch vorbereitet (nicht aus dem Cache), damit die Zähler bei 0 beginnen
+    let mut stmt = conn.prepare(sql)?;
+    if !stmt.readonly() {
+        return Err(DatabaseError::StatementError {
+            reason: "Only read-only statements can be profiled".to_string(),
+        });
+    }
+    ValueConverter::bind_params(&mut stmt, params, sql)?;
+
+    let started = Instant::now();
+    let mut rows_returned = 0;
+    let mut rows = stmt.raw_query();
+    while rows.next()?.is_some() {
+        rows_returned += 1;
+    }
+    drop(rows);
+    let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    let status = |kind| stmt.get_status(kind).max(0) as usize;
+
+    Ok(QueryDiagnostics {
+        plan,
+        duration_ms,
+        rows_returned,
+        rows_scanned: status(StatementStatus::FullscanStep),
+        sorts: status(StatementStatus::Sort),
+        auto_indexes: status(StatementStatus::AutoIndex),
+    })
+}
+
+/// Plan als eingerückte Zeilen, für Log-Ausgaben
+pub fn format_plan(plan: &[QueryPlanNode]) -> String {
+    fn write(nodes: &[QueryPlanNode], depth: usize, out: &mut Vec<String>) {
+        for node in nodes {
+            out.push(format!("{}{}", "  ".repeat(depth), node.detail));
+            write(&node.children, depth + 1, out);
+        }
+    }
+
+    let mut lines = Vec::new();
+    write(plan, 0, &mut lines);
+    lines.join("\n")
+}
+
+struct ExtensionSlowLog {
+    threshold: Duration,
+    entries: VecDeque<SlowQueryEntry>,
+}
+
+/// Log langsamer Queries, nur für Extensions mit gesetztem Schwellwert aktiv
+#[derive(Default)]
+pub struct SlowQueryLog {
+    extensions: Mutex<HashMap<String, ExtensionSlowLog>>,
+}
+
+impl SlowQueryLog {
+    fn lock(
+        &self,
+    ) -> Result<std::sync::MutexGuard<'_, HashMap<String, ExtensionSlowLog>>, DatabaseError> {
+        self.extensions
+            .lock()
+            .map_err(|e| DatabaseError::LockError {
+                reason: e.to_string(),
+            })
+    }
+
+    /// Aktiviert das Log mit Schwellwert bzw. deaktiviert und leert es bei `None`
+    pub fn set_threshold(
+        &self,
+        extension_id: &str,
+        threshold_ms: Option<u32>,
+    ) -> Result<(), DatabaseError> {
+        let mut extensions = self.lock()?;
+        match threshold_ms {
+            Some(ms) => {
+                let threshold = Duration::from_millis(ms.into());
+                extensions
+                    .entry(extension_id.to_string())
+                    .and_modify(|log| log.threshold = threshold)
+                    .or_insert_with(|| ExtensionSlowLog {
+                        threshold,
+                        entries: VecDeque::new(),
+                    });
+            }
+            None => {
+                extensions.remove(extension_id);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn threshold(&self, extension_id: &str) -> Result<Option<Duration>, DatabaseError> {
+        Ok(self.lock()?.get(extension_id).map(|log| log.threshold))
+    }
+
+    pub fn record(
+        &self,
+        extension_id: &str,
+        sql: &str,
+        duration: Duration,
+        plan: Vec<QueryPlanNode>,
+    ) -> Result<(), DatabaseError> {
+        let mut extensions = self.lock()?;
+        let Some(log) = extensions.get_mut(extension_id) else {
+            return Ok(());
+        };
+
+        if log.entries.len() >= MAX_SLOW_QUERIES_PER_EXTENSION {
+            log.entries.pop_front();
+        }
+        log.entries.push_back(SlowQueryEntry {
+            sql: sql.to_string(),
+            duration_ms: duration.as_secs_f64() * 1000.0,
+            plan,
+            recorded_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        });
+        Ok(())
+    }
+
+    pub fn entries(&self, extension_id: &str) -> Result<Vec<SlowQueryEntry>, DatabaseError> {
+        Ok(self
+            .lock()?
+            .get(extension_id)
+            .map(|log| log.entries.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_reports_scan_and_plan_tree() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE notes (id TEXT PRIMARY KEY, title TEXT);
+             CREATE TABLE tags (note_id TEXT, tag TEXT);
+             INSERT INTO notes VALUES ('a', 'x'), ('b', 'y'), ('c', 'z');
+             INSERT INTO tags VALUES ('a', 'rot');",
+        )
+        .unwrap();
+
+        let report = profile_query(
+            &conn,
+            "SELECT * FROM notes WHERE title != ?",
+            &vec![serde_json::json!("y")].into(),
+        )
+        .unwrap();
+        assert_eq!(report.rows_returned, 2);
+        assert!(report.rows_scanned > 0);
+
+        // Unterabfragen hängen als Kinder unter ihrem Knoten
+        let plan = explain_query_plan(
+            &conn,
+            "SELECT * FROM notes WHERE id IN (SELECT note_id FROM tags WHERE tag = ?)",
+        )
+        .unwrap();
+        assert!(plan.iter().any(|node| !node.children.is_empty()));
+        assert!(format_plan(&plan).contains("\n  "));
+
+        assert!(matches!(
+            profile_query(&conn, "DELETE FROM notes", &SqlParams::default()),
+            Err(DatabaseError::StatementError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_slow_log_keeps_latest_entries_per_extension() {
+        let log = SlowQueryLog::default();
+        log.record("ext", "SELECT 1", Duration::from_millis(5), vec![])
+            .unwrap();
+        assert!(log.entries("ext").unwrap().is_empty());
+
+        log.set_threshold("ext", Some(10)).unwrap();
+        assert_eq!(
+            log.threshold("ext").unwrap(),
+            Some(Duration::from_millis(10))
+        );
+        for i in 0..MAX_SLOW_QUERIES_PER_EXTENSION + 5 {
+            log.record(
+                "ext",
+                &format!("SELECT {i}"),
+                Duration::from_millis(20),
+                vec![],
+            )
+            .unwrap();
+        }
+
+        let entries = log.entries("ext").unwrap();
+        assert_eq!(entries.len(), MAX_SLOW_QUERIES_PER_EXTENSION);
+        assert_eq!(entries[0].sql, "SELECT 5");
+        assert!(log.entries("other").unwrap().is_empty());
+
+        log.set_threshold("ext", None).unwrap();
+        assert!(log.entries("ext").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,518 @@
+// src-tauri/src/database/fts.rs
+// Volltextsuche über FTS5: Pro Tabelle eine FTS5-Tabelle mit externem Inhalt (`content=`),
+// die über Trigger neben den `z_crdt_*`-Triggern aktuell gehalten wird. Die FTS-Tabellen
+// (inkl. ihrer Shadow-Tabellen) sind rein lokal, werden nicht synchronisiert und bei Bedarf
+// aus der Quelltabelle neu aufgebaut.
+
+use crate::crdt::trigger;
+use crate::database::core::convert_value_ref_to_json;
+use crate::database::error::DatabaseError;
+use crate::table_names::{TABLE_CRDT_CONFIGS, TABLE_CRDT_LOGS};
+use rusqlite::{Connection, OptionalExtension, Transaction};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashSet;
+use ts_rs::TS;
+
+/// Präfix der FTS-Tabellen. FTS5 legt dazu Shadow-Tabellen `{name}_data`, `{name}_idx`, ... an,
+/// die damit ebenfalls unter das Präfix fallen.
+pub const FTS_TABLE_PREFIX: &str = "haex_fts_";
+
+const INSERT_TRIGGER_TPL: &str = "haex_fts_{TABLE_NAME}_insert";
+const UPDATE_TRIGGER_TPL: &str = "haex_fts_{TABLE_NAME}_update";
+const DELETE_TRIGGER_TPL: &str = "haex_fts_{TABLE_NAME}_delete";
+
+const DEFAULT_SEARCH_LIMIT: u32 = 50;
+const DEFAULT_SNIPPET_TOKENS: u32 = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchIndexInfo {
+    /// Quelltabelle
+    pub table: String,
+    /// Durchsuchbare Spalten in Index-Reihenfolge
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchOptions {
+    /// Maximale Trefferzahl (Standard: 50)
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    /// Markierung vor/nach Fundstellen im Snippet (Standard: `<mark>` / `</mark>`)
+    pub highlight_start: Option<String>,
+    pub highlight_end: Option<String>,
+    /// Länge des Snippets in Tokens (Standard: 16, maximal 64)
+    pub snippet_tokens: Option<u32>,
+    /// Suchbegriff als FTS5-Query-Syntax (`AND`, `OR`, `NEAR`, `col:term`, ...) statt als Freitext
+    pub raw_query: bool,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    /// Zeile der Quelltabelle
+    #[ts(type = "Record<string, unknown>")]
+    pub row: Map<String, JsonValue>,
+    /// BM25-Relevanz - kleiner ist relevanter, Treffer sind danach sortiert
+    pub rank: f64,
+    /// Ausschnitt aus der am besten passenden Spalte mit markierten Fundstellen
+    pub snippet: String,
+}
+
+/// Name der FTS-Tabelle zu einer Quelltabelle
+pub fn fts_table_name(table_name: &str) -> String {
+    format!("{FTS_TABLE_PREFIX}{table_name}")
+}
+
+/// FTS-Tabellen und ihre Shadow-Tabellen - lokal, nie Teil der CRDT-Synchronisation
+pub fn is_fts_table(table_name: &str) -> bool {
+    table_name.starts_with(FTS_TABLE_PREFIX)
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn index_error(table_name: &str, reason: impl Into<String>) -> DatabaseError {
+    DatabaseError::SearchIndexError {
+        table: table_name.to_string(),
+        reason: reason.into(),
+    }
+}
+
+/// Liefert die Spalten des Suchindex einer Tabelle bzw. `None`, wenn keiner deklariert ist
+pub fn get_search_index(
+    conn: &Connection,
+    table_name: &str,
+) -> Result<Option<SearchIndexInfo>, DatabaseError> {
+    let fts_name = fts_table_name(table_name);
+    let exists = conn
+        .prepare_cached(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1 AND sql LIKE 'CREATE VIRTUAL TABLE%'",
+        )?
+        .query_row([&fts_name], |_| Ok(()))
+        .optional()?
+        .is_some();
+
+    if !exists {
+        return Ok(None);
+    }
+
+    let columns = conn
+        .prepare_cached("SELECT name FROM pragma_table_info(?1) ORDER BY cid")?
+        .query_map([&fts_name], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Some(SearchIndexInfo {
+        table: table_name.to_string(),
+        columns,
+    }))
+}
+
+/// Alle deklarierten Suchindizes, optional auf ein Tabellen-Präfix (z.B. einer Extension) beschränkt
+pub fn list_search_indexes(
+    conn: &Connection,
+    table_prefix: Option<&str>,
+) -> Result<Vec<SearchIndexInfo>, DatabaseError> {
+    let fts_names = conn
+        .prepare(
+            "SELECT name FROM sqlite_master
+             WHERE type = 'table' AND sql LIKE 'CREATE VIRTUAL TABLE%' ORDER BY name",
+        )?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut indexes = Vec::new();
+    for fts_name in fts_names {
+        let Some(table_name) = fts_name.strip_prefix(FTS_TABLE_PREFIX) else {
+            continue;
+        };
+        if table_prefix.is_some_and(|prefix| !table_name.starts_with(prefix)) {
+            continue;
+        }
+        if let Some(index) = get_search_index(conn, table_name)? {
+            indexes.push(index);
+        }
+    }
+
+    Ok(indexes)
+}
+
+/// Legt den Suchindex für eine Tabelle an bzw. ersetzt ihn mit neuen Spalten
+/// und füllt ihn mit dem aktuellen Tabelleninhalt.
+pub fn declare_search_index(
+    tx: &Transaction,
+    table_name: &str,
+    columns: &[String],
+) -> Result<SearchIndexInfo, DatabaseError> {
+    if is_fts_table(table_name) || [TABLE_CRDT_LOGS, TABLE_CRDT_CONFIGS].contains(&table_name) {
+        return Err(index_error(table_name, "Table cannot be indexed"));
+    }
+
+    let schema = trigger::get_table_schema(tx, table_name)
+        .map_err(|e| index_error(table_name, e.to_string()))?;
+    if schema.is_empty() {
+        return Err(index_error(table_name, "Table not found"));
+    }
+
+    if columns.is_empty() {
+        return Err(index_error(table_name, "At least one column is required"));
+    }
+
+    let mut seen = HashSet::new();
+    for column in columns {
+        if !seen.insert(column.as_str()) {
+            return Err(index_error(
+                table_name,
+                format!("Duplicate column '{column}'"),
+            ));
+        }
+        if !schema.iter().any(|c| &c.name == column) {
+            return Err(index_error(
+                table_name,
+                format!("Unknown column '{column}'"),
+            ));
+        }
+    }
+
+    // Externer Inhalt wird über die rowid zugeordnet
+    let table_sql: String = tx.query_row(
+        "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [table_name],
+        |row| row.get(0),
+    )?;
+    if table_sql.to_uppercase().contains("WITHOUT ROWID") {
+        return Err(index_error(
+            table_name,
+            "WITHOUT ROWID tables cannot be indexed",
+        ));
+    }
+
+    drop_search_index(tx, table_name)?;
+
+    let column_list = columns
+        .iter()
+        .map(|c| quote_ident(c))
+        .collect::<Vec<_>>()
+        .join(", ");
+    tx.execute_batch(&format!(
+        "CREATE VIRTUAL TABLE {} USING fts5({column_list}, content={}, tokenize='unicode61 remove_diacritics 2');",
+        quote_ident(&fts_table_name(table_name)),
+        quote_ident(table_name),
+    ))?;
+
+    create_triggers(tx, table_name, columns)?;
+    rebuild_search_index(tx, table_name)?;
+
+    Ok(SearchIndexInfo {
+        table: table_name.to_string(),
+        columns: columns.to_vec(),
+    })
+}
+
+/// Entfernt Suchindex und Trigger einer Tabelle. Kein Fehler, wenn keiner existiert.
+pub fn drop_search_index(tx: &Transaction, table_name: &str) -> Result<(), DatabaseError> {
+    drop_triggers(tx, table_name)?;
+    tx.execute_batch(&format!(
+        "DROP TABLE IF EXISTS {};",
+        quote_ident(&fts_table_name(table_name))
+    ))?;
+    Ok(())
+}
+
+/// Baut den Index komplett aus der Quelltabelle neu auf
+pub fn rebuild_search_index(conn: &Connection, table_name: &str) -> Result<(), DatabaseError> {
+    let fts_name = quote_ident(&fts_table_name(table_name));
+    conn.execute_batch(&format!(
+        "INSERT INTO {fts_name}({fts_name}) VALUES('rebuild');"
+    ))?;
+    Ok(())
+}
+
+/// Baut alle Indizes neu auf, z.B. nachdem entfernte Änderungen angewendet wurden oder ein
+/// VACUUM die rowids neu vergeben hat. Indizes verschwundener Tabellen werden entfernt.
+pub fn rebuild_all_search_indexes(tx: &Transaction) -> Result<Vec<String>, DatabaseError> {
+    let mut rebuilt = Vec::new();
+    for index in list_search_indexes(tx, None)? {
+        if trigger::get_table_schema(tx, &index.table)
+            .map(|schema| schema.is_empty())
+            .unwrap_or(true)
+        {
+            tracing::info!("Dropping search index of missing table: {}", index.table);
+            drop_search_index(tx, &index.table)?;
+            continue;
+        }
+
+        rebuild_search_index(tx, &index.table)?;
+        rebuilt.push(index.table);
+    }
+    Ok(rebuilt)
+}
+
+/// Stellt die FTS-Trigger einer Tabelle wieder her, falls sie einen Suchindex hat.
+/// Wird zusammen mit den CRDT-Triggern aufgerufen. Fehlten die Trigger oder sollen sie neu
+/// erzeugt werden, wurde die Tabelle evtl. neu angelegt - dann wird auch der Index neu aufgebaut.
+pub fn setup_triggers_for_table(
+    tx: &Transaction,
+    table_name: &str,
+    recreate: bool,
+) -> Result<(), DatabaseError> {
+    let Some(index) = get_search_index(tx, table_name)? else {
+        return Ok(());
+    };
+
+    let schema = trigger::get_table_schema(tx, table_name)
+        .map_err(|e| index_error(table_name, e.to_string()))?;
+    if let Some(missing) = index
+        .columns
+        .iter()
+        .find(|column| !schema.iter().any(|c| &c.name == *column))
+    {
+        tracing::warn!(
+            "Dropping search index of {table_name}: column '{missing}' no longer exists"
+        );
+        return drop_search_index(tx, table_name);
+    }
+
+    if recreate || !triggers_exist(tx, table_name)? {
+        drop_triggers(tx, table_name)?;
+        create_triggers(tx, table_name, &index.columns)?;
+        rebuild_search_index(tx, table_name)?;
+    }
+    Ok(())
+}
+
+fn create_triggers(
+    tx: &Transaction,
+    table_name: &str,
+    columns: &[String],
+) -> Result<(), DatabaseError> {
+    let fts_name = quote_ident(&fts_table_name(table_name));
+    let table = quote_ident(table_name);
+    let column_list = columns
+        .iter()
+        .map(|c| quote_ident(c))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let values = |row: &str| {
+        columns
+            .iter()
+            .map(|c| format!("{row}.{}", quote_ident(c)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let (new_values, old_values) = (values("NEW"), values("OLD"));
+
+    let insert_trigger = quote_ident(&INSERT_TRIGGER_TPL.replace("{TABLE_NAME}", table_name));
+    let update_trigger = quote_ident(&UPDATE_TRIGGER_TPL.replace("{TABLE_NAME}", table_name));
+    let delete_trigger = quote_ident(&DELETE_TRIGGER_TPL.replace("{TABLE_NAME}", table_name));
+
+    tx.execute_batch(&format!(
+        "CREATE TRIGGER IF NOT EXISTS {insert_trigger} AFTER INSERT ON {table} BEGIN
+            INSERT INTO {fts_name}(rowid, {column_list}) VALUES (NEW.rowid, {new_values});
+         END;
+         CREATE TRIGGER IF NOT EXISTS {delete_trigger} AFTER DELETE ON {table} BEGIN
+            INSERT INTO {fts_name}({fts_name}, rowid, {column_list}) VALUES ('delete', OLD.rowid, {old_values});
+         END;
+         CREATE TRIGGER IF NOT EXISTS {update_trigger} AFTER UPDATE OF {column_list} ON {table} BEGIN
+            INSERT INTO {fts_name}({fts_name}, rowid, {column_list}) VALUES ('delete', OLD.rowid, {old_values});
+            INSERT INTO {fts_name}(rowid, {column_list}) VALUES (NEW.rowid, {new_values});
+         END;"
+    ))?;
+    Ok(())
+}
+
+fn triggers_exist(conn: &Connection, table_name: &str) -> Result<bool, DatabaseError> {
+    let count: i64 = conn
+        .prepare_cached(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'trigger' AND tbl_name = ?1 AND name IN (?2, ?3, ?4)",
+        )?
+        .query_row(
+            rusqlite::params![
+                table_name,
+                INSERT_TRIGGER_TPL.replace("{TABLE_NAME}", table_name),
+                UPDATE_TRIGGER_TPL.replace("{TABLE_NAME}", table_name),
+                DELETE_TRIGGER_TPL.replace("{TABLE_NAME}", table_name),
+            ],
+            |row| row.get(0),
+        )?;
+    Ok(count == 3)
+}
+
+fn drop_triggers(tx: &Transaction, table_name: &str) -> Result<(), DatabaseError> {
+    for tpl in [INSERT_TRIGGER_TPL, UPDATE_TRIGGER_TPL, DELETE_TRIGGER_TPL] {
+        tx.execute_batch(&format!(
+            "DROP TRIGGER IF EXISTS {};",
+            quote_ident(&tpl.replace("{TABLE_NAME}", table_name))
+        ))?;
+    }
+    Ok(())
+}
+
+/// Wandelt Freitext in eine FTS5-Query: jedes Wort als Phrase (Sonderzeichen verlieren ihre
+/// Bedeutung), das letzte Wort zusätzlich als Präfix für Suche während der Eingabe.
+fn free_text_to_match_query(text: &str) -> Option<String> {
+    let mut terms: Vec<String> = text
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    terms.last_mut()?.push('*');
+    Some(terms.join(" "))
+}
+
+/// Durchsucht den Index einer Tabelle, sortiert nach BM25
+pub fn search(
+    conn: &Connection,
+    table_name: &str,
+    query: &str,
+    options: &SearchOptions,
+) -> Result<Vec<SearchHit>, DatabaseError> {
+    if get_search_index(conn, table_name)?.is_none() {
+        return Err(index_error(table_name, "No search index declared"));
+    }
+
+    let match_query = if options.raw_query {
+        query.to_string()
+    } else {
+        match free_text_to_match_query(query) {
+            Some(match_query) => match_query,
+            None => return Ok(Vec::new()),
+        }
+    };
+
+    let fts_name = quote_ident(&fts_table_name(table_name));
+    let sql = format!(
+        "SELECT src.*, bm25({fts_name}), snippet({fts_name}, -1, ?2, ?3, '…', ?4)
+         FROM {fts_name} JOIN {} AS src ON src.rowid = {fts_name}.rowid
+         WHERE {fts_name} MATCH ?1
+         ORDER BY bm25({fts_name})
+         LIMIT ?5 OFFSET ?6",
+        quote_ident(table_name)
+    );
+
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let column_names: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+    let source_columns = column_names.len() - 2;
+
+    let mut rows = stmt
+        .query(rusqlite::params![
+            match_query,
+            options.highlight_start.as_deref().unwrap_or("<mark>"),
+            options.highlight_end.as_deref().unwrap_or("</mark>"),
+            options
+                .snippet_tokens
+                .unwrap_or(DEFAULT_SNIPPET_TOKENS)
+                .clamp(1, 64),
+            options.limit.unwrap_or(DEFAULT_SEARCH_LIMIT),
+            options.offset.unwrap_or(0),
+        ])
+        .map_err(|e| index_error(table_name, format!("Invalid search query: {e}")))?;
+
+    let mut hits = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .map_err(|e| index_error(table_name, format!("Invalid search query: {e}")))?
+    {
+        let mut values = Map::new();
+        for (i, name) in column_names.iter().take(source_columns).enumerate() {
+            values.insert(name.clone(), convert_value_ref_to_json(row.get_ref(i)?)?);
+        }
+        hits.push(SearchHit {
+            row: values,
+            rank: row.get(source_columns)?,
+            snippet: row.get(source_columns + 1)?,
+        });
+    }
+
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE notes (id TEXT PRIMARY KEY, title TEXT, body TEXT, haex_timestamp TEXT);
+             INSERT INTO notes VALUES ('a', 'Einkaufsliste', 'Äpfel und Birnen kaufen', NULL);
+             INSERT INTO notes VALUES ('b', 'Urlaub', 'Flug buchen, Äpfel nicht vergessen', NULL);",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_index_follows_table_changes() {
+        let mut conn = setup();
+        let tx = conn.transaction().unwrap();
+        let index =
+            declare_search_index(&tx, "notes", &["title".to_string(), "body".to_string()]).unwrap();
+        tx.commit().unwrap();
+        assert_eq!(get_search_index(&conn, "notes").unwrap(), Some(index));
+
+        // Bestehende Zeilen sind indexiert, Umlaute werden normalisiert
+        let hits = search(&conn, "notes", "apfel", &SearchOptions::default()).unwrap();
+        assert_eq!(hits.len(), 2);
+
+        let hits = search(&conn, "notes", "urlaub", &SearchOptions::default()).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].row["id"], "b");
+
+        conn.execute("UPDATE notes SET body = 'Koffer packen' WHERE id = 'b'", [])
+            .unwrap();
+        conn.execute("DELETE FROM notes WHERE id = 'a'", [])
+            .unwrap();
+        conn.execute(
+            "INSERT INTO notes VALUES ('c', 'Koffer', 'Noch ein Koffer', NULL)",
+            [],
+        )
+        .unwrap();
+
+        assert!(search(&conn, "notes", "äpfel", &SearchOptions::default())
+            .unwrap()
+            .is_empty());
+
+        let hits = search(&conn, "notes", "koff", &SearchOptions::default()).unwrap();
+        assert_eq!(hits.len(), 2);
+        // "Koffer" in Titel und Text ist relevanter
+        assert_eq!(hits[0].row["id"], "c");
+        assert!(hits[0].snippet.contains("<mark>Koffer</mark>"));
+
+        let tx = conn.transaction().unwrap();
+        assert_eq!(rebuild_all_search_indexes(&tx).unwrap(), vec!["notes"]);
+        drop_search_index(&tx, "notes").unwrap();
+        tx.commit().unwrap();
+        assert!(list_search_indexes(&conn, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rejects_invalid_declarations_and_queries() {
+        let mut conn = setup();
+        let tx = conn.transaction().unwrap();
+        assert!(declare_search_index(&tx, "notes", &[]).is_err());
+        assert!(declare_search_index(&tx, "notes", &["missing".to_string()]).is_err());
+        assert!(declare_search_index(&tx, "missing", &["title".to_string()]).is_err());
+        declare_search_index(&tx, "notes", &["title".to_string()]).unwrap();
+        tx.commit().unwrap();
+
+        // Freitext mit FTS-Sonderzeichen bleibt gültig, roher Syntaxfehler wird gemeldet
+        assert!(search(&conn, "notes", "\"Urlaub AND (", &SearchOptions::default()).is_ok());
+        let raw = SearchOptions {
+            raw_query: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            search(&conn, "notes", "Urlaub AND (", &raw),
+            Err(DatabaseError::SearchIndexError { .. })
+        ));
+
+        assert!(is_fts_table(&fts_table_name("notes")));
+        assert!(is_fts_table("haex_fts_notes_data"));
+    }
+}
@@ -7,6 +7,7 @@ use crate::table_names::{
     TABLE_DESKTOP_ITEMS,
     TABLE_EXTENSIONS,
     TABLE_EXTENSION_PERMISSIONS,
+    TABLE_EXTENSION_PUBLISHERS,
     TABLE_NOTIFICATIONS,
     TABLE_SETTINGS,
     TABLE_WORKSPACES,
@@ -14,10 +15,11 @@ use crate::table_names::{
 use rusqlite::{params, Connection};
 
 /// Liste aller CRDT-Tabellen die Trigger benötigen (ohne Password-Tabellen - die kommen in Extension)
-const CRDT_TABLES: &[&str] = &[
+pub const CRDT_TABLES: &[&str] = &[
     TABLE_SETTINGS,
     TABLE_EXTENSIONS,
     TABLE_EXTENSION_PERMISSIONS,
+    TABLE_EXTENSION_PUBLISHERS,
     TABLE_NOTIFICATIONS,
     TABLE_WORKSPACES,
     TABLE_DESKTOP_ITEMS,
@@ -47,20 +49,20 @@ pub fn ensure_triggers_initialized(conn: &mut Connection) -> Result<bool, Databa
         .ok();
 
     if initialized.is_some() {
-        eprintln!("DEBUG: Triggers already initialized, skipping");
+        tracing::debug!("Triggers already initialized, skipping");
         tx.commit()?; // Wichtig: Transaktion trotzdem abschließen
         return Ok(true); // true = war schon initialisiert
     }
 
-    eprintln!("INFO: Initializing CRDT triggers for database...");
+    tracing::info!("Initializing CRDT triggers for database...");
 
     // Create triggers for all CRDT tables
     for table_name in CRDT_TABLES {
-        eprintln!("  - Setting up triggers for: {table_name}");
+        tracing::debug!("Setting up triggers for: {table_name}");
         trigger::setup_triggers_for_table(&tx, table_name, false)?;
     }
 
     tx.commit()?;
-    eprintln!("INFO: ✓ CRDT triggers created successfully (flag pending)");
+    tracing::info!("CRDT triggers created successfully (flag pending)");
     Ok(false) // false = wurde gerade initialisiert
 }
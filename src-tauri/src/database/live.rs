@@ -0,0 +1,287 @@
+// src-tauri/src/database/live.rs
+// Live-Queries: Ein registriertes SELECT wird nach jedem Commit, der eine seiner Tabellen
+// berührt, erneut ausgeführt. Nur geänderte Ergebnisse werden gemeldet.
+
+use crate::database::core::SqlParams;
+use crate::database::error::DatabaseError;
+use crate::database::query::{query_typed, QueryOptions, QueryResult};
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, MutexGuard};
+use ts_rs::TS;
+
+/// Maximale Anzahl gleichzeitiger Live-Queries pro Vault
+pub const MAX_LIVE_QUERIES: usize = 256;
+
+/// Wer die Live-Query registriert hat und ihre Ergebnisse bekommt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiveQueryOwner {
+    Host,
+    Extension(String),
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveQuerySubscription {
+    pub subscription_id: String,
+    /// Sofortiges erstes Ergebnis
+    pub result: QueryResult,
+}
+
+/// Payload des `live-query-result`-Events
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveQueryResultEvent {
+    pub vault_id: String,
+    pub subscription_id: String,
+    pub result: QueryResult,
+}
+
+struct LiveQuery {
+    sql: String,
+    params: SqlParams,
+    owner: LiveQueryOwner,
+    /// Gelesene Tabellen in Kleinbuchstaben
+    tables: HashSet<String>,
+    /// Die Query liest aus Views oder unbekannten Namen (z.B. CTEs) - dann bei jeder Änderung neu
+    watch_all: bool,
+    result_hash: u64,
+}
+
+#[derive(Default)]
+pub struct LiveQueryRegistry {
+    queries: Mutex<HashMap<String, LiveQuery>>,
+}
+
+fn normalize_table_name(name: &str) -> String {
+    name.trim_matches('"')
+        .trim_matches('`')
+        .trim_matches('[')
+        .trim_matches(']')
+        .to_lowercase()
+}
+
+fn hash_result(result: &QueryResult) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(&result.rows)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+impl LiveQueryRegistry {
+    fn lock(&self) -> Result<MutexGuard<'_, HashMap<String, LiveQuery>>, DatabaseError> {
+        self.queries.lock().map_err(|e| DatabaseError::LockError {
+            reason: e.to_string(),
+        })
+    }
+
+    /// Führt die Query aus und registriert sie. `tables` sind die aus dem SQL extrahierten
+    /// Tabellennamen (siehe `core::extract_table_names_from_statement`).
+    pub fn subscribe(
+        &self,
+        conn: &Connection,
+        sql: String,
+        params: SqlParams,
+        tables: Vec<String>,
+        owner: LiveQueryOwner,
+    ) -> Result<LiveQuerySubscription, DatabaseError> {
+        if self.lock()?.len() >= MAX_LIVE_QUERIES {
+            return Err(DatabaseError::QueryError {
+                reason: format!("Too many live queries (max {MAX_LIVE_QUERIES})"),
+            });
+        }
+
+        let result = query_typed(conn, &sql, &params, &QueryOptions::default())?;
+
+        let tables: HashSet<String> = tables.iter().map(|t| normalize_table_name(t)).collect();
+        let mut watch_all = tables.is_empty();
+        for table in &tables {
+            let kind: Option<String> = conn
+                .prepare_cached("SELECT type FROM sqlite_master WHERE lower(name) = ?1")?
+                .query_row([table], |row| row.get(0))
+                .optional()?;
+            if kind.as_deref() != Some("table") {
+                watch_all = true;
+            }
+        }
+
+        let subscription_id = uuid::Uuid::new_v4().to_string();
+        self.lock()?.insert(
+            subscription_id.clone(),
+            LiveQuery {
+                sql,
+                params,
+                owner,
+                tables,
+                watch_all,
+                result_hash: hash_result(&result),
+            },
+        );
+
+        Ok(LiveQuerySubscription {
+            subscription_id,
+            result,
+        })
+    }
+
+    /// Beendet eine Live-Query. Nur der Eigentümer darf sie beenden.
+    pub fn unsubscribe(
+        &self,
+        subscription_id: &str,
+        owner: &LiveQueryOwner,
+    ) -> Result<bool, DatabaseError> {
+        let mut queries = self.lock()?;
+        match queries.get(subscription_id) {
+            Some(query) if &query.owner == owner => {
+                queries.remove(subscription_id);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Entfernt alle Live-Queries eines Eigentümers, z.B. wenn dessen Fenster geschlossen sind
+    pub fn unsubscribe_owner(&self, owner: &LiveQueryOwner) -> Result<(), DatabaseError> {
+        self.lock()?.retain(|_, query| &query.owner != owner);
+        Ok(())
+    }
+
+    /// Entfernt alle Live-Queries, z.B. wenn die Vault geschlossen wird
+    pub fn unsubscribe_all(&self) -> Result<(), DatabaseError> {
+        self.lock()?.clear();
+        Ok(())
+    }
+
+    /// IDs der Live-Queries, die eine der geänderten Tabellen lesen
+    pub fn affected_by<'a>(
+        &self,
+        changed_tables: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Vec<String>, DatabaseError> {
+        let changed: HashSet<String> = changed_tables
+            .into_iter()
+            .map(normalize_table_name)
+            .collect();
+
+        Ok(self
+            .lock()?
+            .iter()
+            .filter(|(_, query)| query.watch_all || !query.tables.is_disjoint(&changed))
+            .map(|(id, _)| id.clone())
+            .collect())
+    }
+
+    /// Eigentümer aller registrierten Live-Queries
+    pub fn owners(&self) -> Result<Vec<LiveQueryOwner>, DatabaseError> {
+        let mut owners: Vec<LiveQueryOwner> = Vec::new();
+        for query in self.lock()?.values() {
+            if !owners.contains(&query.owner) {
+                owners.push(query.owner.clone());
+            }
+        }
+        Ok(owners)
+    }
+
+    /// Führt die Query erneut aus. Liefert `None`, wenn sie nicht mehr registriert ist
+    /// oder sich das Ergebnis nicht geändert hat.
+    pub fn refresh(
+        &self,
+        conn: &Connection,
+        subscription_id: &str,
+    ) -> Result<Option<(LiveQueryOwner, QueryResult)>, DatabaseError> {
+        // Nicht während der Ausführung sperren, damit subscribe/unsubscribe nicht warten
+        let Some((sql, params)) = self
+            .lock()?
+            .get(subscription_id)
+            .map(|query| (query.sql.clone(), query.params.clone()))
+        else {
+            return Ok(None);
+        };
+
+        let result = query_typed(conn, &sql, &params, &QueryOptions::default())?;
+        let result_hash = hash_result(&result);
+
+        let mut queries = self.lock()?;
+        let Some(query) = queries.get_mut(subscription_id) else {
+            return Ok(None);
+        };
+        if query.result_hash == result_hash {
+            return Ok(None);
+        }
+        query.result_hash = result_hash;
+        Ok(Some((query.owner.clone(), result)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refresh_reports_only_changed_results() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE notes (id TEXT PRIMARY KEY, title TEXT);
+             CREATE TABLE tags (id TEXT PRIMARY KEY);
+             CREATE VIEW note_titles AS SELECT title FROM notes;
+             INSERT INTO notes VALUES ('a', 'eins');",
+        )
+        .unwrap();
+
+        let registry = LiveQueryRegistry::default();
+        let subscription = registry
+            .subscribe(
+                &conn,
+                "SELECT id, title FROM notes ORDER BY id".to_string(),
+                SqlParams::default(),
+                vec!["\"notes\"".to_string()],
+                LiveQueryOwner::Host,
+            )
+            .unwrap();
+        assert_eq!(subscription.result.rows.len(), 1);
+        let id = subscription.subscription_id;
+
+        assert_eq!(registry.affected_by(["NOTES"]).unwrap(), vec![id.clone()]);
+        assert!(registry.affected_by(["tags"]).unwrap().is_empty());
+
+        // Unverändertes Ergebnis wird nicht erneut gemeldet
+        assert!(registry.refresh(&conn, &id).unwrap().is_none());
+
+        conn.execute("INSERT INTO notes VALUES ('b', 'zwei')", [])
+            .unwrap();
+        let (owner, result) = registry.refresh(&conn, &id).unwrap().unwrap();
+        assert_eq!(owner, LiveQueryOwner::Host);
+        assert_eq!(result.rows.len(), 2);
+
+        // Views werden bei jeder Änderung neu ausgewertet
+        let view = registry
+            .subscribe(
+                &conn,
+                "SELECT * FROM note_titles".to_string(),
+                SqlParams::default(),
+                vec!["note_titles".to_string()],
+                LiveQueryOwner::Extension("ext".to_string()),
+            )
+            .unwrap();
+        assert!(registry
+            .affected_by(["tags"])
+            .unwrap()
+            .contains(&view.subscription_id));
+
+        // Nur der Eigentümer darf beenden
+        assert!(!registry
+            .unsubscribe(&view.subscription_id, &LiveQueryOwner::Host)
+            .unwrap());
+        registry
+            .unsubscribe_owner(&LiveQueryOwner::Extension("ext".to_string()))
+            .unwrap();
+        assert_eq!(registry.owners().unwrap(), vec![LiveQueryOwner::Host]);
+        assert!(registry.unsubscribe(&id, &LiveQueryOwner::Host).unwrap());
+        assert!(registry.refresh(&conn, &id).unwrap().is_none());
+    }
+}
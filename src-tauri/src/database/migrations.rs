@@ -0,0 +1,384 @@
+// src-tauri/src/database/migrations.rs
+// Schema-Migrationen für bestehende Vaults (kompatibel mit dem Drizzle-Migrator)
+
+use crate::crdt::trigger;
+use crate::database::error::DatabaseError;
+use crate::database::init::CRDT_TABLES;
+use rusqlite::{params, Connection, Transaction};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use ts_rs::TS;
+
+/// Name der Tabelle, in der Drizzle die angewendeten Migrationen protokolliert.
+/// Wir verwenden dieselbe Tabelle, damit Vaults aus dem Template (von drizzle-kit migriert)
+/// und vom Rust-Runner migrierte Vaults identisch aussehen.
+pub const MIGRATIONS_TABLE: &str = "__drizzle_migrations";
+
+/// Trennzeichen zwischen einzelnen Statements in Drizzle-Migrationsdateien
+const STATEMENT_BREAKPOINT: &str = "--> statement-breakpoint";
+
+/// Eine in das Binary eingebettete Migration (siehe generator/migrations.rs)
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Dateiname ohne Endung, z.B. "0004_fast_epoch"
+    pub tag: &'static str,
+    /// Zeitstempel aus dem Drizzle-Journal (`when`), dient als Versionsnummer
+    pub created_at: i64,
+    pub sql: &'static str,
+}
+
+impl Migration {
+    /// SHA-256 des Dateiinhalts - identisch zum Hash, den Drizzle speichert
+    pub fn hash(&self) -> String {
+        hex::encode(Sha256::digest(self.sql.as_bytes()))
+    }
+
+    /// Zerlegt die Migration an den Drizzle-Breakpoints in einzelne Statements
+    pub fn statements(&self) -> impl Iterator<Item = &'static str> {
+        self.sql
+            .split(STATEMENT_BREAKPOINT)
+            .map(str::trim)
+            .filter(|stmt| !stmt.is_empty())
+    }
+}
+
+mod embedded {
+    use super::Migration;
+    include!(concat!(env!("OUT_DIR"), "/migrations.rs"));
+}
+
+pub use embedded::MIGRATIONS;
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    /// Letzte angewendete Migration vor dem Lauf (None = leere Vault)
+    pub previous_version: Option<i64>,
+    /// Letzte angewendete Migration nach dem Lauf
+    pub current_version: Option<i64>,
+    /// Tags der in diesem Lauf angewendeten Migrationen
+    pub applied: Vec<String>,
+    /// Tabellen, deren CRDT-Trigger neu erzeugt wurden
+    pub recreated_triggers: Vec<String>,
+}
+
+/// Wendet alle ausstehenden, eingebetteten Migrationen auf die Vault an.
+pub fn run_pending_migrations(conn: &mut Connection) -> Result<MigrationReport, DatabaseError> {
+    apply_migrations(conn, MIGRATIONS)
+}
+
+/// Liest die zuletzt angewendete Migration aus der Vault
+pub fn get_applied_version(conn: &Connection) -> Result<Option<i64>, DatabaseError> {
+    ensure_migrations_table(conn)?;
+
+    conn.query_row(
+        &format!("SELECT MAX(created_at) FROM \"{MIGRATIONS_TABLE}\""),
+        [],
+        |row| row.get::<_, Option<i64>>(0),
+    )
+    .map_err(DatabaseError::from)
+}
+
+fn ensure_migrations_table(conn: &Connection) -> Result<(), DatabaseError> {
+    conn.execute_batch(&format!(
+        "CREATE TABLE IF NOT EXISTS \"{MIGRATIONS_TABLE}\" (id SERIAL PRIMARY KEY, hash text NOT NULL, created_at numeric)"
+    ))
+    .map_err(|e| DatabaseError::MigrationError {
+        migration: MIGRATIONS_TABLE.to_string(),
+        reason: format!("Failed to create migrations table: {e}"),
+    })
+}
+
+/// Wendet die übergebenen Migrationen an, sofern sie neuer als der Stand der Vault sind.
+///
+/// Alle ausstehenden Migrationen laufen in einer einzigen Transaktion. Schlägt eine fehl,
+/// bleibt die Vault unverändert. Vaults mit einem neueren Schema als dem der App werden
+/// abgelehnt, statt sie mit einem veralteten Schema weiter zu beschreiben.
+pub fn apply_migrations(
+    conn: &mut Connection,
+    migrations: &[Migration],
+) -> Result<MigrationReport, DatabaseError> {
+    let previous_version = get_applied_version(conn)?;
+    let app_version = migrations.iter().map(|m| m.created_at).max();
+
+    if let Some(vault_version) = previous_version {
+        if app_version.is_none_or(|app_version| vault_version > app_version) {
+            return Err(DatabaseError::VaultSchemaTooNew {
+                vault_version,
+                app_version,
+            });
+        }
+    }
+
+    let mut pending: Vec<&Migration> = migrations
+        .iter()
+        .filter(|m| previous_version.is_none_or(|version| m.created_at > version))
+        .collect();
+    pending.sort_by_key(|m| m.created_at);
+
+    if pending.is_empty() {
+        return Ok(MigrationReport {
+            previous_version,
+            current_version: previous_version,
+            applied: vec![],
+            recreated_triggers: vec![],
+        });
+    }
+
+    tracing::info!(
+        "Applying {} pending vault migration(s) (vault version: {previous_version:?})",
+        pending.len()
+    );
+
+    // Drizzle-Migrationen bauen Tabellen per "__new_*"-Kopie um und schalten dafür
+    // die Foreign Keys ab. Innerhalb einer Transaktion ist das PRAGMA wirkungslos,
+    // daher muss es vorher gesetzt werden.
+    let foreign_keys_enabled: bool = conn
+        .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+        .map_err(|e| DatabaseError::PragmaError {
+            pragma: "foreign_keys".to_string(),
+            reason: e.to_string(),
+        })?;
+    set_foreign_keys(conn, false)?;
+
+    let result = apply_pending_in_transaction(conn, &pending);
+
+    // Der ursprüngliche Migrationsfehler hat Vorrang vor einem Fehler beim Zurücksetzen
+    let restored = set_foreign_keys(conn, foreign_keys_enabled);
+    let recreated_triggers = match result {
+        Ok(recreated) => {
+            restored?;
+            recreated
+        }
+        Err(e) => {
+            if let Err(restore_error) = restored {
+                tracing::warn!(
+                    "Failed to restore foreign_keys after failed migration: {restore_error}"
+                );
+            }
+            return Err(e);
+        }
+    };
+
+    Ok(MigrationReport {
+        previous_version,
+        current_version: pending.last().map(|m| m.created_at),
+        applied: pending.iter().map(|m| m.tag.to_string()).collect(),
+        recreated_triggers,
+    })
+}
+
+fn apply_pending_in_transaction(
+    conn: &mut Connection,
+    pending: &[&Migration],
+) -> Result<Vec<String>, DatabaseError> {
+    let tx = conn.transaction()?;
+
+    let schemas_before = snapshot_crdt_schemas(&tx)?;
+
+    for migration in pending {
+        tracing::info!("Applying migration: {}", migration.tag);
+
+        for statement in migration.statements() {
+            tx.execute_batch(statement)
+                .map_err(|e| DatabaseError::MigrationError {
+                    migration: migration.tag.to_string(),
+                    reason: format!("{e} - SQL: {statement}"),
+                })?;
+        }
+
+        tx.execute(
+            &format!("INSERT INTO \"{MIGRATIONS_TABLE}\" (hash, created_at) VALUES (?1, ?2)"),
+            params![migration.hash(), migration.created_at],
+        )
+        .map_err(|e| DatabaseError::MigrationError {
+            migration: migration.tag.to_string(),
+            reason: format!("Failed to record migration: {e}"),
+        })?;
+    }
+
+    let recreated = regenerate_changed_triggers(&tx, &schemas_before)?;
+
+    tx.commit()?;
+    tracing::info!("Vault migrations applied");
+
+    Ok(recreated)
+}
+
+fn set_foreign_keys(conn: &Connection, enabled: bool) -> Result<(), DatabaseError> {
+    conn.pragma_update(None, "foreign_keys", enabled)
+        .map_err(|e| DatabaseError::PragmaError {
+            pragma: format!("foreign_keys={enabled}"),
+            reason: e.to_string(),
+        })
+}
+
+/// Spaltenliste und Trigger-Status aller CRDT-Tabellen vor der Migration
+struct TableSnapshot {
+    columns: Vec<String>,
+    has_triggers: bool,
+}
+
+fn snapshot_crdt_schemas(
+    tx: &Transaction,
+) -> Result<HashMap<&'static str, TableSnapshot>, DatabaseError> {
+    let mut snapshots = HashMap::new();
+
+    for table_name in CRDT_TABLES {
+        let columns = trigger::get_table_schema(tx, table_name)?
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        let has_triggers = trigger::triggers_exist_for_table(tx, table_name)?;
+
+        snapshots.insert(
+            *table_name,
+            TableSnapshot {
+                columns,
+                has_triggers,
+            },
+        );
+    }
+
+    Ok(snapshots)
+}
+
+/// Erzeugt die CRDT-Trigger für alle Tabellen neu, deren Spalten sich geändert haben
+/// oder deren Trigger durch einen Tabellen-Umbau (DROP + RENAME) verloren gegangen sind.
+///
+/// Vaults, deren Trigger noch nie initialisiert wurden (frisches Template), bleiben
+/// unberührt - das übernimmt `init::ensure_triggers_initialized`.
+fn regenerate_changed_triggers(
+    tx: &Transaction,
+    schemas_before: &HashMap<&'static str, TableSnapshot>,
+) -> Result<Vec<String>, DatabaseError> {
+    let triggers_initialized = schemas_before.values().any(|s| s.has_triggers);
+    if !triggers_initialized {
+        return Ok(vec![]);
+    }
+
+    let mut recreated = Vec::new();
+
+    for table_name in CRDT_TABLES {
+        let columns: Vec<String> = trigger::get_table_schema(tx, table_name)?
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+
+        if columns.is_empty() {
+            continue;
+        }
+
+        let columns_changed = schemas_before
+            .get(table_name)
+            .is_none_or(|before| before.columns != columns);
+        let triggers_missing = !trigger::triggers_exist_for_table(tx, table_name)?;
+
+        if columns_changed || triggers_missing {
+            tracing::info!("Recreating CRDT triggers for: {table_name}");
+            trigger::setup_triggers_for_table(tx, table_name, true)?;
+            recreated.push(table_name.to_string());
+        }
+    }
+
+    Ok(recreated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CREATE_ITEMS: Migration = Migration {
+        tag: "0000_items",
+        created_at: 100,
+        sql: "CREATE TABLE `items` (`id` text PRIMARY KEY NOT NULL, `haex_timestamp` text);",
+    };
+
+    const ALTER_ITEMS: Migration = Migration {
+        tag: "0001_items_name",
+        created_at: 200,
+        sql: "ALTER TABLE `items` ADD `name` text;--> statement-breakpoint\nCREATE INDEX `idx_items_name` ON `items` (`name`);",
+    };
+
+    #[test]
+    fn test_statements_are_split_at_breakpoints() {
+        let statements: Vec<&str> = ALTER_ITEMS.statements().collect();
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].starts_with("ALTER TABLE"));
+        assert!(statements[1].starts_with("CREATE INDEX"));
+    }
+
+    #[test]
+    fn test_applies_only_pending_migrations() {
+        let mut conn = Connection::open_in_memory().unwrap();
+
+        let report = apply_migrations(&mut conn, &[CREATE_ITEMS]).unwrap();
+        assert_eq!(report.previous_version, None);
+        assert_eq!(report.applied, vec!["0000_items"]);
+
+        let report = apply_migrations(&mut conn, &[CREATE_ITEMS, ALTER_ITEMS]).unwrap();
+        assert_eq!(report.previous_version, Some(100));
+        assert_eq!(report.current_version, Some(200));
+        assert_eq!(report.applied, vec!["0001_items_name"]);
+
+        let report = apply_migrations(&mut conn, &[CREATE_ITEMS, ALTER_ITEMS]).unwrap();
+        assert!(report.applied.is_empty());
+    }
+
+    #[test]
+    fn test_records_drizzle_compatible_hash() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        apply_migrations(&mut conn, &[CREATE_ITEMS]).unwrap();
+
+        let hash: String = conn
+            .query_row(
+                &format!("SELECT hash FROM \"{MIGRATIONS_TABLE}\""),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(hash, CREATE_ITEMS.hash());
+    }
+
+    #[test]
+    fn test_failed_migration_is_rolled_back() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        apply_migrations(&mut conn, &[CREATE_ITEMS]).unwrap();
+
+        let broken = Migration {
+            tag: "0001_broken",
+            created_at: 200,
+            sql: "ALTER TABLE `items` ADD `name` text;--> statement-breakpoint\nINVALID SQL;",
+        };
+
+        conn.pragma_update(None, "foreign_keys", true).unwrap();
+        let result = apply_migrations(&mut conn, &[CREATE_ITEMS, broken]);
+        assert!(matches!(result, Err(DatabaseError::MigrationError { .. })));
+
+        let foreign_keys: bool = conn
+            .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+            .unwrap();
+        assert!(foreign_keys);
+
+        assert_eq!(get_applied_version(&conn).unwrap(), Some(100));
+        let columns = trigger::get_table_schema(&conn, "items").unwrap();
+        assert!(!columns.iter().any(|c| c.name == "name"));
+    }
+
+    #[test]
+    fn test_refuses_vault_from_newer_schema() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        apply_migrations(&mut conn, &[CREATE_ITEMS, ALTER_ITEMS]).unwrap();
+
+        let result = apply_migrations(&mut conn, &[CREATE_ITEMS]);
+        assert!(matches!(
+            result,
+            Err(DatabaseError::VaultSchemaTooNew {
+                vault_version: 200,
+                app_version: Some(100),
+            })
+        ));
+    }
+}
@@ -1,92 +1,616 @@
 // src-tauri/src/database/mod.rs
 
+pub mod changes;
+pub mod check;
 pub mod core;
 pub mod error;
+pub mod explain;
+pub mod fts;
 pub mod generated;
 pub mod init;
+pub mod live;
+pub mod migrations;
+pub mod pool;
+pub mod query;
+pub mod transfer;
+pub mod vault;
+pub mod vault_meta;
+pub mod worker;
 
 use crate::crdt::hlc::HlcService;
-use crate::database::core::execute_with_crdt;
+use crate::database::core::{execute_with_crdt, SqlParams};
 use crate::database::error::DatabaseError;
+use crate::event_names::{EVENT_DATABASE_CHANGED, EVENT_DATABASE_LIVE_QUERY_RESULT};
 use crate::extension::database::executor::SqlExecutor;
+use crate::logging::redact;
 use crate::table_names::{TABLE_CRDT_CONFIGS, TABLE_SETTINGS};
 use crate::AppState;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::path::Path;
+use std::str::FromStr;
 use std::sync::Mutex;
 use std::time::UNIX_EPOCH;
 use std::{fs, sync::Arc};
-use tauri::{path::BaseDirectory, AppHandle, Manager, State};
+use tauri::{path::BaseDirectory, AppHandle, Emitter, Manager, State};
 use tauri_plugin_fs::FsExt;
 #[cfg(not(target_os = "android"))]
 use trash;
 use ts_rs::TS;
 
-pub struct DbConnection(pub Arc<Mutex<Option<Connection>>>);
+/// Schreib-Verbindung einer Vault und ihr Änderungs-Feed
+pub struct DbConnection(pub Arc<Mutex<Option<Connection>>>, pub Arc<changes::ChangeFeed>);
 
 const VAULT_EXTENSION: &str = ".db";
 const VAULT_DIRECTORY: &str = "vaults";
 
 #[tauri::command]
-pub fn sql_select(
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(%vault_id, sql = %redact::sql(&sql), params = %redact::params(&params)),
+)]
+pub async fn sql_select(
     sql: String,
-    params: Vec<JsonValue>,
+    params: SqlParams,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<Vec<JsonValue>>, DatabaseError> {
-    core::select(sql, params, &state.db)
+    let vault = state.vaults.get(&vault_id)?;
+    vault
+        .read(move |vault| core::select(sql, params, &vault.readers))
+        .await
 }
 
 #[tauri::command]
-pub fn sql_execute(
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(%vault_id, sql = %redact::sql(&sql), params = %redact::params(&params)),
+)]
+pub async fn sql_execute(
     sql: String,
-    params: Vec<JsonValue>,
+    params: SqlParams,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<Vec<JsonValue>>, DatabaseError> {
-    core::execute(sql, params, &state.db)
+    let vault = state.vaults.get(&vault_id)?;
+    vault
+        .write(move |vault| core::execute(sql, params, &vault.db))
+        .await
 }
 
 #[tauri::command]
-pub fn sql_select_with_crdt(
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(%vault_id, sql = %redact::sql(&sql), params = %redact::params(&params)),
+)]
+pub async fn sql_select_with_crdt(
     sql: String,
-    params: Vec<JsonValue>,
+    params: SqlParams,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<Vec<JsonValue>>, DatabaseError> {
-    core::select_with_crdt(sql, params, &state.db)
+    let vault = state.vaults.get(&vault_id)?;
+    vault
+        .read(move |vault| core::select_with_crdt(sql, params, &vault.readers))
+        .await
 }
 
 #[tauri::command]
-pub fn sql_execute_with_crdt(
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(%vault_id, sql = %redact::sql(&sql), params = %redact::params(&params)),
+)]
+pub async fn sql_execute_with_crdt(
     sql: String,
-    params: Vec<JsonValue>,
+    params: SqlParams,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<Vec<JsonValue>>, DatabaseError> {
-    let hlc_service = state.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
-        reason: "Failed to lock HLC service".to_string(),
-    })?;
-    core::execute_with_crdt(sql, params, &state.db, &hlc_service)
+    let vault = state.vaults.get(&vault_id)?;
+    vault
+        .write(move |vault| {
+            let hlc_service = vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                reason: "Failed to lock HLC service".to_string(),
+            })?;
+            core::execute_with_crdt(sql, params, &vault.db, &hlc_service)
+        })
+        .await
 }
 
 #[tauri::command]
-pub fn sql_query_with_crdt(
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(%vault_id, sql = %redact::sql(&sql), params = %redact::params(&params)),
+)]
+pub async fn sql_query_with_crdt(
     sql: String,
-    params: Vec<JsonValue>,
+    params: SqlParams,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<Vec<JsonValue>>, DatabaseError> {
-    let hlc_service = state.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
-        reason: "Failed to lock HLC service".to_string(),
-    })?;
+    let vault = state.vaults.get(&vault_id)?;
+    vault
+        .write(move |vault| {
+            let hlc_service = vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                reason: "Failed to lock HLC service".to_string(),
+            })?;
+
+            core::with_connection(&vault.db, |conn| {
+                let tx = conn.transaction().map_err(DatabaseError::from)?;
+                let (_modified_tables, result) =
+                    SqlExecutor::query_internal(&tx, &hlc_service, &sql, &params)?;
+                tx.commit().map_err(DatabaseError::from)?;
+                Ok(result)
+            })
+        })
+        .await
+}
+
+/// Lesende Query mit benannten Spalten statt positionsbasierter Zeilen
+#[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(%vault_id, sql = %redact::sql(&sql), params = %redact::params(&params)),
+)]
+pub async fn sql_query_typed(
+    sql: String,
+    params: SqlParams,
+    options: Option<query::QueryOptions>,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<query::QueryResult, DatabaseError> {
+    let vault = state.vaults.get(&vault_id)?;
+    let options = options.unwrap_or_default();
+
+    vault
+        .read(move |vault| {
+            vault
+                .readers
+                .with_reader(|conn| query::query_typed(conn, &sql, &params, &options))
+        })
+        .await
+}
+
+/// Registriert ein SELECT als Live-Query: liefert sofort das Ergebnis und danach bei jedem
+/// Commit auf eine der gelesenen Tabellen ein `live-query-result`-Event mit dem neuen Ergebnis
+#[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(%vault_id, sql = %redact::sql(&sql), params = %redact::params(&params)),
+)]
+pub async fn subscribe_live_query(
+    sql: String,
+    params: SqlParams,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<live::LiveQuerySubscription, DatabaseError> {
+    let vault = state.vaults.get(&vault_id)?;
+    let tables = core::extract_table_names_from_statement(&core::parse_single_statement(&sql)?);
+
+    vault
+        .read(move |vault| {
+            vault.readers.with_reader(|conn| {
+                vault
+                    .live_queries
+                    .subscribe(conn, sql, params, tables, live::LiveQueryOwner::Host)
+            })
+        })
+        .await
+}
+
+#[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(%vault_id, %subscription_id),
+)]
+pub fn unsubscribe_live_query(
+    subscription_id: String,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<bool, DatabaseError> {
+    let vault = state.vaults.get(&vault_id)?;
+    vault
+        .live_queries
+        .unsubscribe(&subscription_id, &live::LiveQueryOwner::Host)
+}
+
+/// Prüft die geöffnete Vault (SQLite-/SQLCipher-Integrität, Foreign Keys, CRDT-Invarianten)
+/// und repariert optional fehlende Trigger bzw. einen zurückliegenden HLC.
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_id))]
+pub async fn check_vault(
+    options: Option<check::VaultCheckOptions>,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<check::VaultCheckReport, DatabaseError> {
+    let vault = state.vaults.get(&vault_id)?;
+    let options = options.unwrap_or_default();
+
+    vault
+        .write(move |vault| {
+            let hlc_service = vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                reason: "Failed to lock HLC service".to_string(),
+            })?;
+
+            let report =
+                core::with_connection(&vault.db, |conn| check::check_vault(conn, &options))?;
+
+            // Der persistierte HLC wurde vorgezogen - der HLC im Speicher muss nachziehen
+            if report.hlc_repaired {
+                if let Some(newest) = report
+                    .newest_log_hlc
+                    .as_deref()
+                    .and_then(|ts| uhlc::Timestamp::from_str(ts).ok())
+                {
+                    hlc_service
+                        .update_with_timestamp(&newest)
+                        .map_err(|e| DatabaseError::HlcError {
+                            reason: e.to_string(),
+                        })?;
+                }
+            }
+
+            Ok(report)
+        })
+        .await
+}
+
+/// Exportiert ausgewählte Tabellen bzw. alle eigenen Tabellen einer Extension
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_id))]
+pub async fn export_tables(
+    request: transfer::ExportRequest,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<transfer::ExportedTable>, DatabaseError> {
+    let vault = state.vaults.get(&vault_id)?;
+    let extension_prefix = match &request.extension_id {
+        Some(extension_id) => {
+            let extension = vault
+                .extension_manager
+                .get_extension(extension_id)
+                .ok_or_else(|| DatabaseError::QueryError {
+                    reason: format!("Extension '{extension_id}' not found"),
+                })?;
+            Some(format!(
+                "{}__{}__",
+                extension.manifest.public_key, extension.manifest.name
+            ))
+        }
+        None => None,
+    };
+
+    // Export liest nur - über einen Leser, damit Writes währenddessen weiterlaufen
+    vault
+        .read(move |vault| {
+            vault.readers.with_reader(|conn| {
+                transfer::export_tables(
+                    conn,
+                    &request.tables,
+                    extension_prefix.as_deref(),
+                    request.format,
+                    Path::new(&request.directory),
+                )
+            })
+        })
+        .await
+}
+
+/// Importiert eine JSONL-/CSV-Datei mit CRDT-Zeitstempeln in eine Tabelle (optional als Dry-Run)
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_id))]
+pub async fn import_table(
+    request: transfer::ImportRequest,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<transfer::ImportReport, DatabaseError> {
+    let vault = state.vaults.get(&vault_id)?;
+    vault
+        .write(move |vault| {
+            let hlc_service = vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                reason: "Failed to lock HLC service".to_string(),
+            })?;
+
+            core::with_connection(&vault.db, |conn| {
+                transfer::import_table(conn, &hlc_service, &request)
+            })
+        })
+        .await
+}
+
+/// Deklariert die durchsuchbaren Spalten einer Tabelle und legt den FTS-Index an
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_id, %table))]
+pub async fn declare_search_index(
+    table: String,
+    columns: Vec<String>,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<fts::SearchIndexInfo, DatabaseError> {
+    let vault = state.vaults.get(&vault_id)?;
+    vault
+        .write(move |vault| {
+            core::with_connection(&vault.db, |conn| {
+                let tx = conn.transaction()?;
+                let index = fts::declare_search_index(&tx, &table, &columns)?;
+                tx.commit()?;
+                Ok(index)
+            })
+        })
+        .await
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_id, %table))]
+pub async fn drop_search_index(
+    table: String,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), DatabaseError> {
+    let vault = state.vaults.get(&vault_id)?;
+    vault
+        .write(move |vault| {
+            core::with_connection(&vault.db, |conn| {
+                let tx = conn.transaction()?;
+                fts::drop_search_index(&tx, &table)?;
+                tx.commit()?;
+                Ok(())
+            })
+        })
+        .await
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"), fields(%vault_id))]
+pub async fn list_search_indexes(
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<fts::SearchIndexInfo>, DatabaseError> {
+    let vault = state.vaults.get(&vault_id)?;
+    vault
+        .read(move |vault| {
+            vault
+                .readers
+                .with_reader(|conn| fts::list_search_indexes(conn, None))
+        })
+        .await
+}
+
+/// Baut die Suchindizes aus den Tabellen neu auf, z.B. nachdem entfernte Änderungen
+/// angewendet wurden. Liefert die neu aufgebauten Tabellen.
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_id))]
+pub async fn rebuild_search_indexes(
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, DatabaseError> {
+    let vault = state.vaults.get(&vault_id)?;
+    vault
+        .write(move |vault| {
+            core::with_connection(&vault.db, |conn| {
+                let tx = conn.transaction()?;
+                let rebuilt = fts::rebuild_all_search_indexes(&tx)?;
+                tx.commit()?;
+                Ok(rebuilt)
+            })
+        })
+        .await
+}
+
+/// Volltextsuche in einer Tabelle, Treffer nach Relevanz sortiert
+#[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"), fields(%vault_id, %table))]
+pub async fn search(
+    table: String,
+    query: String,
+    options: Option<fts::SearchOptions>,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<fts::SearchHit>, DatabaseError> {
+    let vault = state.vaults.get(&vault_id)?;
+    let options = options.unwrap_or_default();
+
+    vault
+        .read(move |vault| {
+            vault
+                .readers
+                .with_reader(|conn| fts::search(conn, &table, &query, &options))
+        })
+        .await
+}
+
+/// Meldet committete Änderungen an das Hauptfenster und an die Extension-Fenster dieser Vault
+fn database_change_listener(app_handle: AppHandle, vault_id: String) -> changes::ChangeListener {
+    Box::new(move |changes| {
+        let app_handle = app_handle.clone();
+        let event = changes::DatabaseChangedEvent {
+            vault_id: vault_id.clone(),
+            changes,
+        };
+
+        // Der Listener läuft mit gesperrter Schreib-Verbindung - alles Weitere asynchron
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = app_handle.emit_to("main", EVENT_DATABASE_CHANGED, &event) {
+                tracing::warn!("Failed to emit database changes: {e}");
+            }
 
-    core::with_connection(&state.db, |conn| {
-        let tx = conn.transaction().map_err(DatabaseError::from)?;
-        let (_modified_tables, result) =
-            SqlExecutor::query_internal(&tx, &hlc_service, &sql, &params)?;
-        tx.commit().map_err(DatabaseError::from)?;
-        Ok(result)
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            emit_changes_to_extensions(&app_handle, &event).await;
+
+            refresh_live_queries(&app_handle, &event).await;
+        });
     })
 }
 
+/// Führt die Live-Queries erneut aus, die eine der geänderten Tabellen lesen, und schickt
+/// geänderte Ergebnisse an ihren Eigentümer
+async fn refresh_live_queries(app_handle: &AppHandle, event: &changes::DatabaseChangedEvent) {
+    let state = app_handle.state::<AppState>();
+    let Ok(vault) = state.vaults.get(&event.vault_id) else {
+        return;
+    };
+
+    // Extensions ohne offenes Fenster können keine Ergebnisse mehr empfangen
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    if let (Ok(owners), Ok(windows)) = (
+        vault.live_queries.owners(),
+        state.extension_webview_manager.windows.lock(),
+    ) {
+        for owner in owners {
+            if let live::LiveQueryOwner::Extension(extension_id) = &owner {
+                let has_window = windows.values().any(|window| {
+                    window.vault_id == event.vault_id && &window.extension_id == extension_id
+                });
+                if !has_window {
+                    let _ = vault.live_queries.unsubscribe_owner(&owner);
+                }
+            }
+        }
+    }
+
+    let affected = match vault
+        .live_queries
+        .affected_by(changes::changed_tables(&event.changes))
+    {
+        Ok(affected) if !affected.is_empty() => affected,
+        _ => return,
+    };
+
+    let results = vault
+        .read(move |vault| {
+            vault.readers.with_reader(|conn| {
+                let mut results = Vec::new();
+                for subscription_id in affected {
+                    match vault.live_queries.refresh(conn, &subscription_id) {
+                        Ok(Some((owner, result))) => results.push((subscription_id, owner, result)),
+                        Ok(None) => {}
+                        Err(e) => tracing::warn!("Live query {subscription_id} failed: {e}"),
+                    }
+                }
+                Ok(results)
+            })
+        })
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to refresh live queries: {e}");
+            Vec::new()
+        });
+
+    for (subscription_id, owner, result) in results {
+        let payload = live::LiveQueryResultEvent {
+            vault_id: event.vault_id.clone(),
+            subscription_id,
+            result,
+        };
+
+        let emitted = match &owner {
+            live::LiveQueryOwner::Host => app_handle
+                .emit_to("main", EVENT_DATABASE_LIVE_QUERY_RESULT, &payload)
+                .map_err(|e| e.to_string()),
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            live::LiveQueryOwner::Extension(extension_id) => state
+                .extension_webview_manager
+                .emit_to_extension_windows(app_handle, EVENT_DATABASE_LIVE_QUERY_RESULT, |window| {
+                    (window.vault_id == payload.vault_id && &window.extension_id == extension_id)
+                        .then(|| payload.clone())
+                })
+                .map_err(|e| e.to_string()),
+            #[cfg(any(target_os = "android", target_os = "ios"))]
+            live::LiveQueryOwner::Extension(_) => Ok(()),
+        };
+        if let Err(e) = emitted {
+            tracing::warn!("Failed to emit live query result: {e}");
+        }
+    }
+}
+
+/// Jede Extension bekommt nur die Änderungen an Tabellen, die sie lesen darf
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+async fn emit_changes_to_extensions(app_handle: &AppHandle, event: &changes::DatabaseChangedEvent) {
+    use crate::extension::permissions::manager::PermissionManager;
+    use crate::extension::permissions::types::{Action, DbAction};
+    use std::collections::{HashMap, HashSet};
+
+    let state = app_handle.state::<AppState>();
+    let Ok(vault) = state.vaults.get(&event.vault_id) else {
+        return;
+    };
+
+    let extension_ids: HashSet<String> = match state.extension_webview_manager.windows.lock() {
+        Ok(windows) => windows
+            .values()
+            .filter(|window| window.vault_id == event.vault_id)
+            .map(|window| window.extension_id.clone())
+            .collect(),
+        Err(_) => return,
+    };
+
+    let tables = changes::changed_tables(&event.changes);
+    let mut visible = HashMap::new();
+    for extension_id in extension_ids {
+        let mut readable = HashSet::new();
+        for table in &tables {
+            if PermissionManager::check_database_permission(
+                &vault,
+                &extension_id,
+                Action::Database(DbAction::Read),
+                table,
+            )
+            .await
+            .is_ok()
+            {
+                readable.insert(*table);
+            }
+        }
+
+        let changes: Vec<_> = event
+            .changes
+            .iter()
+            .filter(|change| readable.contains(change.table.as_str()))
+            .cloned()
+            .collect();
+        if !changes.is_empty() {
+            visible.insert(
+                extension_id,
+                changes::DatabaseChangedEvent {
+                    vault_id: event.vault_id.clone(),
+                    changes,
+                },
+            );
+        }
+    }
+
+    if let Err(e) = state.extension_webview_manager.emit_to_extension_windows(
+        app_handle,
+        EVENT_DATABASE_CHANGED,
+        |window| {
+            if window.vault_id != event.vault_id {
+                return None;
+            }
+            visible.get(&window.extension_id).cloned()
+        },
+    ) {
+        tracing::warn!("Failed to emit database changes to extensions: {e}");
+    }
+}
+
 /// Resolves a database name to the full vault path
 fn get_vault_path(app_handle: &AppHandle, vault_name: &str) -> Result<String, DatabaseError> {
     // Sicherstellen, dass der Name eine .db Endung hat
@@ -139,22 +663,31 @@ pub fn get_vaults_directory(app_handle: &AppHandle) -> Result<String, DatabaseEr
 #[serde(rename_all = "camelCase")]
 pub struct VaultInfo {
     name: String,
+    /// Letztes Öffnen laut Metadaten, sonst Änderungszeit der Datei
     last_access: u64,
     path: String,
+    display_name: Option<String>,
+    icon: Option<String>,
+    color: Option<String>,
+    created_at: Option<u64>,
+    last_opened_at: Option<u64>,
+    schema_version: Option<i64>,
+    size_bytes: u64,
 }
 
 /// Lists all vault databases in the vaults directory
 #[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"))]
 pub fn list_vaults(app_handle: AppHandle) -> Result<Vec<VaultInfo>, DatabaseError> {
     let vaults_dir_str = get_vaults_directory(&app_handle)?;
     let vaults_dir = Path::new(&vaults_dir_str);
 
-    println!("Suche vaults in {}", vaults_dir.display());
+    tracing::debug!("Suche vaults in {}", vaults_dir.display());
 
     let mut vaults: Vec<VaultInfo> = vec![];
 
     if !vaults_dir.exists() {
-        println!("Vaults-Verzeichnis existiert nicht, gebe leere Liste zurück.");
+        tracing::debug!("Vaults-Verzeichnis existiert nicht, gebe leere Liste zurück.");
         return Ok(vec![]);
     }
 
@@ -167,24 +700,27 @@ pub fn list_vaults(app_handle: AppHandle) -> Result<Vec<VaultInfo>, DatabaseErro
             reason: e.to_string(),
         })?;
 
-        println!("Suche entry {}", entry.path().to_string_lossy());
         let path = entry.path();
         if path.is_file() {
             if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
                 if filename.ends_with(VAULT_EXTENSION) {
                     // Entferne .db Endung für die Rückgabe
-                    println!("Vault gefunden {filename}");
+                    tracing::trace!("Vault gefunden {filename}");
 
                     let metadata = fs::metadata(&path).map_err(|e| DatabaseError::IoError {
                         path: path.to_string_lossy().to_string(),
                         reason: format!("Metadaten konnten nicht gelesen werden: {e}"),
                     })?;
 
-                    let last_access_timestamp = metadata
-                        .accessed()
+                    // atime ist auf vielen Systemen deaktiviert, daher zählt das
+                    // in den Metadaten gespeicherte letzte Öffnen
+                    let vault_metadata = vault_meta::read_metadata(&path).unwrap_or_default();
+
+                    let modified_timestamp = metadata
+                        .modified()
                         .map_err(|e| DatabaseError::IoError {
                             path: path.to_string_lossy().to_string(),
-                            reason: format!("Zugriffszeit konnte nicht gelesen werden: {e}"),
+                            reason: format!("Änderungszeit konnte nicht gelesen werden: {e}"),
                         })?
                         .duration_since(UNIX_EPOCH)
                         .unwrap_or_default() // Fallback für den seltenen Fall einer Zeit vor 1970
@@ -194,19 +730,36 @@ pub fn list_vaults(app_handle: AppHandle) -> Result<Vec<VaultInfo>, DatabaseErro
 
                     vaults.push(VaultInfo {
                         name: vault_name,
-                        last_access: last_access_timestamp,
+                        last_access: vault_metadata
+                            .last_opened_at
+                            .unwrap_or(modified_timestamp),
                         path: path.to_string_lossy().to_string(),
+                        display_name: vault_metadata.display_name,
+                        icon: vault_metadata.icon,
+                        color: vault_metadata.color,
+                        created_at: vault_metadata.created_at,
+                        last_opened_at: vault_metadata.last_opened_at,
+                        schema_version: vault_metadata.schema_version,
+                        size_bytes: metadata.len(),
                     });
                 }
             }
         }
     }
 
+    // Zuletzt geöffnete Vault zuerst, bei Gleichstand alphabetisch
+    vaults.sort_by(|a, b| {
+        b.last_access
+            .cmp(&a.last_access)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
     Ok(vaults)
 }
 
 /// Checks if a vault with the given name exists
 #[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"), fields(%vault_name))]
 pub fn vault_exists(app_handle: AppHandle, vault_name: String) -> Result<bool, DatabaseError> {
     let vault_path = get_vault_path(&app_handle, &vault_name)?;
     Ok(Path::new(&vault_path).exists())
@@ -214,6 +767,7 @@ pub fn vault_exists(app_handle: AppHandle, vault_name: String) -> Result<bool, D
 
 /// Moves a vault database file to trash (or deletes permanently if trash is unavailable)
 #[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_name))]
 pub fn move_vault_to_trash(
     app_handle: AppHandle,
     vault_name: String,
@@ -221,10 +775,7 @@ pub fn move_vault_to_trash(
     // On Android, trash is not available, so delete permanently
     #[cfg(target_os = "android")]
     {
-        println!(
-            "Android platform detected, permanently deleting vault '{}'",
-            vault_name
-        );
+        tracing::info!("Android platform detected, permanently deleting vault '{vault_name}'");
         return delete_vault(app_handle, vault_name);
     }
 
@@ -249,13 +800,14 @@ pub fn move_vault_to_trash(
             // Also try to move auxiliary files to trash (ignore errors as they might not exist)
             let _ = trash::delete(&vault_shm_path);
             let _ = trash::delete(&vault_wal_path);
+            let _ = trash::delete(vault_meta::metadata_path(Path::new(&vault_path)));
 
             Ok(format!(
                 "Vault '{vault_name}' successfully moved to trash"
             ))
         } else {
             // Fallback: Permanent deletion if trash fails
-            println!(
+            tracing::warn!(
                 "Trash not available, falling back to permanent deletion for vault '{vault_name}'"
             );
             delete_vault(app_handle, vault_name)
@@ -263,8 +815,79 @@ pub fn move_vault_to_trash(
     }
 }
 
+/// Updates the user-editable vault metadata (display name, icon, color)
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_name))]
+pub fn update_vault_metadata(
+    app_handle: AppHandle,
+    vault_name: String,
+    display_name: Option<String>,
+    icon: Option<String>,
+    color: Option<String>,
+) -> Result<vault_meta::VaultMetadata, DatabaseError> {
+    let vault_path = get_vault_path(&app_handle, &vault_name)?;
+    let vault_path = Path::new(&vault_path);
+
+    if !vault_path.exists() {
+        return Err(DatabaseError::IoError {
+            path: vault_path.display().to_string(),
+            reason: "Vault does not exist".to_string(),
+        });
+    }
+
+    let mut metadata = vault_meta::read_metadata(vault_path).unwrap_or_default();
+    metadata.display_name = display_name;
+    metadata.icon = icon;
+    metadata.color = color;
+    vault_meta::write_metadata(vault_path, &metadata)?;
+
+    Ok(metadata)
+}
+
+/// Renames a vault file together with its WAL files and metadata
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_name))]
+pub fn rename_vault(
+    app_handle: AppHandle,
+    vault_name: String,
+    new_vault_name: String,
+    state: State<'_, AppState>,
+) -> Result<String, DatabaseError> {
+    let new_name = new_vault_name.trim_end_matches(VAULT_EXTENSION);
+    if new_name.is_empty() || new_name.starts_with('.') || new_name.contains(['/', '\\']) {
+        return Err(DatabaseError::IoError {
+            path: new_vault_name.clone(),
+            reason: "Invalid vault name".to_string(),
+        });
+    }
+
+    let vault_path = get_vault_path(&app_handle, &vault_name)?;
+    let new_vault_path = get_vault_path(&app_handle, new_name)?;
+
+    // Eine geöffnete Vault darf nicht verschoben werden, die Verbindung hält die Datei
+    let canonical_vault_path = fs::canonicalize(&vault_path).ok();
+    let is_open = canonical_vault_path.is_some()
+        && state
+            .vaults
+            .list()?
+            .iter()
+            .any(|open| fs::canonicalize(&open.path).ok() == canonical_vault_path);
+
+    if is_open {
+        return Err(DatabaseError::IoError {
+            path: vault_path,
+            reason: "Vault is currently open".to_string(),
+        });
+    }
+
+    vault_meta::rename_vault_files(Path::new(&vault_path), Path::new(&new_vault_path))?;
+
+    Ok(new_vault_path)
+}
+
 /// Deletes a vault database file permanently (bypasses trash)
 #[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_name))]
 pub fn delete_vault(app_handle: AppHandle, vault_name: String) -> Result<String, DatabaseError> {
     let vault_path = get_vault_path(&app_handle, &vault_name)?;
     let vault_shm_path = format!("{vault_path}-shm");
@@ -296,20 +919,31 @@ pub fn delete_vault(app_handle: AppHandle, vault_name: String) -> Result<String,
         reason: format!("Failed to delete vault: {e}"),
     })?;
 
+    let metadata_path = vault_meta::metadata_path(Path::new(&vault_path));
+    if metadata_path.exists() {
+        fs::remove_file(&metadata_path).map_err(|e| DatabaseError::IoError {
+            path: metadata_path.display().to_string(),
+            reason: format!("Failed to delete vault metadata: {e}"),
+        })?;
+    }
+
     Ok(format!("Vault '{vault_name}' successfully deleted"))
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    err(level = "warn"),
+    fields(%vault_name, key = %redact::Secret(key.as_str())),
+)]
 pub fn create_encrypted_database(
     app_handle: AppHandle,
     vault_name: String,
     key: String,
     state: State<'_, AppState>,
 ) -> Result<String, DatabaseError> {
-    println!("Creating encrypted vault with name: {vault_name}");
-
     let vault_path = get_vault_path(&app_handle, &vault_name)?;
-    println!("Resolved vault path: {vault_path}");
+    tracing::info!("Creating encrypted vault at {vault_path}");
 
     // Prüfen, ob bereits eine Vault mit diesem Namen existiert
     if Path::new(&vault_path).exists() {
@@ -358,7 +992,7 @@ pub fn create_encrypted_database(
         });
     } */
 
-    println!("Öffne Temp-Datenbank direkt: {}", temp_path_clone.display());
+    tracing::debug!("Öffne Temp-Datenbank direkt: {}", temp_path_clone.display());
     let conn = Connection::open(&temp_path_clone).map_err(|e| DatabaseError::ConnectionFailed {
         path: temp_path_clone.display().to_string(),
         reason: format!(
@@ -366,10 +1000,7 @@ pub fn create_encrypted_database(
         ),
     })?;
 
-    println!(
-        "Hänge neue, verschlüsselte Datenbank an unter '{}'",
-        &vault_path
-    );
+    tracing::debug!("Hänge neue, verschlüsselte Datenbank an unter '{vault_path}'");
     // ATTACH DATABASE 'Dateiname' AS Alias KEY 'Passwort';
     conn.execute(
         "ATTACH DATABASE ?1 AS encrypted KEY ?2;",
@@ -381,7 +1012,7 @@ pub fn create_encrypted_database(
         table: None,
     })?;
 
-    println!("Exportiere Daten von 'main' nach 'encrypted' ...");
+    tracing::debug!("Exportiere Daten von 'main' nach 'encrypted' ...");
 
     if let Err(e) = conn.query_row("SELECT sqlcipher_export('encrypted');", [], |_| Ok(())) {
         // Versuche aufzuräumen, ignoriere Fehler dabei
@@ -394,7 +1025,7 @@ pub fn create_encrypted_database(
         });
     }
 
-    println!("Löse die verschlüsselte Datenbank vom Handle...");
+    tracing::debug!("Löse die verschlüsselte Datenbank vom Handle...");
 
     conn.execute("DETACH DATABASE encrypted;", [])
         .map_err(|e| DatabaseError::ExecutionError {
@@ -403,24 +1034,22 @@ pub fn create_encrypted_database(
             table: None,
         })?;
 
-    println!(
-        "Datenbank erfolgreich nach '{}' verschlüsselt.",
-        &vault_path
-    );
+    tracing::debug!("Datenbank erfolgreich nach '{vault_path}' verschlüsselt.");
 
     // SQLCipher-Verifizierung
-    println!("Prüfe SQLCipher-Aktivität mit 'PRAGMA cipher_version;'...");
+    tracing::debug!("Prüfe SQLCipher-Aktivität mit 'PRAGMA cipher_version;'...");
     match conn.query_row("PRAGMA cipher_version;", [], |row| {
         let version: String = row.get(0)?;
         Ok(version)
     }) {
         Ok(version) => {
-            println!("SQLCipher ist aktiv! Version: {version}");
+            tracing::debug!("SQLCipher ist aktiv! Version: {version}");
         }
         Err(e) => {
-            eprintln!("FEHLER: SQLCipher scheint NICHT aktiv zu sein!");
-            eprintln!("Der Befehl 'PRAGMA cipher_version;' schlug fehl: {e}");
-            eprintln!("Die Datenbank wurde wahrscheinlich NICHT verschlüsselt.");
+            tracing::error!(
+                "SQLCipher scheint NICHT aktiv zu sein! 'PRAGMA cipher_version;' schlug fehl: {e}. \
+                 Die Datenbank wurde wahrscheinlich NICHT verschlüsselt."
+            );
         }
     }
 
@@ -438,14 +1067,18 @@ pub fn create_encrypted_database(
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    err(level = "warn"),
+    fields(%vault_path, key = %redact::Secret(key.as_str())),
+)]
 pub fn open_encrypted_database(
     app_handle: AppHandle,
     vault_path: String,
     key: String,
     state: State<'_, AppState>,
 ) -> Result<String, DatabaseError> {
-    println!("Opening encrypted database vault_path: {vault_path}");
-    println!("Resolved vault path: {vault_path}");
+    tracing::info!("Opening encrypted database {vault_path}");
 
     if !Path::new(&vault_path).exists() {
         return Err(DatabaseError::IoError {
@@ -454,21 +1087,98 @@ pub fn open_encrypted_database(
         });
     }
 
-    initialize_session(&app_handle, &vault_path, &key, &state)?;
+    // Die Vault-ID adressiert die Vault in allen weiteren Commands
+    initialize_session(&app_handle, &vault_path, &key, &state)
+}
 
-    Ok(format!("Vault '{vault_path}' opened successfully"))
+/// Closes an open vault. Extension windows launched from it are closed as well.
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_id))]
+pub fn close_vault(
+    app_handle: AppHandle,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), DatabaseError> {
+    let session = state.vaults.remove(&vault_id)?;
+    shutdown_session(&app_handle, &state, &session)
 }
 
-/// Opens the DB, initializes the HLC service, and stores both in the AppState.
+/// Beendet Worker, Lese-Pool und Live-Queries einer Session, schließt ihre Extension-Fenster
+/// und die Schreib-Verbindung. Die Session muss bereits aus der Registry entfernt sein.
+fn shutdown_session(
+    app_handle: &AppHandle,
+    state: &AppState,
+    session: &vault::VaultSession,
+) -> Result<(), DatabaseError> {
+    session.writer.shutdown();
+    session.readers.close();
+    session.changes.set_listener(None);
+    session.live_queries.unsubscribe_all()?;
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    state
+        .extension_webview_manager
+        .close_windows_for_vault(app_handle, &session.id);
+    #[cfg(any(target_os = "android", target_os = "ios"))]
+    let _ = (app_handle, state);
+
+    // Laufende Commands halten ggf. noch eine Referenz auf die Session - die Verbindung
+    // wird trotzdem jetzt geschlossen, danach liefern sie einen ConnectionError.
+    let conn = session
+        .db
+        .0
+        .lock()
+        .map_err(|e| DatabaseError::LockError {
+            reason: e.to_string(),
+        })?
+        .take();
+
+    if let Some(conn) = conn {
+        conn.close().map_err(|(_, e)| DatabaseError::ConnectionFailed {
+            path: session.path.clone(),
+            reason: format!("Failed to close vault: {e}"),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Lists all currently open vaults
+#[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"))]
+pub fn list_open_vaults(
+    state: State<'_, AppState>,
+) -> Result<Vec<vault::OpenVaultInfo>, DatabaseError> {
+    state.vaults.list()
+}
+
+/// Opens the DB, initializes the HLC service, and registers both as a vault session.
+/// Returns the vault ID of the session.
 fn initialize_session(
     app_handle: &AppHandle,
     path: &str,
     key: &str,
     state: &State<'_, AppState>,
-) -> Result<(), DatabaseError> {
+) -> Result<String, DatabaseError> {
     // 1. Establish the raw database connection
     let mut conn = core::open_and_init_db(path, key, false)?;
 
+    // 1.5. Bring older vaults up to the current schema (refuses vaults from newer app versions)
+    let migration_report = migrations::run_pending_migrations(&mut conn)?;
+    if !migration_report.applied.is_empty() {
+        tracing::info!(
+            "Vault migrated from {:?} to {:?} ({} migration(s))",
+            migration_report.previous_version,
+            migration_report.current_version,
+            migration_report.applied.len()
+        );
+    }
+
+    // Metadaten sind nur Komfort - ein Fehler darf das Öffnen nicht verhindern
+    if let Err(e) = vault_meta::record_opened(Path::new(path), migration_report.current_version) {
+        tracing::warn!("Failed to update vault metadata: {e}");
+    }
+
     // 2. Ensure CRDT triggers are initialized (for template DB)
     let triggers_were_already_initialized = init::ensure_triggers_initialized(&mut conn)?;
 
@@ -482,36 +1192,38 @@ fn initialize_session(
         }
     })?;
 
-    // 4. Store everything in the global AppState
-    let mut db_guard = state.db.0.lock().map_err(|e| DatabaseError::LockError {
-        reason: e.to_string(),
-    })?;
-    // Wichtig: Wir brauchen den db_guard gleich nicht mehr,
-    // da 'execute_with_crdt' 'with_connection' aufruft, was
-    // 'state.db' selbst locken muss.
-    // Wir müssen den Guard freigeben, *bevor* wir 'execute_with_crdt' rufen,
-    // um einen Deadlock zu verhindern.
-    // Aber wir müssen die 'conn' erst hineinbewegen.
-    *db_guard = Some(conn);
-    drop(db_guard);
-
-    let mut hlc_guard = state.hlc.lock().map_err(|e| DatabaseError::LockError {
+    // 4. Register the vault session. Other open vaults stay untouched;
+    // reopening the same vault replaces its previous session.
+    let (session, previous) = state
+        .vaults
+        .insert(vault::VaultSession::new(path, key, conn, hlc_service)?)?;
+    if let Some(previous) = previous {
+        tracing::info!("Vault '{}' was already open, session replaced", session.id);
+        if let Err(e) = shutdown_session(app_handle, state, &previous) {
+            tracing::warn!(
+                "Failed to shut down previous session of '{}': {e}",
+                session.id
+            );
+        }
+    }
+    session.changes.set_listener(Some(database_change_listener(
+        app_handle.clone(),
+        session.id.clone(),
+    )));
+
+    let hlc_guard = session.hlc.lock().map_err(|e| DatabaseError::LockError {
         reason: e.to_string(),
     })?;
-    *hlc_guard = hlc_service;
-
-    // WICHTIG: hlc_guard *nicht* freigeben, da 'execute_with_crdt'
-    // eine Referenz auf die Guard erwartet.
 
     // 5. NEUER SCHRITT: Setze das Flag via CRDT, falls nötig
     if !triggers_were_already_initialized {
-        eprintln!("INFO: Setting 'triggers_initialized' flag via CRDT...");
+        tracing::info!("Setting 'triggers_initialized' flag via CRDT...");
 
         let insert_sql = format!(
             "INSERT INTO {TABLE_SETTINGS} (id, key, type, value) VALUES (?, ?, ?, ?)"
         );
 
-        // execute_with_crdt erwartet Vec<JsonValue>, kein params!-Makro
+        // execute_with_crdt erwartet JSON-Parameter, kein params!-Makro
         let params_vec: Vec<JsonValue> = vec![
             JsonValue::String(uuid::Uuid::new_v4().to_string()),
             JsonValue::String("triggers_initialized".to_string()),
@@ -522,12 +1234,12 @@ fn initialize_session(
         // Jetzt können wir 'execute_with_crdt' sicher aufrufen,
         // da der AppState initialisiert ist.
         execute_with_crdt(
-            insert_sql, params_vec, &state.db,  // Das &DbConnection (der Mutex)
+            insert_sql, params_vec.into(), &session.db,  // Das &DbConnection (der Mutex)
             &hlc_guard, // Die gehaltene MutexGuard
         )?;
 
-        eprintln!("INFO: ✓ 'triggers_initialized' flag set.");
+        tracing::info!("'triggers_initialized' flag set.");
     }
 
-    Ok(())
+    Ok(session.id.clone())
 }
@@ -0,0 +1,225 @@
+// src-tauri/src/database/pool.rs
+// Lese-Verbindungen einer Vault. Im WAL-Modus können Leser parallel zur einzigen
+// Schreib-Verbindung (DbConnection) arbeiten, lange SELECTs blockieren so keine Writes mehr.
+
+use crate::database::core::open_and_init_db;
+use crate::database::error::DatabaseError;
+use crate::database::query::STATEMENT_CACHE_CAPACITY;
+use rusqlite::Connection;
+use std::sync::{Condvar, Mutex, MutexGuard};
+
+/// Maximale Anzahl gleichzeitig geöffneter Lese-Verbindungen pro Vault
+pub const DEFAULT_READER_COUNT: usize = 4;
+
+struct PoolState {
+    idle: Vec<Connection>,
+    /// Anzahl der geöffneten Verbindungen (idle + ausgeliehen)
+    open: usize,
+    /// Schlüssel für neue Verbindungen, `None` sobald der Pool geschlossen wurde
+    key: Option<String>,
+}
+
+/// Pool von Lese-Verbindungen. Verbindungen werden erst bei Bedarf geöffnet
+/// (jede Verbindung kostet eine Schlüsselableitung) und danach wiederverwendet.
+pub struct ReadPool {
+    path: String,
+    max_readers: usize,
+    state: Mutex<PoolState>,
+    available: Condvar,
+}
+
+/// Gibt die Verbindung auch bei einem Panic im Closure an den Pool zurück
+struct PooledReader<'a> {
+    pool: &'a ReadPool,
+    conn: Option<Connection>,
+}
+
+impl Drop for PooledReader<'_> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.pool.checkin(conn);
+        }
+    }
+}
+
+impl ReadPool {
+    pub fn new(path: &str, key: &str, max_readers: usize) -> Self {
+        Self {
+            path: path.to_string(),
+            max_readers: max_readers.max(1),
+            state: Mutex::new(PoolState {
+                idle: Vec::new(),
+                open: 0,
+                key: Some(key.to_string()),
+            }),
+            available: Condvar::new(),
+        }
+    }
+
+    /// Führt `f` mit einer Lese-Verbindung aus. Sind alle Leser belegt, wird gewartet.
+    pub fn with_reader<T, F>(&self, f: F) -> Result<T, DatabaseError>
+    where
+        F: FnOnce(&mut Connection) -> Result<T, DatabaseError>,
+    {
+        let mut reader = PooledReader {
+            pool: self,
+            conn: Some(self.checkout()?),
+        };
+
+        match reader.conn.as_mut() {
+            Some(conn) => f(conn),
+            None => Err(DatabaseError::ConnectionError {
+                reason: "Reader connection missing".to_string(),
+            }),
+        }
+    }
+
+    /// Schließt alle Lese-Verbindungen. Ausgeliehene Verbindungen werden bei der Rückgabe geschlossen.
+    pub fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.key = None;
+            let idle = std::mem::take(&mut state.idle);
+            state.open -= idle.len();
+            drop(state);
+            drop(idle);
+        }
+        self.available.notify_all();
+    }
+
+    fn lock_state(&self) -> Result<MutexGuard<'_, PoolState>, DatabaseError> {
+        self.state.lock().map_err(|e| DatabaseError::MutexPoisoned {
+            reason: e.to_string(),
+        })
+    }
+
+    fn checkout(&self) -> Result<Connection, DatabaseError> {
+        let mut state = self.lock_state()?;
+
+        loop {
+            let Some(key) = state.key.clone() else {
+                return Err(DatabaseError::ConnectionError {
+                    reason: "Vault is closed".to_string(),
+                });
+            };
+
+            if let Some(conn) = state.idle.pop() {
+                return Ok(conn);
+            }
+
+            if state.open < self.max_readers {
+                // Platz reservieren und außerhalb des Locks öffnen
+                state.open += 1;
+                drop(state);
+
+                return Self::open_reader(&self.path, &key).inspect_err(|_| {
+                    if let Ok(mut state) = self.state.lock() {
+                        state.open -= 1;
+                    }
+                    self.available.notify_one();
+                });
+            }
+
+            state = self
+                .available
+                .wait(state)
+                .map_err(|e| DatabaseError::MutexPoisoned {
+                    reason: e.to_string(),
+                })?;
+        }
+    }
+
+    fn checkin(&self, conn: Connection) {
+        if let Ok(mut state) = self.state.lock() {
+            if state.key.is_some() {
+                state.idle.push(conn);
+            } else {
+                state.open -= 1;
+            }
+        }
+        self.available.notify_one();
+    }
+
+    fn open_reader(path: &str, key: &str) -> Result<Connection, DatabaseError> {
+        let conn = open_and_init_db(path, key, false)?;
+
+        // Leser dürfen nie schreiben - CRDT-Writes laufen ausschließlich über die Schreib-Verbindung
+        conn.pragma_update(None, "query_only", true)
+            .map_err(|e| DatabaseError::PragmaError {
+                pragma: "query_only".to_string(),
+                reason: e.to_string(),
+            })?;
+        conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+
+        Ok(conn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Barrier};
+
+    fn temp_vault() -> (String, Connection) {
+        let path = std::env::temp_dir()
+            .join(format!("haex_pool_{}.db", uuid::Uuid::new_v4()))
+            .display()
+            .to_string();
+        let writer = open_and_init_db(&path, "test", true).unwrap();
+        writer
+            .execute_batch(
+                "CREATE TABLE items (id TEXT PRIMARY KEY); INSERT INTO items VALUES ('a');",
+            )
+            .unwrap();
+        (path, writer)
+    }
+
+    fn count_items(conn: &mut Connection) -> Result<i64, DatabaseError> {
+        Ok(conn.query_row("SELECT COUNT(*) FROM items", [], |row| row.get(0))?)
+    }
+
+    #[test]
+    fn test_readers_run_while_writer_holds_transaction() {
+        let (path, mut writer) = temp_vault();
+        let pool = Arc::new(ReadPool::new(&path, "test", 2));
+
+        let tx = writer.transaction().unwrap();
+        tx.execute("INSERT INTO items VALUES ('b')", []).unwrap();
+
+        // Zwei Leser gleichzeitig, beide sehen nur den committeten Stand
+        let barrier = Arc::new(Barrier::new(2));
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let pool = pool.clone();
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    pool.with_reader(|conn| {
+                        barrier.wait();
+                        count_items(conn)
+                    })
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap().unwrap(), 1);
+        }
+
+        tx.commit().unwrap();
+        assert_eq!(pool.with_reader(count_items).unwrap(), 2);
+
+        // Leser sind read-only
+        assert!(pool
+            .with_reader(|conn| Ok(conn.execute("DELETE FROM items", [])?))
+            .is_err());
+
+        pool.close();
+        assert!(matches!(
+            pool.with_reader(count_items),
+            Err(DatabaseError::ConnectionError { .. })
+        ));
+
+        drop(writer);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(format!("{path}-wal"));
+        let _ = std::fs::remove_file(format!("{path}-shm"));
+    }
+}
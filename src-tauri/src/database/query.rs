@@ -0,0 +1,201 @@
+// src-tauri/src/database/query.rs
+// Typisierte Lese-Queries: Ergebniszeilen als Objekte mit Spaltennamen statt positionsbasierter
+// Arrays. Statements werden über den Statement-Cache der Verbindung wiederverwendet.
+
+use crate::database::core::{convert_value_ref_to_json, SqlParams, ValueConverter};
+use crate::database::error::DatabaseError;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashSet;
+use ts_rs::TS;
+
+/// Anzahl vorbereiteter Statements, die pro Lese-Verbindung im Cache gehalten werden
+pub const STATEMENT_CACHE_CAPACITY: usize = 64;
+
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QueryOptions {
+    /// Deklarierte Spaltentypen (z.B. `TEXT`, `INTEGER`) mitliefern
+    pub include_column_types: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryColumn {
+    pub name: String,
+    /// Deklarierter Typ aus dem Tabellenschema, `null` bei Ausdrücken oder ohne `includeColumnTypes`
+    pub decl_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryResult {
+    /// Spalten in der Reihenfolge des SELECT
+    pub columns: Vec<QueryColumn>,
+    #[ts(type = "Array<Record<string, unknown>>")]
+    pub rows: Vec<Map<String, JsonValue>>,
+}
+
+/// Führt eine lesende Query aus und liefert die Zeilen mit Spaltennamen.
+/// Statt die SQL mit sqlparser zu prüfen, entscheidet SQLite selbst, ob das Statement nur liest.
+pub fn query_typed(
+    conn: &Connection,
+    sql: &str,
+    params: &SqlParams,
+    options: &QueryOptions,
+) -> Result<QueryResult, DatabaseError> {
+    let mut stmt = conn.prepare_cached(sql)?;
+
+    if !stmt.readonly() {
+        return Err(DatabaseError::StatementError {
+            reason: "Only read-only statements are allowed in typed queries".to_string(),
+        });
+    }
+
+    ValueConverter::bind_params(&mut stmt, params, sql)?;
+
+    let columns: Vec<QueryColumn> = if options.include_column_types {
+        stmt.columns()
+            .iter()
+            .map(|column| QueryColumn {
+                name: column.name().to_string(),
+                decl_type: column.decl_type().map(str::to_string),
+            })
+            .collect()
+    } else {
+        stmt.column_names()
+            .into_iter()
+            .map(|name| QueryColumn {
+                name: name.to_string(),
+                decl_type: None,
+            })
+            .collect()
+    };
+
+    // Doppelte Namen (z.B. `id` aus zwei gejointen Tabellen) würden sich im Objekt überschreiben
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = columns.iter().find(|c| !seen.insert(c.name.as_str())) {
+        return Err(DatabaseError::QueryError {
+            reason: format!(
+                "Duplicate column name '{}' in typed query, use an alias",
+                duplicate.name
+            ),
+        });
+    }
+
+    let mut rows = stmt.raw_query();
+    let mut result_rows = Vec::new();
+
+    while let Some(row) = rows.next()? {
+        let mut object = Map::with_capacity(columns.len());
+        for (i, column) in columns.iter().enumerate() {
+            object.insert(
+                column.name.clone(),
+                convert_value_ref_to_json(row.get_ref(i)?)?,
+            );
+        }
+        result_rows.push(object);
+    }
+
+    Ok(QueryResult {
+        columns,
+        rows: result_rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE notes (id TEXT PRIMARY KEY, title TEXT, pinned INTEGER);
+             INSERT INTO notes VALUES ('a', 'Einkauf', 1), ('b', 'Urlaub', 0);",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_rows_are_keyed_by_column_name() {
+        let conn = setup();
+        let options = QueryOptions {
+            include_column_types: true,
+        };
+
+        let result = query_typed(
+            &conn,
+            "SELECT pinned, title, upper(id) AS key FROM notes WHERE id = ?",
+            &vec![json!("a")].into(),
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(
+            result.columns,
+            vec![
+                QueryColumn {
+                    name: "pinned".to_string(),
+                    decl_type: Some("INTEGER".to_string()),
+                },
+                QueryColumn {
+                    name: "title".to_string(),
+                    decl_type: Some("TEXT".to_string()),
+                },
+                QueryColumn {
+                    name: "key".to_string(),
+                    decl_type: None,
+                },
+            ]
+        );
+        assert_eq!(
+            JsonValue::Object(result.rows[0].clone()),
+            json!({ "pinned": 1, "title": "Einkauf", "key": "A" })
+        );
+
+        // Zweiter Aufruf trifft den Statement-Cache und liefert dasselbe Ergebnis für andere Parameter
+        let result = query_typed(
+            &conn,
+            "SELECT pinned, title, upper(id) AS key FROM notes WHERE id = ?",
+            &vec![json!("b")].into(),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(result.rows[0]["title"], json!("Urlaub"));
+    }
+
+    #[test]
+    fn test_rejects_writes_and_ambiguous_columns() {
+        let conn = setup();
+        let options = QueryOptions::default();
+
+        assert!(matches!(
+            query_typed(&conn, "DELETE FROM notes", &SqlParams::default(), &options),
+            Err(DatabaseError::StatementError { .. })
+        ));
+        assert!(matches!(
+            query_typed(
+                &conn,
+                "SELECT a.id, b.id FROM notes a JOIN notes b ON a.id = b.id",
+                &SqlParams::default(),
+                &options
+            ),
+            Err(DatabaseError::QueryError { .. })
+        ));
+        assert!(matches!(
+            query_typed(
+                &conn,
+                "SELECT * FROM notes WHERE id = ?",
+                &SqlParams::default(),
+                &options
+            ),
+            Err(DatabaseError::ParameterMismatchError { .. })
+        ));
+    }
+}
@@ -0,0 +1,766 @@
+// src-tauri/src/database/transfer.rs
+// Export und Import von Vault-Daten als JSON Lines oder CSV (eine Datei pro Tabelle)
+
+use crate::crdt::hlc::HlcService;
+use crate::crdt::trigger::{self, HLC_TIMESTAMP_COLUMN};
+use crate::database::core::{convert_value_ref_to_json, ValueConverter, BIGINT_TAG, BLOB_TAG};
+use crate::database::error::DatabaseError;
+use crate::extension::database::executor::SqlExecutor;
+use rusqlite::{Connection, ToSql};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use ts_rs::TS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "lowercase")]
+pub enum DataFormat {
+    /// Ein JSON-Objekt pro Zeile
+    Jsonl,
+    /// RFC 4180, erste Zeile enthält die Spaltennamen. Leere Felder ohne Quotes sind NULL.
+    Csv,
+}
+
+impl DataFormat {
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            DataFormat::Jsonl => "jsonl",
+            DataFormat::Csv => "csv",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportRequest {
+    /// Zu exportierende Tabellen
+    #[serde(default)]
+    pub tables: Vec<String>,
+    /// Exportiert zusätzlich alle eigenen Tabellen dieser Extension
+    #[serde(default)]
+    pub extension_id: Option<String>,
+    pub format: DataFormat,
+    /// Zielverzeichnis, pro Tabelle wird `{table}.{jsonl|csv}` geschrieben
+    pub directory: String,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedTable {
+    pub table: String,
+    pub path: String,
+    pub rows: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRequest {
+    pub path: String,
+    pub table: String,
+    pub format: DataFormat,
+    /// Spalte in der Datei -> Spalte in der Tabelle. Nicht aufgeführte Spalten behalten ihren Namen.
+    #[serde(default)]
+    pub column_mapping: HashMap<String, String>,
+    /// Nur prüfen und Konflikte melden, nichts schreiben
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportConflict {
+    /// Zeile in der Importdatei (1-basiert)
+    pub line: usize,
+    /// Primärschlüssel als JSON-Objekt, wie in haex_crdt_logs.row_pks
+    pub row_pks: String,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub table: String,
+    pub dry_run: bool,
+    pub total_rows: usize,
+    pub imported: usize,
+    /// Zeilen, deren Primärschlüssel bereits existiert (oder in der Datei doppelt vorkommt).
+    /// Sie werden beim Import übersprungen.
+    pub conflicts: Vec<ImportConflict>,
+}
+
+/// Eine Zeile aus der Importdatei, bereits auf die Zielspalten abgebildet
+struct ImportRow {
+    line: usize,
+    values: Map<String, JsonValue>,
+}
+
+// =================================================================
+// EXPORT
+// =================================================================
+
+/// Exportiert die angegebenen Tabellen sowie optional alle Tabellen mit `extension_prefix`.
+pub fn export_tables(
+    conn: &Connection,
+    tables: &[String],
+    extension_prefix: Option<&str>,
+    format: DataFormat,
+    directory: &Path,
+) -> Result<Vec<ExportedTable>, DatabaseError> {
+    let mut selected: Vec<String> = Vec::new();
+    for table in tables {
+        ensure_table_exists(conn, table)?;
+        if !selected.contains(table) {
+            selected.push(table.clone());
+        }
+    }
+    if let Some(prefix) = extension_prefix {
+        for table in list_tables_with_prefix(conn, prefix)? {
+            if !selected.contains(&table) {
+                selected.push(table);
+            }
+        }
+    }
+
+    fs::create_dir_all(directory).map_err(|e| DatabaseError::IoError {
+        path: directory.display().to_string(),
+        reason: format!("Failed to create export directory: {e}"),
+    })?;
+
+    selected
+        .iter()
+        .map(|table| {
+            let path = directory.join(format!("{table}.{}", format.file_extension()));
+            let rows = export_table(conn, table, format, &path)?;
+            Ok(ExportedTable {
+                table: table.clone(),
+                path: path.to_string_lossy().to_string(),
+                rows,
+            })
+        })
+        .collect()
+}
+
+fn export_table(
+    conn: &Connection,
+    table: &str,
+    format: DataFormat,
+    path: &Path,
+) -> Result<usize, DatabaseError> {
+    let io_err = |e: std::io::Error| DatabaseError::IoError {
+        path: path.display().to_string(),
+        reason: format!("Failed to write export file: {e}"),
+    };
+
+    let file = fs::File::create(path).map_err(io_err)?;
+    let mut writer = BufWriter::new(file);
+
+    let mut stmt = conn.prepare(&format!("SELECT * FROM \"{table}\""))?;
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+
+    if format == DataFormat::Csv {
+        let header: Vec<Option<String>> = columns.iter().cloned().map(Some).collect();
+        writeln!(writer, "{}", encode_csv_record(&header)).map_err(io_err)?;
+    }
+
+    let mut rows = stmt.query([])?;
+    let mut count = 0;
+    while let Some(row) = rows.next()? {
+        let mut values = Vec::with_capacity(columns.len());
+        for i in 0..columns.len() {
+            values.push(convert_value_ref_to_json(row.get_ref(i)?)?);
+        }
+
+        let line = match format {
+            DataFormat::Jsonl => {
+                let object: Map<String, JsonValue> = columns.iter().cloned().zip(values).collect();
+                serde_json::to_string(&object).map_err(|e| DatabaseError::SerializationError {
+                    reason: e.to_string(),
+                })?
+            }
+            DataFormat::Csv => {
+                let fields: Vec<Option<String>> = values
+                    .into_iter()
+                    .map(|value| match value {
+                        JsonValue::Null => None,
+                        JsonValue::String(s) => Some(s),
+                        // Getaggte Werte ($blob, $bigint) bleiben als JSON erhalten, siehe decode_csv_field
+                        other => Some(other.to_string()),
+                    })
+                    .collect();
+                encode_csv_record(&fields)
+            }
+        };
+        writeln!(writer, "{line}").map_err(io_err)?;
+        count += 1;
+    }
+
+    writer.flush().map_err(io_err)?;
+    Ok(count)
+}
+
+// =================================================================
+// IMPORT
+// =================================================================
+
+/// Importiert eine Datei in eine Tabelle.
+///
+/// Die Zeilen laufen durch denselben CRDT-Pfad wie `execute_with_crdt` (SqlExecutor),
+/// erhalten also HLC-Zeitstempel und werden synchronisiert. Der gesamte Import läuft
+/// in einer Transaktion. Zeilen mit bereits vorhandenem Primärschlüssel werden
+/// übersprungen und als Konflikt gemeldet.
+pub fn import_table(
+    conn: &mut Connection,
+    hlc_service: &HlcService,
+    request: &ImportRequest,
+) -> Result<ImportReport, DatabaseError> {
+    ensure_table_exists(conn, &request.table)?;
+    let schema = trigger::get_table_schema(conn, &request.table)?;
+    let known_columns: HashSet<&str> = schema.iter().map(|c| c.name.as_str()).collect();
+    let pk_columns: Vec<&str> = schema
+        .iter()
+        .filter(|c| c.is_pk)
+        .map(|c| c.name.as_str())
+        .collect();
+
+    let content = fs::read_to_string(&request.path).map_err(|e| DatabaseError::IoError {
+        path: request.path.clone(),
+        reason: format!("Failed to read import file: {e}"),
+    })?;
+
+    let rows = match request.format {
+        DataFormat::Jsonl => parse_jsonl(&content),
+        DataFormat::Csv => parse_csv(&content),
+    }
+    .map_err(|(line, reason)| DatabaseError::ImportError {
+        path: request.path.clone(),
+        line: Some(line),
+        reason,
+    })?;
+
+    let rows =
+        map_columns(rows, &request.column_mapping, &known_columns).map_err(|(line, reason)| {
+            DatabaseError::ImportError {
+                path: request.path.clone(),
+                line: Some(line),
+                reason,
+            }
+        })?;
+
+    let conflicts = find_conflicts(conn, &request.table, &pk_columns, &rows)?;
+
+    let mut report = ImportReport {
+        table: request.table.clone(),
+        dry_run: request.dry_run,
+        total_rows: rows.len(),
+        imported: 0,
+        conflicts,
+    };
+
+    if request.dry_run {
+        return Ok(report);
+    }
+
+    let conflicting_lines: HashSet<usize> = report.conflicts.iter().map(|c| c.line).collect();
+    let tx = conn.transaction()?;
+
+    for row in rows.iter().filter(|r| !conflicting_lines.contains(&r.line)) {
+        if row.values.is_empty() {
+            continue;
+        }
+
+        let columns: Vec<String> = row.values.keys().map(|c| format!("\"{c}\"")).collect();
+        let placeholders: Vec<String> = (1..=columns.len()).map(|i| format!("?{i}")).collect();
+        let sql = format!(
+            "INSERT INTO \"{}\" ({}) VALUES ({})",
+            request.table,
+            columns.join(", "),
+            placeholders.join(", ")
+        );
+        let params: Vec<JsonValue> = row.values.values().cloned().collect();
+
+        SqlExecutor::execute_internal(&tx, hlc_service, &sql, &params.into())?;
+        report.imported += 1;
+    }
+
+    tx.commit()?;
+
+    tracing::info!(
+        "Imported {} of {} rows into {} ({} conflicts skipped)",
+        report.imported,
+        report.total_rows,
+        request.table,
+        report.conflicts.len()
+    );
+
+    Ok(report)
+}
+
+/// Wendet das Spalten-Mapping an und verwirft die HLC-Spalte, die beim Import neu gesetzt wird.
+fn map_columns(
+    rows: Vec<ImportRow>,
+    mapping: &HashMap<String, String>,
+    known_columns: &HashSet<&str>,
+) -> Result<Vec<ImportRow>, (usize, String)> {
+    rows.into_iter()
+        .map(|row| {
+            let mut values = Map::new();
+            for (source, value) in row.values {
+                let target = mapping.get(&source).cloned().unwrap_or(source);
+                if target == HLC_TIMESTAMP_COLUMN {
+                    continue;
+                }
+                if !known_columns.contains(target.as_str()) {
+                    return Err((row.line, format!("Unknown column '{target}'")));
+                }
+                values.insert(target, value);
+            }
+            Ok(ImportRow {
+                line: row.line,
+                values,
+            })
+        })
+        .collect()
+}
+
+fn find_conflicts(
+    conn: &Connection,
+    table: &str,
+    pk_columns: &[&str],
+    rows: &[ImportRow],
+) -> Result<Vec<ImportConflict>, DatabaseError> {
+    // Ohne expliziten Primärschlüssel vergibt SQLite die rowid - keine Konflikte möglich
+    if pk_columns.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let condition: Vec<String> = pk_columns
+        .iter()
+        .enumerate()
+        .map(|(i, pk)| format!("\"{pk}\" = ?{}", i + 1))
+        .collect();
+    let mut stmt = conn.prepare(&format!(
+        "SELECT EXISTS(SELECT 1 FROM \"{table}\" WHERE {})",
+        condition.join(" AND ")
+    ))?;
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut conflicts = Vec::new();
+
+    for row in rows {
+        // Fehlt ein Teil des Schlüssels, wird er von der Tabelle (DEFAULT) erzeugt
+        let Some(pk_values) = pk_columns
+            .iter()
+            .map(|pk| row.values.get(*pk).filter(|v| !v.is_null()))
+            .collect::<Option<Vec<&JsonValue>>>()
+        else {
+            continue;
+        };
+
+        let row_pks: Map<String, JsonValue> = pk_columns
+            .iter()
+            .map(|pk| pk.to_string())
+            .zip(pk_values.iter().map(|v| (*v).clone()))
+            .collect();
+        let row_pks = JsonValue::Object(row_pks).to_string();
+
+        let sql_values = pk_values
+            .into_iter()
+            .map(ValueConverter::json_to_rusqlite_value)
+            .collect::<Result<Vec<_>, _>>()?;
+        let params: Vec<&dyn ToSql> = sql_values.iter().map(|v| v as &dyn ToSql).collect();
+        let exists: bool = stmt.query_row(&params[..], |r| r.get(0))?;
+
+        if exists || !seen.insert(row_pks.clone()) {
+            conflicts.push(ImportConflict {
+                line: row.line,
+                row_pks,
+            });
+        }
+    }
+
+    Ok(conflicts)
+}
+
+// =================================================================
+// HELFER
+// =================================================================
+
+fn ensure_table_exists(conn: &Connection, table: &str) -> Result<(), DatabaseError> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+        [table],
+        |row| row.get(0),
+    )?;
+
+    if exists {
+        Ok(())
+    } else {
+        Err(DatabaseError::NoTableError {
+            sql: table.to_string(),
+        })
+    }
+}
+
+fn list_tables_with_prefix(conn: &Connection, prefix: &str) -> Result<Vec<String>, DatabaseError> {
+    // Kein LIKE: "_" ist dort ein Platzhalter und kommt in jedem Präfix vor
+    let mut stmt = conn.prepare(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND substr(name, 1, length(?1)) = ?1 ORDER BY name",
+    )?;
+    let tables = stmt
+        .query_map([prefix], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(tables)
+}
+
+fn parse_jsonl(content: &str) -> Result<Vec<ImportRow>, (usize, String)> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(
+            |(index, line)| match serde_json::from_str::<JsonValue>(line) {
+                Ok(JsonValue::Object(values)) => Ok(ImportRow {
+                    line: index + 1,
+                    values,
+                }),
+                Ok(_) => Err((index + 1, "Expected a JSON object".to_string())),
+                Err(e) => Err((index + 1, e.to_string())),
+            },
+        )
+        .collect()
+}
+
+fn parse_csv(content: &str) -> Result<Vec<ImportRow>, (usize, String)> {
+    let mut records = parse_csv_records(content)?.into_iter();
+
+    let Some((_, header)) = records.next() else {
+        return Ok(vec![]);
+    };
+    let header: Vec<String> = header.into_iter().map(Option::unwrap_or_default).collect();
+
+    records
+        .map(|(line, fields)| {
+            if fields.len() != header.len() {
+                return Err((
+                    line,
+                    format!("Expected {} fields, found {}", header.len(), fields.len()),
+                ));
+            }
+            let values = header
+                .iter()
+                .cloned()
+                .zip(
+                    fields
+                        .into_iter()
+                        .map(|f| f.map_or(JsonValue::Null, decode_csv_field)),
+                )
+                .collect();
+            Ok(ImportRow { line, values })
+        })
+        .collect()
+}
+
+/// Macht aus einem exportierten Tag-Objekt (`{"$blob": ...}`, `{"$bigint": ...}`) wieder
+/// den getaggten Wert, damit BLOBs und große Ganzzahlen den CSV-Roundtrip überstehen.
+fn decode_csv_field(field: String) -> JsonValue {
+    if field.starts_with('{') {
+        if let Ok(JsonValue::Object(map)) = serde_json::from_str::<JsonValue>(&field) {
+            if map.len() == 1 && (map.contains_key(BLOB_TAG) || map.contains_key(BIGINT_TAG)) {
+                return JsonValue::Object(map);
+            }
+        }
+    }
+    JsonValue::String(field)
+}
+
+/// Startzeile eines CSV-Datensatzes und seine Felder
+type CsvRecord = (usize, Vec<Option<String>>);
+
+/// Zerlegt CSV nach RFC 4180. `None` steht für ein leeres Feld ohne Anführungszeichen (NULL).
+fn parse_csv_records(content: &str) -> Result<Vec<CsvRecord>, (usize, String)> {
+    let mut records = Vec::new();
+    let mut record: Vec<Option<String>> = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+
+    let take_field = |field: &mut String, quoted: &mut bool| {
+        let value = if *quoted || !field.is_empty() {
+            Some(std::mem::take(field))
+        } else {
+            None
+        };
+        *quoted = false;
+        value
+    };
+
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() && !quoted => {
+                in_quotes = true;
+                quoted = true;
+            }
+            ',' => record.push(take_field(&mut field, &mut quoted)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(take_field(&mut field, &mut quoted));
+                // Leerzeilen überspringen
+                if !(record.len() == 1 && record[0].is_none()) {
+                    records.push((record_line, std::mem::take(&mut record)));
+                }
+                record.clear();
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err((record_line, "Unterminated quoted field".to_string()));
+    }
+    if !record.is_empty() || !field.is_empty() || quoted {
+        record.push(take_field(&mut field, &mut quoted));
+        records.push((record_line, record));
+    }
+
+    Ok(records)
+}
+
+fn encode_csv_record(fields: &[Option<String>]) -> String {
+    fields
+        .iter()
+        .map(|field| match field {
+            None => String::new(),
+            Some(s) if s.is_empty() || s.contains([',', '"', '\n', '\r']) => {
+                format!("\"{}\"", s.replace('"', "\"\""))
+            }
+            Some(s) => s.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table_names::{TABLE_CRDT_CONFIGS, TABLE_CRDT_LOGS};
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE \"abc__ext__notes\" (id TEXT PRIMARY KEY, title TEXT, body TEXT, haex_timestamp TEXT);
+             CREATE TABLE \"abc__ext__tags\" (id TEXT PRIMARY KEY, name TEXT, haex_timestamp TEXT);
+             CREATE TABLE \"abcd__other__notes\" (id TEXT PRIMARY KEY, haex_timestamp TEXT);
+             INSERT INTO \"abc__ext__notes\" VALUES ('1', 'Hello, \"world\"', NULL, '1/1'), ('2', '', 'multi\nline', '2/1');",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_csv_round_trip_preserves_quotes_and_nulls() {
+        let fields = vec![
+            Some("a,b".to_string()),
+            Some("say \"hi\"".to_string()),
+            None,
+            Some(String::new()),
+            Some("x\ny".to_string()),
+        ];
+        let encoded = encode_csv_record(&fields);
+        let parsed = parse_csv_records(&format!("{encoded}\r\n\nnext\n")).unwrap();
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0], (1, fields));
+        assert_eq!(parsed[1], (4, vec![Some("next".to_string())]));
+        assert!(parse_csv_records("\"open").is_err());
+    }
+
+    #[test]
+    fn test_export_extension_tables() {
+        let conn = setup_db();
+        let dir = std::env::temp_dir().join(format!("haex_export_{}", uuid::Uuid::new_v4()));
+
+        let exported =
+            export_tables(&conn, &[], Some("abc__ext__"), DataFormat::Csv, &dir).unwrap();
+        let tables: Vec<&str> = exported.iter().map(|t| t.table.as_str()).collect();
+        assert_eq!(tables, vec!["abc__ext__notes", "abc__ext__tags"]);
+        assert_eq!(exported[0].rows, 2);
+
+        let content = fs::read_to_string(&exported[0].path).unwrap();
+        let rows = parse_csv(&content).unwrap();
+        assert_eq!(rows[0].values["title"], JsonValue::from("Hello, \"world\""));
+        assert_eq!(rows[0].values["body"], JsonValue::Null);
+        assert_eq!(rows[1].values["body"], JsonValue::from("multi\nline"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_dry_run_reports_primary_key_conflicts() {
+        let mut conn = setup_db();
+        let path = std::env::temp_dir().join(format!("haex_import_{}.jsonl", uuid::Uuid::new_v4()));
+        fs::write(
+            &path,
+            "{\"key\":\"1\",\"title\":\"dup\",\"haex_timestamp\":\"9/1\"}\n\n{\"key\":\"3\",\"title\":\"new\"}\n{\"key\":\"3\",\"title\":\"again\"}\n",
+        )
+        .unwrap();
+
+        let request = ImportRequest {
+            path: path.to_string_lossy().to_string(),
+            table: "abc__ext__notes".to_string(),
+            format: DataFormat::Jsonl,
+            column_mapping: HashMap::from([("key".to_string(), "id".to_string())]),
+            dry_run: true,
+        };
+        let report = import_table(&mut conn, &HlcService::new(), &request).unwrap();
+
+        assert_eq!(report.total_rows, 3);
+        assert_eq!(report.imported, 0);
+        let lines: Vec<usize> = report.conflicts.iter().map(|c| c.line).collect();
+        assert_eq!(lines, vec![1, 4]);
+        assert_eq!(report.conflicts[0].row_pks, "{\"id\":\"1\"}");
+
+        let unknown = ImportRequest {
+            column_mapping: HashMap::new(),
+            ..request
+        };
+        assert!(matches!(
+            import_table(&mut conn, &HlcService::new(), &unknown),
+            Err(DatabaseError::ImportError { line: Some(1), .. })
+        ));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_csv_export_keeps_tagged_values() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE \"abc__ext__files\" (id TEXT PRIMARY KEY, data BLOB, size INTEGER, haex_timestamp TEXT);
+             INSERT INTO \"abc__ext__files\" VALUES ('1', x'00FF2C22', 9007199254740993, '1/1');",
+        )
+        .unwrap();
+        let dir = std::env::temp_dir().join(format!("haex_export_{}", uuid::Uuid::new_v4()));
+
+        let exported = export_tables(
+            &conn,
+            &["abc__ext__files".to_string()],
+            None,
+            DataFormat::Csv,
+            &dir,
+        )
+        .unwrap();
+        let rows = parse_csv(&fs::read_to_string(&exported[0].path).unwrap()).unwrap();
+
+        let data = ValueConverter::json_to_rusqlite_value(&rows[0].values["data"]).unwrap();
+        let size = ValueConverter::json_to_rusqlite_value(&rows[0].values["size"]).unwrap();
+        assert_eq!(
+            data,
+            rusqlite::types::Value::Blob(vec![0x00, 0xFF, 0x2C, 0x22])
+        );
+        assert_eq!(size, rusqlite::types::Value::Integer(9007199254740993));
+        assert_eq!(
+            decode_csv_field("{\"note\":1}".to_string()),
+            JsonValue::from("{\"note\":1}")
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_import_writes_hlc_timestamps_and_crdt_logs() {
+        let mut conn = setup_db();
+        conn.create_scalar_function(
+            trigger::UUID_FUNCTION_NAME,
+            0,
+            rusqlite::functions::FunctionFlags::SQLITE_UTF8,
+            |_| Ok(uuid::Uuid::new_v4().to_string()),
+        )
+        .unwrap();
+        conn.execute_batch(&format!(
+            "CREATE TABLE {TABLE_CRDT_LOGS} (id TEXT PRIMARY KEY, haex_timestamp TEXT, op_type TEXT, table_name TEXT, row_pks TEXT, column_name TEXT, new_value TEXT, old_value TEXT);
+             CREATE TABLE {TABLE_CRDT_CONFIGS} (key TEXT PRIMARY KEY, value TEXT);"
+        ))
+        .unwrap();
+        let tx = conn.transaction().unwrap();
+        trigger::setup_triggers_for_table(&tx, "abc__ext__notes", false).unwrap();
+        tx.commit().unwrap();
+
+        let path = std::env::temp_dir().join(format!("haex_import_{}.jsonl", uuid::Uuid::new_v4()));
+        fs::write(
+            &path,
+            "{\"id\":\"1\",\"title\":\"dup\"}\n{\"id\":\"3\",\"title\":\"new\",\"haex_timestamp\":\"9/1\"}\n",
+        )
+        .unwrap();
+
+        let request = ImportRequest {
+            path: path.to_string_lossy().to_string(),
+            table: "abc__ext__notes".to_string(),
+            format: DataFormat::Jsonl,
+            column_mapping: HashMap::new(),
+            dry_run: false,
+        };
+        let report = import_table(&mut conn, &HlcService::new_for_tests(), &request).unwrap();
+        assert_eq!(report.imported, 1);
+        assert_eq!(report.conflicts.len(), 1);
+
+        // Der Zeitstempel aus der Datei wird verworfen und neu vergeben
+        let timestamp: String = conn
+            .query_row(
+                "SELECT haex_timestamp FROM \"abc__ext__notes\" WHERE id = '3'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_ne!(timestamp, "9/1");
+
+        let logged: Vec<(String, String)> = conn
+            .prepare(&format!(
+                "SELECT row_pks, column_name FROM {TABLE_CRDT_LOGS} WHERE table_name = 'abc__ext__notes' AND op_type = 'INSERT' AND haex_timestamp = ?1 ORDER BY column_name"
+            ))
+            .unwrap()
+            .query_map([&timestamp], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let pks = "{\"id\":\"3\"}".to_string();
+        assert_eq!(
+            logged,
+            vec![
+                (pks.clone(), "body".to_string()),
+                (pks.clone(), HLC_TIMESTAMP_COLUMN.to_string()),
+                (pks, "title".to_string())
+            ]
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,223 @@
+// src-tauri/src/database/vault.rs
+// Registry der geöffneten Vaults. Jede Vault hat eine eigene Verbindung, einen eigenen
+// HLC-Service und eigene geladene Extensions - mehrere Vaults können gleichzeitig offen sein.
+
+use crate::crdt::hlc::HlcService;
+use crate::database::changes::ChangeFeed;
+use crate::database::error::DatabaseError;
+use crate::database::explain::SlowQueryLog;
+use crate::database::live::LiveQueryRegistry;
+use crate::database::pool::{ReadPool, DEFAULT_READER_COUNT};
+use crate::database::worker::{run_blocking, DbWorker};
+use crate::database::DbConnection;
+use crate::extension::core::ExtensionManager;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use ts_rs::TS;
+
+/// Vault-ID: SHA-256 des Pfads als Hex-String, stabil über Neustarts hinweg
+pub fn vault_id_for_path(path: &str) -> String {
+    hex::encode(Sha256::digest(path.as_bytes()))
+}
+
+/// Alles, was zu einer geöffneten Vault gehört
+pub struct VaultSession {
+    pub id: String,
+    pub path: String,
+    /// Einzige Schreib-Verbindung - alle Writes und CRDT-Transaktionen laufen hierüber
+    pub db: DbConnection,
+    /// Lese-Verbindungen für SELECTs
+    pub readers: ReadPool,
+    /// Committete Änderungen der Schreib-Verbindung (siehe `changes`)
+    pub changes: Arc<ChangeFeed>,
+    /// Thread, auf dem async Commands ihre Schreibzugriffe ausführen
+    pub writer: DbWorker,
+    pub hlc: Mutex<HlcService>,
+    pub extension_manager: ExtensionManager,
+    /// Langsame Queries von Dev-Extensions
+    pub slow_queries: SlowQueryLog,
+    /// Registrierte Live-Queries von Frontend und Extensions
+    pub live_queries: LiveQueryRegistry,
+    /// Aus dem Vault-Schlüssel abgeleiteter MAC-Schlüssel für den Integritäts-Cache der Extensions
+    pub integrity_key: [u8; 32],
+}
+
+impl VaultSession {
+    pub fn new(
+        path: &str,
+        key: &str,
+        conn: Connection,
+        hlc_service: HlcService,
+    ) -> Result<Self, DatabaseError> {
+        let id = vault_id_for_path(path);
+        let writer = DbWorker::spawn(format!("haex-db-{}", &id[..8]))?;
+        let changes = Arc::new(ChangeFeed::default());
+        changes.attach(&conn);
+
+        Ok(Self {
+            id,
+            path: path.to_string(),
+            db: DbConnection(Arc::new(Mutex::new(Some(conn))), changes.clone()),
+            readers: ReadPool::new(path, key, DEFAULT_READER_COUNT),
+            changes,
+            writer,
+            hlc: Mutex::new(hlc_service),
+            extension_manager: ExtensionManager::new(),
+            slow_queries: SlowQueryLog::default(),
+            live_queries: LiveQueryRegistry::default(),
+            integrity_key: Sha256::new()
+                .chain_update(b"haex-extension-integrity:")
+                .chain_update(key.as_bytes())
+                .finalize()
+                .into(),
+        })
+    }
+
+    /// Führt Schreibzugriffe (Schreib-Verbindung, HLC) auf dem Worker-Thread der Vault aus
+    pub async fn write<T, F>(self: &Arc<Self>, f: F) -> Result<T, DatabaseError>
+    where
+        F: FnOnce(&VaultSession) -> Result<T, DatabaseError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let session = self.clone();
+        self.writer.run(move || f(&session)).await
+    }
+
+    /// Führt Lesezugriffe auf dem Blocking-Threadpool aus, damit mehrere Leser parallel laufen
+    pub async fn read<T, F>(self: &Arc<Self>, f: F) -> Result<T, DatabaseError>
+    where
+        F: FnOnce(&VaultSession) -> Result<T, DatabaseError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let session = self.clone();
+        run_blocking(move || f(&session)).await
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenVaultInfo {
+    pub id: String,
+    pub path: String,
+}
+
+#[derive(Default)]
+pub struct VaultRegistry {
+    sessions: Mutex<HashMap<String, Arc<VaultSession>>>,
+}
+
+impl VaultRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(
+        &self,
+    ) -> Result<std::sync::MutexGuard<'_, HashMap<String, Arc<VaultSession>>>, DatabaseError> {
+        self.sessions.lock().map_err(|e| DatabaseError::LockError {
+            reason: e.to_string(),
+        })
+    }
+
+    /// Registriert eine Session. Eine bereits offene Session derselben Vault wird ersetzt
+    /// und zurückgegeben, damit der Aufrufer sie schließen kann.
+    pub fn insert(
+        &self,
+        session: VaultSession,
+    ) -> Result<(Arc<VaultSession>, Option<Arc<VaultSession>>), DatabaseError> {
+        let session = Arc::new(session);
+        let previous = self.lock()?.insert(session.id.clone(), session.clone());
+        Ok((session, previous))
+    }
+
+    /// Liefert die Session zu einer Vault-ID
+    pub fn get(&self, vault_id: &str) -> Result<Arc<VaultSession>, DatabaseError> {
+        self.lock()?
+            .get(vault_id)
+            .cloned()
+            .ok_or_else(|| DatabaseError::VaultNotOpen {
+                vault_id: vault_id.to_string(),
+            })
+    }
+
+    pub fn remove(&self, vault_id: &str) -> Result<Arc<VaultSession>, DatabaseError> {
+        self.lock()?
+            .remove(vault_id)
+            .ok_or_else(|| DatabaseError::VaultNotOpen {
+                vault_id: vault_id.to_string(),
+            })
+    }
+
+    pub fn contains(&self, vault_id: &str) -> Result<bool, DatabaseError> {
+        Ok(self.lock()?.contains_key(vault_id))
+    }
+
+    pub fn list(&self) -> Result<Vec<OpenVaultInfo>, DatabaseError> {
+        let mut vaults: Vec<OpenVaultInfo> = self
+            .lock()?
+            .values()
+            .map(|session| OpenVaultInfo {
+                id: session.id.clone(),
+                path: session.path.clone(),
+            })
+            .collect();
+        vaults.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(vaults)
+    }
+
+    /// Alle geöffneten Sessions, z.B. für Prüfungen ohne Vault-Kontext
+    pub fn sessions(&self) -> Result<Vec<Arc<VaultSession>>, DatabaseError> {
+        Ok(self.lock()?.values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(path: &str) -> VaultSession {
+        VaultSession::new(
+            path,
+            "key",
+            Connection::open_in_memory().unwrap(),
+            HlcService::new(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sessions_are_isolated_per_vault() {
+        let registry = VaultRegistry::new();
+        let (privat, _) = registry.insert(session("/vaults/privat.db")).unwrap();
+        let (arbeit, _) = registry.insert(session("/vaults/arbeit.db")).unwrap();
+
+        assert_ne!(privat.id, arbeit.id);
+        assert_eq!(registry.list().unwrap().len(), 2);
+        assert_eq!(registry.get(&arbeit.id).unwrap().path, "/vaults/arbeit.db");
+
+        // Erneutes Öffnen ersetzt nur die Session derselben Vault
+        let (_, previous) = registry.insert(session("/vaults/privat.db")).unwrap();
+        assert!(previous.is_some_and(|p| Arc::ptr_eq(&p, &privat)));
+        assert_eq!(registry.list().unwrap().len(), 2);
+
+        registry.remove(&privat.id).unwrap();
+        assert!(matches!(
+            registry.get(&privat.id),
+            Err(DatabaseError::VaultNotOpen { .. })
+        ));
+        assert!(registry.contains(&arbeit.id).unwrap());
+    }
+
+    #[test]
+    fn test_vault_id_matches_frontend_hash() {
+        // sha256("abc")
+        assert_eq!(
+            vault_id_for_path("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+// src-tauri/src/database/vault_meta.rs
+// Unverschlüsselte Metadaten-Datei neben jeder Vault ("{name}.meta.json"),
+// damit list_vaults Infos anzeigen kann, ohne die Datenbank zu öffnen.
+
+use crate::database::error::DatabaseError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use ts_rs::TS;
+
+const METADATA_EXTENSION: &str = "meta.json";
+
+/// Zusatzdateien von SQLite im WAL-Modus, die beim Umbenennen mitwandern müssen
+const VAULT_AUX_SUFFIXES: &[&str] = &["-wal", "-shm"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultMetadata {
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Unix-Zeit in Sekunden
+    #[serde(default)]
+    pub created_at: Option<u64>,
+    /// Unix-Zeit in Sekunden, wird von initialize_session gesetzt
+    #[serde(default)]
+    pub last_opened_at: Option<u64>,
+    /// Zuletzt angewendete Migration (siehe migrations::get_applied_version)
+    #[serde(default)]
+    pub schema_version: Option<i64>,
+}
+
+/// Pfad der Metadaten-Datei zu einer Vault, z.B. "vaults/privat.db" -> "vaults/privat.meta.json"
+pub fn metadata_path(vault_path: &Path) -> PathBuf {
+    vault_path.with_extension(METADATA_EXTENSION)
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Liest die Metadaten einer Vault. Fehlende oder beschädigte Dateien ergeben `None`,
+/// da die Metadaten nur eine Ergänzung sind und jederzeit neu geschrieben werden können.
+pub fn read_metadata(vault_path: &Path) -> Option<VaultMetadata> {
+    let path = metadata_path(vault_path);
+    let content = fs::read_to_string(&path).ok()?;
+
+    match serde_json::from_str(&content) {
+        Ok(metadata) => Some(metadata),
+        Err(e) => {
+            tracing::warn!("Ignoring invalid vault metadata '{}': {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Schreibt die Metadaten über eine temporäre Datei + rename, damit nie eine halbe Datei entsteht.
+pub fn write_metadata(vault_path: &Path, metadata: &VaultMetadata) -> Result<(), DatabaseError> {
+    let path = metadata_path(vault_path);
+    let temp_path = path.with_extension(format!("{METADATA_EXTENSION}.tmp"));
+
+    let content =
+        serde_json::to_string_pretty(metadata).map_err(|e| DatabaseError::SerializationError {
+            reason: e.to_string(),
+        })?;
+
+    fs::write(&temp_path, content).map_err(|e| DatabaseError::IoError {
+        path: temp_path.display().to_string(),
+        reason: format!("Failed to write vault metadata: {e}"),
+    })?;
+
+    fs::rename(&temp_path, &path).map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        DatabaseError::IoError {
+            path: path.display().to_string(),
+            reason: format!("Failed to write vault metadata: {e}"),
+        }
+    })
+}
+
+/// Aktualisiert die Metadaten nach dem Öffnen einer Vault.
+/// Für Vaults ohne Metadaten wird `created_at` aus dem Dateisystem übernommen.
+pub fn record_opened(vault_path: &Path, schema_version: Option<i64>) -> Result<(), DatabaseError> {
+    let mut metadata = read_metadata(vault_path).unwrap_or_default();
+
+    if metadata.created_at.is_none() {
+        metadata.created_at = fs::metadata(vault_path)
+            .and_then(|m| m.created().or_else(|_| m.modified()))
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs())
+            .or_else(|| Some(now_secs()));
+    }
+    metadata.last_opened_at = Some(now_secs());
+    metadata.schema_version = schema_version;
+
+    write_metadata(vault_path, &metadata)
+}
+
+/// Benennt eine Vault samt WAL-Dateien und Metadaten um.
+///
+/// Entweder werden alle vorhandenen Dateien verschoben oder keine: schlägt ein Schritt fehl,
+/// werden die bereits verschobenen Dateien zurückbenannt.
+pub fn rename_vault_files(from: &Path, to: &Path) -> Result<(), DatabaseError> {
+    if !from.exists() {
+        return Err(DatabaseError::IoError {
+            path: from.display().to_string(),
+            reason: "Vault does not exist".to_string(),
+        });
+    }
+
+    let mut moves: Vec<(PathBuf, PathBuf)> = vec![(from.to_path_buf(), to.to_path_buf())];
+    for suffix in VAULT_AUX_SUFFIXES {
+        moves.push((
+            PathBuf::from(format!("{}{suffix}", from.display())),
+            PathBuf::from(format!("{}{suffix}", to.display())),
+        ));
+    }
+    moves.push((metadata_path(from), metadata_path(to)));
+    moves.retain(|(source, _)| source.exists());
+
+    if let Some((_, target)) = moves.iter().find(|(_, target)| target.exists()) {
+        return Err(DatabaseError::IoError {
+            path: target.display().to_string(),
+            reason: "Target already exists".to_string(),
+        });
+    }
+
+    let mut done: Vec<&(PathBuf, PathBuf)> = Vec::new();
+    for entry in &moves {
+        let (source, target) = entry;
+        if let Err(e) = fs::rename(source, target) {
+            for (source, target) in done.iter().rev() {
+                let _ = fs::rename(target, source);
+            }
+            return Err(DatabaseError::IoError {
+                path: source.display().to_string(),
+                reason: format!("Failed to rename vault: {e}"),
+            });
+        }
+        done.push(entry);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("haex_vaults_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_record_opened_keeps_user_fields() {
+        let dir = temp_dir();
+        let vault = dir.join("privat.db");
+        fs::write(&vault, b"db").unwrap();
+
+        assert_eq!(read_metadata(&vault), None);
+
+        let metadata = VaultMetadata {
+            display_name: Some("Privat".to_string()),
+            color: Some("#ff0000".to_string()),
+            ..Default::default()
+        };
+        write_metadata(&vault, &metadata).unwrap();
+        record_opened(&vault, Some(42)).unwrap();
+
+        let stored = read_metadata(&vault).unwrap();
+        assert_eq!(stored.display_name.as_deref(), Some("Privat"));
+        assert_eq!(stored.color.as_deref(), Some("#ff0000"));
+        assert_eq!(stored.schema_version, Some(42));
+        assert!(stored.created_at.is_some());
+        assert!(stored.last_opened_at.is_some());
+        assert!(dir.join("privat.meta.json").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_rename_moves_all_files_or_none() {
+        let dir = temp_dir();
+        let vault = dir.join("alt.db");
+        fs::write(&vault, b"db").unwrap();
+        fs::write(dir.join("alt.db-wal"), b"wal").unwrap();
+        write_metadata(&vault, &VaultMetadata::default()).unwrap();
+
+        // Ziel-Metadaten existieren bereits -> nichts wird verschoben
+        let blocked = dir.join("blockiert.db");
+        fs::write(metadata_path(&blocked), b"{}").unwrap();
+        assert!(rename_vault_files(&vault, &blocked).is_err());
+        assert!(vault.exists());
+        assert!(!blocked.exists());
+
+        let renamed = dir.join("neu.db");
+        rename_vault_files(&vault, &renamed).unwrap();
+        assert!(!vault.exists());
+        assert!(!dir.join("alt.db-wal").exists());
+        assert!(renamed.exists());
+        assert!(dir.join("neu.db-wal").exists());
+        assert!(dir.join("neu.meta.json").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,136 @@
+// src-tauri/src/database/worker.rs
+// Blockierende SQLite-Arbeit läuft nicht auf den Tokio-Workern: Schreibzugriffe gehen an
+// einen eigenen Thread pro Vault (DbWorker), Lesezugriffe auf den Blocking-Threadpool.
+
+use crate::database::error::DatabaseError;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{mpsc, Mutex};
+use tokio::sync::oneshot;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Eigener Thread, der Jobs nacheinander in Eingangsreihenfolge abarbeitet
+pub struct DbWorker {
+    sender: Mutex<Option<mpsc::Sender<Job>>>,
+}
+
+impl DbWorker {
+    pub fn spawn(name: String) -> Result<Self, DatabaseError> {
+        let (sender, receiver) = mpsc::channel::<Job>();
+
+        std::thread::Builder::new()
+            .name(name)
+            .spawn(move || {
+                // Endet, sobald der Sender geschlossen wurde und alle Jobs erledigt sind
+                for job in receiver {
+                    job();
+                }
+            })
+            .map_err(|e| DatabaseError::DatabaseError {
+                reason: format!("Failed to start database worker: {e}"),
+            })?;
+
+        Ok(Self {
+            sender: Mutex::new(Some(sender)),
+        })
+    }
+
+    /// Führt `f` auf dem Worker-Thread aus und wartet asynchron auf das Ergebnis
+    pub async fn run<T, F>(&self, f: F) -> Result<T, DatabaseError>
+    where
+        F: FnOnce() -> Result<T, DatabaseError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (result_tx, result_rx) = oneshot::channel();
+
+        let job: Job = Box::new(move || {
+            // Ein Panic darf den Worker nicht beenden, sonst ist die Vault unbenutzbar
+            let result = catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| {
+                Err(DatabaseError::DatabaseError {
+                    reason: "Database job panicked".to_string(),
+                })
+            });
+            let _ = result_tx.send(result);
+        });
+
+        {
+            let sender = self
+                .sender
+                .lock()
+                .map_err(|e| DatabaseError::MutexPoisoned {
+                    reason: e.to_string(),
+                })?;
+            let sender = sender
+                .as_ref()
+                .ok_or_else(|| DatabaseError::ConnectionError {
+                    reason: "Vault is closed".to_string(),
+                })?;
+            sender
+                .send(job)
+                .map_err(|_| DatabaseError::ConnectionError {
+                    reason: "Database worker stopped".to_string(),
+                })?;
+        }
+
+        result_rx
+            .await
+            .map_err(|_| DatabaseError::ConnectionError {
+                reason: "Database worker stopped before finishing the job".to_string(),
+            })?
+    }
+
+    /// Nimmt keine neuen Jobs mehr an. Bereits eingereihte Jobs laufen noch zu Ende.
+    pub fn shutdown(&self) {
+        if let Ok(mut sender) = self.sender.lock() {
+            sender.take();
+        }
+    }
+}
+
+/// Führt `f` auf dem Blocking-Threadpool der Runtime aus
+pub async fn run_blocking<T, F>(f: F) -> Result<T, DatabaseError>
+where
+    F: FnOnce() -> Result<T, DatabaseError> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| DatabaseError::DatabaseError {
+            reason: format!("Blocking database task failed: {e}"),
+        })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_jobs_run_in_order_on_worker_thread() {
+        let worker = DbWorker::spawn("haex-db-test".to_string()).unwrap();
+
+        let order = std::sync::Arc::new(Mutex::new(Vec::new()));
+        for i in 0..3 {
+            let order = order.clone();
+            let thread_name = worker
+                .run(move || {
+                    order.lock().unwrap().push(i);
+                    Ok(std::thread::current().name().map(str::to_string))
+                })
+                .await
+                .unwrap();
+            assert_eq!(thread_name.as_deref(), Some("haex-db-test"));
+        }
+        assert_eq!(*order.lock().unwrap(), vec![0, 1, 2]);
+
+        // Ein Panic wird zum Fehler, der Worker bleibt benutzbar
+        let panicked: Result<(), _> = worker.run(|| panic!("boom")).await;
+        assert!(panicked.is_err());
+        assert_eq!(worker.run(|| Ok(42)).await.unwrap(), 42);
+
+        worker.shutdown();
+        assert!(matches!(
+            worker.run(|| Ok(())).await,
+            Err(DatabaseError::ConnectionError { .. })
+        ));
+    }
+}
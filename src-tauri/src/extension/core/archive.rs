@@ -0,0 +1,376 @@
+// src-tauri/src/extension/core/archive.rs
+// Entpacken von .haextension-Archiven. Die Archive sind nicht vertrauenswürdig, daher wird jeder
+// Eintrag vor dem Schreiben geprüft: keine Symlinks, keine absoluten oder aus dem Zielordner
+// führenden Pfade, keine doppelten Einträge (auch nicht in anderer Groß-/Kleinschreibung), nur
+// erlaubte Dateitypen. Anzahl, Gesamtgröße und Kompressionsrate sind begrenzt (Zip-Bomben);
+// die Größe wird beim Schreiben gezählt, nicht den Angaben im Archiv geglaubt.
+
+use crate::extension::error::ExtensionError;
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Read, Seek};
+use std::path::{Component, Path, PathBuf};
+use zip::ZipArchive;
+
+/// Dateiendungen, die ein Extension-Paket enthalten darf (klein geschrieben)
+#[rustfmt::skip]
+const ALLOWED_EXTENSIONS: &[&str] = &[
+    // Web
+    "html", "htm", "js", "mjs", "cjs", "css", "map", "json", "webmanifest", "wasm", "xml",
+    // Schema-Migrationen
+    "sql",
+    // Bilder
+    "svg", "png", "jpg", "jpeg", "gif", "webp", "avif", "ico", "bmp",
+    // Schriften
+    "woff", "woff2", "ttf", "otf", "eot",
+    // Medien
+    "mp3", "mp4", "webm", "ogg", "wav",
+    // Text
+    "txt", "md",
+];
+
+/// Dateien ohne Endung, die trotzdem erlaubt sind
+const ALLOWED_PLAIN_FILES: &[&str] = &["license", "licence", "notice", "readme", "changelog"];
+
+/// Unterhalb dieser Größe wird die Kompressionsrate eines Eintrags nicht bewertet
+const RATIO_CHECK_MIN_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ArchiveLimits {
+    /// Maximale Summe der entpackten Dateigrößen
+    pub max_total_size: u64,
+    /// Maximale Anzahl an Einträgen (Dateien und Ordner)
+    pub max_file_count: usize,
+    /// Maximales Verhältnis entpackt/komprimiert eines Eintrags
+    pub max_compression_ratio: u64,
+}
+
+impl Default for ArchiveLimits {
+    fn default() -> Self {
+        Self {
+            max_total_size: 512 * 1024 * 1024,
+            max_file_count: 10_000,
+            max_compression_ratio: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ArchiveStats {
+    /// Entpackte Einträge (Dateien und Ordner)
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Normalisierter relativer Pfad eines Eintrags oder `None`, wenn er absolut ist
+/// bzw. aus dem Zielordner hinausführt
+fn normalized_path(name: &str) -> Option<PathBuf> {
+    // Windows-Laufwerke und Backslash-Pfade werden auf jeder Plattform als absolut gewertet
+    let name = name.replace('\\', "/");
+    if name.starts_with('/') || name.as_bytes().get(1) == Some(&b':') {
+        return None;
+    }
+
+    let mut path = PathBuf::new();
+    for component in Path::new(&name).components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !path.pop() {
+                    return None;
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(path)
+}
+
+fn is_allowed_file_type(path: &Path) -> bool {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => ALLOWED_EXTENSIONS.contains(&ext.to_lowercase().as_str()),
+        None => path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| ALLOWED_PLAIN_FILES.contains(&name.to_lowercase().as_str())),
+    }
+}
+
+/// Entpackt ein Archiv nach `dest`. Bei einem Fehler bleiben bereits geschriebene Dateien
+/// liegen - der Aufrufer verwirft `dest` als Ganzes.
+pub fn extract_archive<R: Read + Seek>(
+    reader: R,
+    dest: &Path,
+    limits: &ArchiveLimits,
+) -> Result<ArchiveStats, ExtensionError> {
+    let mut archive = ZipArchive::new(reader).map_err(|e| ExtensionError::InstallationFailed {
+        reason: format!("Invalid ZIP: {e}"),
+    })?;
+
+    let mut stats = ArchiveStats::default();
+    let mut seen = HashSet::new();
+
+    for index in 0..archive.len() {
+        let mut entry =
+            archive
+                .by_index(index)
+                .map_err(|e| ExtensionError::InstallationFailed {
+                    reason: format!("Cannot read ZIP entry {index}: {e}"),
+                })?;
+        let name = entry.name().to_string();
+
+        if entry.is_symlink() {
+            return Err(ExtensionError::ArchiveSymlink { path: name });
+        }
+
+        let relative = match (entry.enclosed_name(), normalized_path(&name)) {
+            (Some(_), Some(path)) => path,
+            _ => {
+                return Err(ExtensionError::ArchiveUnsafePath {
+                    path: name,
+                    reason: "absolute or outside of the extension directory".to_string(),
+                })
+            }
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+
+        // Auf Dateisystemen ohne Groß-/Kleinschreibung würden sich solche Einträge überschreiben
+        if !seen.insert(relative.to_string_lossy().to_lowercase()) {
+            return Err(ExtensionError::ArchiveDuplicateEntry { path: name });
+        }
+
+        // Auch Ordner zählen, sonst ließen sich beliebig viele leere Verzeichnisse anlegen
+        stats.files += 1;
+        if stats.files > limits.max_file_count {
+            return Err(ExtensionError::ArchiveTooManyFiles {
+                limit: limits.max_file_count,
+            });
+        }
+
+        let out_path = dest.join(&relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path).map_err(|e| {
+                ExtensionError::filesystem_with_path(out_path.display().to_string(), e)
+            })?;
+            continue;
+        }
+
+        if !is_allowed_file_type(&relative) {
+            return Err(ExtensionError::ArchiveFileTypeNotAllowed { path: name });
+        }
+
+        // Angaben aus dem Archiv vorab prüfen, damit offensichtliche Bomben gar nicht erst
+        // entpackt werden
+        let remaining = limits.max_total_size - stats.bytes;
+        if entry.size() > remaining {
+            return Err(ExtensionError::ArchiveTooLarge {
+                limit: limits.max_total_size,
+            });
+        }
+        check_ratio(&name, entry.size(), entry.compressed_size(), limits)?;
+
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                ExtensionError::filesystem_with_path(parent.display().to_string(), e)
+            })?;
+        }
+        let mut file = fs::File::create(&out_path)
+            .map_err(|e| ExtensionError::filesystem_with_path(out_path.display().to_string(), e))?;
+
+        // Tatsächlich geschriebene Bytes zählen - die Größenangabe im Archiv kann falsch sein
+        let compressed_size = entry.compressed_size();
+        let written = io::copy(&mut (&mut entry).take(remaining + 1), &mut file).map_err(|e| {
+            ExtensionError::InstallationFailed {
+                reason: format!("Cannot extract {name}: {e}"),
+            }
+        })?;
+        if written > remaining {
+            return Err(ExtensionError::ArchiveTooLarge {
+                limit: limits.max_total_size,
+            });
+        }
+        check_ratio(&name, written, compressed_size, limits)?;
+
+        stats.bytes += written;
+    }
+
+    Ok(stats)
+}
+
+fn check_ratio(
+    name: &str,
+    size: u64,
+    compressed_size: u64,
+    limits: &ArchiveLimits,
+) -> Result<(), ExtensionError> {
+    if size < RATIO_CHECK_MIN_BYTES {
+        return Ok(());
+    }
+
+    let ratio = size / compressed_size.max(1);
+    if ratio > limits.max_compression_ratio {
+        return Err(ExtensionError::ArchiveCompressionRatio {
+            path: name.to_string(),
+            ratio,
+            limit: limits.max_compression_ratio,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipWriter};
+
+    fn archive(entries: &[(&str, &[u8])]) -> Cursor<Vec<u8>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        for (name, content) in entries {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        let mut cursor = writer.finish().unwrap();
+        cursor.set_position(0);
+        cursor
+    }
+
+    fn extract(
+        reader: Cursor<Vec<u8>>,
+        limits: &ArchiveLimits,
+    ) -> Result<ArchiveStats, ExtensionError> {
+        let dest = std::env::temp_dir().join(format!("haex_archive_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dest).unwrap();
+        let result = extract_archive(reader, &dest, limits);
+        fs::remove_dir_all(&dest).unwrap();
+        result
+    }
+
+    #[test]
+    fn extracts_regular_extension() {
+        let reader = archive(&[
+            ("index.html", b"<html></html>"),
+            ("assets/app.js", b"console.log(1)"),
+            ("haextension/manifest.json", b"{}"),
+            (
+                "haextension/migrations/0001_init.sql",
+                b"CREATE TABLE x (id TEXT);",
+            ),
+            ("LICENSE", b"MIT"),
+        ]);
+        let stats = extract(reader, &ArchiveLimits::default()).unwrap();
+        assert_eq!(stats.files, 5);
+    }
+
+    #[test]
+    fn rejects_symlinks() {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .add_symlink("index.html", "/etc/passwd", SimpleFileOptions::default())
+            .unwrap();
+        let mut reader = writer.finish().unwrap();
+        reader.set_position(0);
+
+        let result = extract(reader, &ArchiveLimits::default());
+        assert!(matches!(result, Err(ExtensionError::ArchiveSymlink { .. })));
+    }
+
+    #[test]
+    fn rejects_unsafe_paths() {
+        for name in [
+            "/etc/cron.js",
+            "../outside.js",
+            "assets/../../outside.js",
+            "C:/evil.js",
+        ] {
+            let result = extract(archive(&[(name, b"x")]), &ArchiveLimits::default());
+            assert!(
+                matches!(result, Err(ExtensionError::ArchiveUnsafePath { .. })),
+                "{name} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_duplicate_entries() {
+        let reader = archive(&[("assets/app.js", b"a"), ("assets/APP.js", b"b")]);
+        let result = extract(reader, &ArchiveLimits::default());
+        assert!(matches!(
+            result,
+            Err(ExtensionError::ArchiveDuplicateEntry { .. })
+        ));
+
+        let reader = archive(&[("app.js", b"a"), ("assets/../app.js", b"b")]);
+        let result = extract(reader, &ArchiveLimits::default());
+        assert!(matches!(
+            result,
+            Err(ExtensionError::ArchiveDuplicateEntry { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_disallowed_file_types() {
+        for name in ["install.sh", "bin/tool.exe", "native.so", "run"] {
+            let result = extract(archive(&[(name, b"x")]), &ArchiveLimits::default());
+            assert!(
+                matches!(
+                    result,
+                    Err(ExtensionError::ArchiveFileTypeNotAllowed { .. })
+                ),
+                "{name} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn enforces_file_count_and_total_size() {
+        let limits = ArchiveLimits {
+            max_file_count: 2,
+            ..ArchiveLimits::default()
+        };
+        let reader = archive(&[("a.js", b"a"), ("b.js", b"b"), ("c.js", b"c")]);
+        assert!(matches!(
+            extract(reader, &limits),
+            Err(ExtensionError::ArchiveTooManyFiles { limit: 2 })
+        ));
+
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for dir in ["a/", "b/", "c/"] {
+            writer
+                .add_directory(dir, SimpleFileOptions::default())
+                .unwrap();
+        }
+        let mut reader = writer.finish().unwrap();
+        reader.set_position(0);
+        assert!(matches!(
+            extract(reader, &limits),
+            Err(ExtensionError::ArchiveTooManyFiles { limit: 2 })
+        ));
+
+        let limits = ArchiveLimits {
+            max_total_size: 10,
+            ..ArchiveLimits::default()
+        };
+        let reader = archive(&[("a.js", b"123456"), ("b.js", b"789012")]);
+        assert!(matches!(
+            extract(reader, &limits),
+            Err(ExtensionError::ArchiveTooLarge { limit: 10 })
+        ));
+    }
+
+    #[test]
+    fn detects_zip_bombs() {
+        let zeros = vec![0u8; 8 * 1024 * 1024];
+        let reader = archive(&[("bomb.js", &zeros)]);
+        let result = extract(reader, &ArchiveLimits::default());
+        assert!(matches!(
+            result,
+            Err(ExtensionError::ArchiveCompressionRatio { .. })
+        ));
+    }
+}
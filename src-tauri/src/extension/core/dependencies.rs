@@ -0,0 +1,614 @@
+// src-tauri/src/extension/core/dependencies.rs
+// Abhängigkeiten zwischen Extensions. Eine Extension deklariert im Manifest andere Extensions
+// (public_key + name + semver-Bereich), die bei ihrer Installation bereits in passender Version
+// installiert sein müssen, und Tabellen, die sie für abhängige Extensions freigibt.
+// Abhängige Extensions erhalten auf diese Tabellen Zugriff ohne eigene Freigabe. Eine Extension,
+// von der andere abhängen, lässt sich nicht deinstallieren und nur auf Versionen aktualisieren,
+// die die Bereiche ihrer Abhängigen weiterhin erfüllen.
+
+use crate::extension::core::manager::ExtensionManager;
+use crate::extension::core::manifest::{ExtensionDependency, ExtensionManifest, TableAccess};
+use crate::extension::core::types::Extension;
+use crate::extension::database::migrations;
+use crate::extension::error::ExtensionError;
+use crate::extension::permissions::types::DbAction;
+use crate::extension::registry::client::compare_versions;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use ts_rs::TS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Comparator {
+    op: Op,
+    version: String,
+    /// Die Version stammt aus einem angegebenen Pre-Release (nicht aus einer `-0`-Grenze)
+    pre_release: bool,
+}
+
+impl Comparator {
+    fn new(op: Op, version: String) -> Self {
+        Self {
+            op,
+            version,
+            pre_release: false,
+        }
+    }
+
+    /// Vergleich mit der angegebenen Version selbst, inklusive ihres Pre-Release-Teils
+    fn at(op: Op, partial: &Partial) -> Self {
+        Self {
+            op,
+            version: partial.version(),
+            pre_release: partial.pre.is_some(),
+        }
+    }
+
+    /// Ein Pre-Release ist nur zulässig, wenn der Bereich selbst ein Pre-Release
+    /// derselben Kernversion nennt
+    fn allows_pre_release_of(&self, core: &str) -> bool {
+        self.pre_release
+            && pre_release_core(&self.version)
+                .is_some_and(|own| compare_versions(own, core) == Ordering::Equal)
+    }
+
+    fn matches(&self, version: &str) -> bool {
+        let ordering = compare_versions(version, &self.version);
+        match self.op {
+            Op::Eq => ordering == Ordering::Equal,
+            Op::Gt => ordering == Ordering::Greater,
+            Op::Ge => ordering != Ordering::Less,
+            Op::Lt => ordering == Ordering::Less,
+            Op::Le => ordering != Ordering::Greater,
+        }
+    }
+}
+
+/// Kernversion von `version`, falls es sich um ein Pre-Release handelt
+fn pre_release_core(version: &str) -> Option<&str> {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split('+').next().unwrap_or(version);
+    version.split_once('-').map(|(core, _)| core)
+}
+
+/// Teilweise angegebene Version wie `1`, `1.2`, `1.2.x` oder `1.2.3-beta.1`
+struct Partial {
+    parts: Vec<u64>,
+    pre: Option<String>,
+}
+
+impl Partial {
+    fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim_start_matches('v');
+        let input = input.split('+').next().unwrap_or(input);
+        let (core, pre) = match input.split_once('-') {
+            Some((core, pre)) if !pre.is_empty() => (core, Some(pre.to_string())),
+            Some(_) => return Err("empty pre-release".to_string()),
+            None => (input, None),
+        };
+
+        let mut parts = Vec::new();
+        let mut wildcard = false;
+        for part in core.split('.') {
+            match part {
+                "x" | "X" | "*" => wildcard = true,
+                _ if wildcard => return Err(format!("'{part}' after a wildcard")),
+                _ => parts.push(
+                    part.parse::<u64>()
+                        .map_err(|_| format!("'{part}' is not a number"))?,
+                ),
+            }
+        }
+        if core.split('.').count() > 3 {
+            return Err("more than three version parts".to_string());
+        }
+        if pre.is_some() && parts.len() < 3 {
+            return Err("pre-release requires a full version".to_string());
+        }
+
+        Ok(Self { parts, pre })
+    }
+
+    fn is_full(&self) -> bool {
+        self.parts.len() == 3
+    }
+
+    fn part(&self, index: usize) -> u64 {
+        self.parts.get(index).copied().unwrap_or(0)
+    }
+
+    /// Version mit fehlenden Teilen als 0
+    fn version(&self) -> String {
+        let core = format!("{}.{}.{}", self.part(0), self.part(1), self.part(2));
+        match &self.pre {
+            Some(pre) => format!("{core}-{pre}"),
+            None => core,
+        }
+    }
+
+    /// Kleinste Version oberhalb des angegebenen Teils, z.B. `1.2` -> `1.3.0-0`.
+    /// Das `-0` schließt auch Pre-Releases der nächsten Version aus.
+    fn bump(&self, index: usize) -> String {
+        match index {
+            0 => format!("{}.0.0-0", self.part(0) + 1),
+            1 => format!("{}.{}.0-0", self.part(0), self.part(1) + 1),
+            _ => format!("{}.{}.{}-0", self.part(0), self.part(1), self.part(2) + 1),
+        }
+    }
+
+    /// Obergrenze, wenn nur ein Teil angegeben ist: `1` -> `<2.0.0-0`, `1.2` -> `<1.3.0-0`
+    fn range(&self) -> Vec<Comparator> {
+        if self.parts.is_empty() {
+            return Vec::new();
+        }
+        vec![
+            Comparator::at(Op::Ge, self),
+            Comparator::new(Op::Lt, self.bump(self.parts.len() - 1)),
+        ]
+    }
+}
+
+/// semver-Bereich im npm-Stil, z.B. `^1.2.0`, `~1.4`, `1.x`, `>=1.0.0 <2.0.0` oder `*`.
+/// Alternativen werden mit `||` getrennt, Bedingungen innerhalb einer Alternative mit Leerzeichen.
+/// Eine vollständige Version ohne Operator verlangt genau diese Version. Pre-Releases erfüllen
+/// einen Bereich nur, wenn eine Bedingung derselben Alternative ein Pre-Release derselben
+/// Kernversion nennt (`>=1.2.3-beta.1` erlaubt `1.2.3-beta.2`, aber nicht `1.3.0-beta`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    alternatives: Vec<Vec<Comparator>>,
+}
+
+impl VersionReq {
+    pub fn parse(input: &str) -> Result<Self, ExtensionError> {
+        let invalid = |reason: String| ExtensionError::ManifestError {
+            reason: format!("Invalid version requirement '{input}': {reason}"),
+        };
+
+        let mut alternatives = Vec::new();
+        for alternative in input.split("||") {
+            let mut comparators = Vec::new();
+            let mut tokens = alternative.split_whitespace();
+            while let Some(token) = tokens.next() {
+                // `>= 1.0.0` ist gleichbedeutend mit `>=1.0.0`
+                let token = if token.chars().all(|c| "<>=^~".contains(c)) {
+                    let version = tokens
+                        .next()
+                        .ok_or_else(|| invalid(format!("'{token}' without version")))?;
+                    format!("{token}{version}")
+                } else {
+                    token.to_string()
+                };
+                comparators.extend(Self::parse_comparator(&token).map_err(invalid)?);
+            }
+            alternatives.push(comparators);
+        }
+
+        Ok(Self { alternatives })
+    }
+
+    fn parse_comparator(token: &str) -> Result<Vec<Comparator>, String> {
+        let (op, version) = ["^", "~", ">=", "<=", ">", "<", "="]
+            .iter()
+            .find_map(|op| token.strip_prefix(op).map(|rest| (*op, rest)))
+            .unwrap_or(("", token));
+        if version.is_empty() {
+            return Err(format!("'{token}' without version"));
+        }
+
+        let partial = Partial::parse(version)?;
+        if partial.parts.is_empty() {
+            return match op {
+                "" | "=" | ">=" | "^" | "~" => Ok(Vec::new()),
+                _ => Err(format!("'{token}' matches no version")),
+            };
+        }
+
+        let comparators = match op {
+            "^" => {
+                // Die erste Stelle ungleich 0 darf sich nicht ändern
+                let fixed = (0..partial.parts.len())
+                    .find(|&i| partial.part(i) != 0)
+                    .unwrap_or(partial.parts.len() - 1);
+                vec![
+                    Comparator::at(Op::Ge, &partial),
+                    Comparator::new(Op::Lt, partial.bump(fixed.min(2))),
+                ]
+            }
+            "~" => vec![
+                Comparator::at(Op::Ge, &partial),
+                Comparator::new(Op::Lt, partial.bump(partial.parts.len().min(2) - 1)),
+            ],
+            "" | "=" if partial.is_full() => vec![Comparator::at(Op::Eq, &partial)],
+            "" | "=" => partial.range(),
+            ">" if partial.is_full() => vec![Comparator::at(Op::Gt, &partial)],
+            ">" => vec![Comparator::new(
+                Op::Ge,
+                partial.bump(partial.parts.len() - 1),
+            )],
+            ">=" => vec![Comparator::at(Op::Ge, &partial)],
+            "<" => vec![Comparator::at(Op::Lt, &partial)],
+            "<=" if partial.is_full() => vec![Comparator::at(Op::Le, &partial)],
+            _ => vec![Comparator::new(
+                Op::Lt,
+                partial.bump(partial.parts.len() - 1),
+            )],
+        };
+        Ok(comparators)
+    }
+
+    pub fn matches(&self, version: &str) -> bool {
+        let pre_release = pre_release_core(version);
+        self.alternatives.iter().any(|comparators| {
+            comparators.iter().all(|c| c.matches(version))
+                && pre_release
+                    .is_none_or(|core| comparators.iter().any(|c| c.allows_pre_release_of(core)))
+        })
+    }
+}
+
+impl TableAccess {
+    /// Exportierte Tabellen erlauben nur Zugriff auf Zeilen, keine Schemaänderungen
+    pub fn allows(&self, action: &DbAction) -> bool {
+        match self {
+            TableAccess::Read => matches!(action, DbAction::Read),
+            TableAccess::ReadWrite => matches!(action, DbAction::Read | DbAction::ReadWrite),
+        }
+    }
+}
+
+/// Stand einer Abhängigkeit für Vorschau und Installation
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyStatus {
+    pub public_key: String,
+    pub name: String,
+    pub requirement: String,
+    /// Installierte Version, falls die Abhängigkeit installiert ist
+    pub installed_version: Option<String>,
+    /// Ob die installierte Abhängigkeit aktiviert ist
+    pub enabled: bool,
+    /// Installiert, aktiviert und in passender Version
+    pub satisfied: bool,
+}
+
+/// Sucht eine installierte Extension, ohne sie als aktuelle Extension zu protokollieren
+fn find_installed(
+    manager: &ExtensionManager,
+    public_key: &str,
+    name: &str,
+) -> Result<Option<Extension>, ExtensionError> {
+    Ok(all_extensions(manager)?
+        .into_iter()
+        .find(|ext| ext.manifest.public_key == public_key && ext.manifest.name == name))
+}
+
+fn all_extensions(manager: &ExtensionManager) -> Result<Vec<Extension>, ExtensionError> {
+    let mut extensions: Vec<Extension> = manager
+        .dev_extensions
+        .lock()
+        .map_err(|e| ExtensionError::MutexPoisoned {
+            reason: e.to_string(),
+        })?
+        .values()
+        .cloned()
+        .collect();
+    extensions.extend(
+        manager
+            .production_extensions
+            .lock()
+            .map_err(|e| ExtensionError::MutexPoisoned {
+                reason: e.to_string(),
+            })?
+            .values()
+            .cloned(),
+    );
+    Ok(extensions)
+}
+
+fn declares(dependency: &ExtensionDependency, public_key: &str, name: &str) -> bool {
+    dependency.public_key == public_key && dependency.name == name
+}
+
+/// Prüft Abhängigkeiten und Exporte eines Manifests auf Gültigkeit
+pub fn validate_manifest(manifest: &ExtensionManifest) -> Result<(), ExtensionError> {
+    for dependency in manifest.dependencies.iter().flatten() {
+        VersionReq::parse(&dependency.version)?;
+        if declares(dependency, &manifest.public_key, &manifest.name) {
+            return Err(ExtensionError::ManifestError {
+                reason: format!("{} cannot depend on itself", manifest.name),
+            });
+        }
+    }
+
+    for table in manifest.exported_tables.iter().flatten() {
+        if table.name.is_empty()
+            || !table
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(ExtensionError::ManifestError {
+                reason: format!("Invalid exported table name: '{}'", table.name),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Stand aller Abhängigkeiten eines Manifests gegenüber den installierten Extensions
+pub fn status(
+    manager: &ExtensionManager,
+    manifest: &ExtensionManifest,
+) -> Result<Vec<DependencyStatus>, ExtensionError> {
+    let mut result = Vec::new();
+    for dependency in manifest.dependencies.iter().flatten() {
+        let requirement = VersionReq::parse(&dependency.version)?;
+        let installed = find_installed(manager, &dependency.public_key, &dependency.name)?;
+        let enabled = installed.as_ref().is_some_and(|ext| ext.enabled);
+        let installed_version = installed.map(|ext| ext.manifest.version);
+
+        // Eine deaktivierte Abhängigkeit gibt weder Tabellen frei noch läuft sie
+        result.push(DependencyStatus {
+            public_key: dependency.public_key.clone(),
+            name: dependency.name.clone(),
+            requirement: dependency.version.clone(),
+            satisfied: enabled
+                && installed_version
+                    .as_deref()
+                    .is_some_and(|version| requirement.matches(version)),
+            installed_version,
+            enabled,
+        });
+    }
+    Ok(result)
+}
+
+/// Bei der Installation müssen alle Abhängigkeiten in passender Version installiert und aktiviert sein
+pub fn ensure_satisfied(
+    manager: &ExtensionManager,
+    manifest: &ExtensionManifest,
+) -> Result<(), ExtensionError> {
+    validate_manifest(manifest)?;
+
+    if let Some(missing) = status(manager, manifest)?
+        .into_iter()
+        .find(|dependency| !dependency.satisfied)
+    {
+        return Err(ExtensionError::DependencyNotSatisfied {
+            name: missing.name,
+            requirement: missing.requirement,
+            reason: match missing.installed_version {
+                Some(version) if !missing.enabled => format!("version {version} is disabled"),
+                Some(version) => format!("version {version} is installed"),
+                None => "not installed".to_string(),
+            },
+        });
+    }
+    Ok(())
+}
+
+/// Installierte Extensions, die von `public_key`/`name` abhängen, mit ihrem Versionsbereich
+fn dependents(
+    manager: &ExtensionManager,
+    public_key: &str,
+    name: &str,
+) -> Result<Vec<(Extension, String)>, ExtensionError> {
+    Ok(all_extensions(manager)?
+        .into_iter()
+        .filter_map(|ext| {
+            let requirement = ext
+                .manifest
+                .dependencies
+                .iter()
+                .flatten()
+                .find(|dependency| declares(dependency, public_key, name))?
+                .version
+                .clone();
+            Some((ext, requirement))
+        })
+        .collect())
+}
+
+/// Eine Extension, von der andere abhängen, darf nicht deinstalliert werden
+pub fn ensure_not_required(
+    manager: &ExtensionManager,
+    public_key: &str,
+    name: &str,
+) -> Result<(), ExtensionError> {
+    let dependents = dependents(manager, public_key, name)?;
+    if dependents.is_empty() {
+        return Ok(());
+    }
+
+    let names: Vec<String> = dependents
+        .into_iter()
+        .map(|(ext, _)| ext.manifest.name)
+        .collect();
+    Err(ExtensionError::DependencyConflict {
+        name: name.to_string(),
+        reason: format!("required by {}", names.join(", ")),
+    })
+}
+
+/// Ein Update, Rollback oder Schlüsselwechsel darf die Bereiche abhängiger Extensions nicht verletzen
+pub fn ensure_dependents_accept(
+    manager: &ExtensionManager,
+    installed_public_key: &str,
+    name: &str,
+    manifest: &ExtensionManifest,
+) -> Result<(), ExtensionError> {
+    for (dependent, requirement) in dependents(manager, installed_public_key, name)? {
+        if manifest.public_key != installed_public_key {
+            return Err(ExtensionError::DependencyConflict {
+                name: name.to_string(),
+                reason: format!(
+                    "{} depends on publisher key {installed_public_key}",
+                    dependent.manifest.name
+                ),
+            });
+        }
+        if !VersionReq::parse(&requirement)?.matches(&manifest.version) {
+            return Err(ExtensionError::DependencyConflict {
+                name: name.to_string(),
+                reason: format!(
+                    "{} requires {requirement}, not {}",
+                    dependent.manifest.name, manifest.version
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Zugriff, den `extension` über eine ihrer Abhängigkeiten auf `table_name` hat.
+/// Deaktivierte Abhängigkeiten geben nichts frei.
+pub fn exported_table_access(
+    manager: &ExtensionManager,
+    extension: &Extension,
+    table_name: &str,
+) -> Result<Option<TableAccess>, ExtensionError> {
+    for dependency in extension.manifest.dependencies.iter().flatten() {
+        let prefix = migrations::table_prefix(&dependency.public_key, &dependency.name);
+        let Some(table) = table_name.strip_prefix(&prefix) else {
+            continue;
+        };
+        let Some(provider) = find_installed(manager, &dependency.public_key, &dependency.name)?
+        else {
+            continue;
+        };
+        if !provider.enabled {
+            continue;
+        }
+
+        return Ok(provider
+            .manifest
+            .exported_tables
+            .iter()
+            .flatten()
+            .find(|exported| exported.name == table)
+            .map(|exported| exported.access));
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(input: &str) -> VersionReq {
+        VersionReq::parse(input).unwrap()
+    }
+
+    #[test]
+    fn caret_keeps_first_non_zero_part() {
+        let r = req("^1.2.0");
+        assert!(r.matches("1.2.0"));
+        assert!(r.matches("1.9.3"));
+        assert!(!r.matches("1.1.9"));
+        assert!(!r.matches("2.0.0"));
+        assert!(!r.matches("2.0.0-beta.1"));
+
+        let r = req("^0.2.3");
+        assert!(r.matches("0.2.9"));
+        assert!(!r.matches("0.3.0"));
+
+        let r = req("^0.0.3");
+        assert!(r.matches("0.0.3"));
+        assert!(!r.matches("0.0.4"));
+    }
+
+    #[test]
+    fn tilde_and_partial_versions() {
+        let r = req("~1.4.2");
+        assert!(r.matches("1.4.7"));
+        assert!(!r.matches("1.5.0"));
+
+        let r = req("~1");
+        assert!(r.matches("1.9.0"));
+        assert!(!r.matches("2.0.0"));
+
+        let r = req("1.2.x");
+        assert!(r.matches("1.2.5"));
+        assert!(!r.matches("1.3.0"));
+
+        assert!(req("*").matches("3.1.4"));
+        assert!(req("").matches("0.0.1"));
+    }
+
+    #[test]
+    fn exact_versions_and_comparator_sets() {
+        let r = req("1.2.3");
+        assert!(r.matches("1.2.3"));
+        assert!(!r.matches("1.2.4"));
+
+        let r = req(">=1.0.0 <2.0.0");
+        assert!(r.matches("1.5.0"));
+        assert!(!r.matches("2.0.0"));
+        assert!(!r.matches("0.9.0"));
+
+        let r = req(">= 1.0.0 <= 1.4.0");
+        assert!(r.matches("1.4.0"));
+        assert!(!r.matches("1.4.1"));
+
+        let r = req(">1.2");
+        assert!(!r.matches("1.2.9"));
+        assert!(r.matches("1.3.0"));
+    }
+
+    #[test]
+    fn alternatives() {
+        let r = req("^1.0.0 || ^3.0.0");
+        assert!(r.matches("1.4.0"));
+        assert!(r.matches("3.0.1"));
+        assert!(!r.matches("2.0.0"));
+    }
+
+    #[test]
+    fn pre_releases_need_a_pre_release_comparator_on_the_same_version() {
+        let r = req("^1.2.0");
+        assert!(!r.matches("1.3.0-beta"));
+        assert!(!r.matches("1.2.0-rc.1"));
+        assert!(!req("*").matches("1.0.0-rc.1"));
+
+        let r = req(">=1.2.3-beta.1");
+        assert!(r.matches("1.2.3-beta.2"));
+        assert!(r.matches("1.2.3"));
+        assert!(r.matches("1.2.4"));
+        assert!(!r.matches("1.2.3-alpha.1"));
+        assert!(!r.matches("1.2.4-beta.1"));
+
+        let r = req("^1.2.3-beta.1 || ^2.0.0");
+        assert!(r.matches("1.2.3-rc.1"));
+        assert!(r.matches("1.9.0"));
+        assert!(!r.matches("1.9.0-rc.1"));
+        assert!(!r.matches("2.0.0-rc.1"));
+
+        assert!(req("1.2.3-beta.1").matches("1.2.3-beta.1"));
+    }
+
+    #[test]
+    fn rejects_invalid_requirements() {
+        for input in ["^", ">=", "1.a", "1.2.3.4", "1.x.3", "<*", "1.2-beta"] {
+            assert!(VersionReq::parse(input).is_err(), "{input} was accepted");
+        }
+    }
+
+    #[test]
+    fn table_access_never_allows_schema_changes() {
+        assert!(TableAccess::Read.allows(&DbAction::Read));
+        assert!(!TableAccess::Read.allows(&DbAction::ReadWrite));
+        assert!(TableAccess::ReadWrite.allows(&DbAction::ReadWrite));
+        assert!(!TableAccess::ReadWrite.allows(&DbAction::Create));
+        assert!(!TableAccess::ReadWrite.allows(&DbAction::AlterDrop));
+        assert!(!TableAccess::ReadWrite.allows(&DbAction::Delete));
+    }
+}
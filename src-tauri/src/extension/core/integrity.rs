@@ -0,0 +1,313 @@
+// src-tauri/src/extension/core/integrity.rs
+// Integritätsprüfung installierter Extensions. Beim Laden wird jede Version neu gehasht und
+// gegen die bei der Installation gespeicherte Signatur geprüft. Damit nicht bei jedem Start
+// alle Dateien gelesen werden, merkt sich ein Cache pro Version den Hash zusammen mit einem
+// Fingerprint (Anzahl, Größe und neueste mtime der Dateien). Passt der Fingerprint, wird nur
+// die Signatur gegen den gecachten Hash geprüft. Der Cache liegt im selben Ordner wie die
+// Extensions, daher trägt jeder Eintrag einen MAC mit einem aus dem Vault-Schlüssel abgeleiteten
+// Schlüssel. Einträge mit ungültigem MAC gelten als Cache-Miss.
+// Manipulierte Versionen werden deaktiviert und vom Protokoll-Handler nicht mehr ausgeliefert.
+
+use crate::extension::crypto::ExtensionCrypto;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+/// Cache ohne MAC aus früheren Versionen, wird beim Laden entfernt
+const LEGACY_CACHE_FILE: &str = "integrity-cache.json";
+const HMAC_BLOCK_SIZE: usize = 64;
+
+lazy_static::lazy_static! {
+    /// Manipulierte Versionen (Schlüssel siehe `key`) mit Grund
+    static ref TAMPERED: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+}
+
+/// Schlüssel einer installierten Version, entspricht dem Pfad unterhalb des Extension-Ordners
+pub fn key(public_key: &str, name: &str, version: &str) -> String {
+    format!("{public_key}/{name}/{version}")
+}
+
+/// Markiert eine Version als manipuliert
+pub fn mark_tampered(key: &str, reason: &str) {
+    if let Ok(mut tampered) = TAMPERED.lock() {
+        tampered.insert(key.to_string(), reason.to_string());
+    }
+}
+
+/// Hebt die Markierung auf, z.B. nach einer Neuinstallation
+pub fn clear_tampered(key: &str) {
+    if let Ok(mut tampered) = TAMPERED.lock() {
+        tampered.remove(key);
+    }
+}
+
+/// Grund, warum eine Version nicht ausgeliefert wird - `None`, wenn sie unverändert ist
+pub fn tampered_reason(key: &str) -> Option<String> {
+    TAMPERED.lock().ok().and_then(|t| t.get(key).cloned())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirFingerprint {
+    files: u64,
+    bytes: u64,
+    /// Neueste Änderungszeit aller Dateien in Nanosekunden seit der Unix-Epoche
+    modified_ns: u64,
+}
+
+/// Ermittelt den Fingerprint eines Verzeichnisses, ohne Dateiinhalte zu lesen
+pub fn fingerprint(dir: &Path) -> std::io::Result<DirFingerprint> {
+    let mut fp = DirFingerprint {
+        files: 0,
+        bytes: 0,
+        modified_ns: 0,
+    };
+    collect_fingerprint(dir, &mut fp)?;
+    Ok(fp)
+}
+
+fn collect_fingerprint(dir: &Path, fp: &mut DirFingerprint) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            collect_fingerprint(&entry.path(), fp)?;
+        } else {
+            let modified_ns = metadata
+                .modified()?
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or_default();
+            fp.files += 1;
+            fp.bytes += metadata.len();
+            fp.modified_ns = fp.modified_ns.max(modified_ns);
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    content_hash: String,
+    fingerprint: DirFingerprint,
+    #[serde(default)]
+    mac: String,
+}
+
+/// HMAC-SHA256 über Schlüssel, Hash und Fingerprint eines Eintrags
+fn entry_mac(mac_key: &[u8; 32], key: &str, content_hash: &str, fp: &DirFingerprint) -> String {
+    let message = format!(
+        "{key}\n{content_hash}\n{}\n{}\n{}",
+        fp.files, fp.bytes, fp.modified_ns
+    );
+
+    let mut block = [0u8; HMAC_BLOCK_SIZE];
+    block[..mac_key.len()].copy_from_slice(mac_key);
+    let pad = |byte: u8| block.iter().map(|b| b ^ byte).collect::<Vec<u8>>();
+
+    let inner = Sha256::new()
+        .chain_update(pad(0x36))
+        .chain_update(message.as_bytes())
+        .finalize();
+    let outer = Sha256::new()
+        .chain_update(pad(0x5c))
+        .chain_update(inner)
+        .finalize();
+    hex::encode(outer)
+}
+
+/// Hash-Cache der installierten Versionen einer Vault, liegt im Extension-Basisordner
+pub struct IntegrityCache {
+    base_dir: PathBuf,
+    file_name: String,
+    mac_key: [u8; 32],
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl IntegrityCache {
+    /// Lädt den Cache der Vault; ein fehlender oder defekter Cache führt nur zu
+    /// vollständigem Neuhashen
+    pub fn load(base_dir: &Path, vault_id: &str, mac_key: &[u8; 32]) -> Self {
+        let _ = fs::remove_file(base_dir.join(LEGACY_CACHE_FILE));
+
+        let file_name = format!("integrity-cache-{vault_id}.json");
+        let entries = fs::read_to_string(base_dir.join(&file_name))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            base_dir: base_dir.to_path_buf(),
+            file_name,
+            mac_key: *mac_key,
+            entries,
+        }
+    }
+
+    fn entry(&self, key: &str, content_hash: String, fingerprint: DirFingerprint) -> CacheEntry {
+        CacheEntry {
+            mac: entry_mac(&self.mac_key, key, &content_hash, &fingerprint),
+            content_hash,
+            fingerprint,
+        }
+    }
+
+    /// Speichert den Cache, Einträge gelöschter Versionen werden dabei verworfen
+    pub fn save(&mut self) {
+        let base_dir = &self.base_dir;
+        self.entries.retain(|key, _| base_dir.join(key).is_dir());
+
+        let path = base_dir.join(&self.file_name);
+        let result = serde_json::to_string(&self.entries)
+            .map_err(|e| e.to_string())
+            .and_then(|content| fs::write(&path, content).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            tracing::warn!("Failed to write integrity cache {}: {e}", path.display());
+        }
+    }
+
+    /// Übernimmt den bei der Installation berechneten Hash für ein Verzeichnis
+    pub fn record(&mut self, key: &str, dir: &Path, content_hash: &str) {
+        match fingerprint(dir) {
+            Ok(fingerprint) => {
+                let entry = self.entry(key, content_hash.to_string(), fingerprint);
+                self.entries.insert(key.to_string(), entry);
+            }
+            Err(e) => tracing::warn!("Cannot fingerprint {}: {e}", dir.display()),
+        }
+    }
+
+    fn cached_hash(&self, key: &str, fingerprint: &DirFingerprint) -> Option<&str> {
+        self.entries
+            .get(key)
+            .filter(|entry| &entry.fingerprint == fingerprint)
+            .filter(|entry| {
+                entry.mac == entry_mac(&self.mac_key, key, &entry.content_hash, &entry.fingerprint)
+            })
+            .map(|entry| entry.content_hash.as_str())
+    }
+
+    /// Prüft eine installierte Version gegen die bei der Installation gespeicherte Signatur.
+    /// Liefert bei Manipulation oder Lesefehlern den Grund als Fehler.
+    pub fn verify(
+        &mut self,
+        key: &str,
+        dir: &Path,
+        manifest_path: &Path,
+        public_key: &str,
+        signature: &str,
+    ) -> Result<(), String> {
+        let fingerprint = fingerprint(dir).map_err(|e| format!("Cannot read files: {e}"))?;
+
+        let content_hash = match self.cached_hash(key, &fingerprint) {
+            Some(hash) => hash.to_string(),
+            None => {
+                tracing::debug!("Integrity cache miss for {key}, hashing files");
+                ExtensionCrypto::hash_directory(dir, manifest_path).map_err(|e| e.to_string())?
+            }
+        };
+
+        if let Err(e) = ExtensionCrypto::verify_signature(public_key, &content_hash, signature) {
+            self.entries.remove(key);
+            return Err(e);
+        }
+
+        let entry = self.entry(key, content_hash, fingerprint);
+        self.entries.insert(key.to_string(), entry);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAC_KEY: [u8; 32] = [7u8; 32];
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("haex_integrity_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("assets")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn fingerprint_changes_with_content() {
+        let dir = temp_dir();
+        fs::write(dir.join("index.html"), "<html></html>").unwrap();
+        fs::write(dir.join("assets/app.js"), "console.log(1)").unwrap();
+        let before = fingerprint(&dir).unwrap();
+        assert_eq!(before.files, 2);
+
+        fs::write(dir.join("assets/app.js"), "console.log('tampered')").unwrap();
+        let after = fingerprint(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn cache_roundtrip_drops_removed_versions() {
+        let base = temp_dir();
+        let version_dir = base.join("pk/notes/1.0.0");
+        fs::create_dir_all(&version_dir).unwrap();
+        fs::write(version_dir.join("index.html"), "<html></html>").unwrap();
+
+        let mut cache = IntegrityCache::load(&base, "vault", &MAC_KEY);
+        cache.record("pk/notes/1.0.0", &version_dir, "abc");
+        cache.record("pk/notes/0.9.0", &version_dir, "old");
+        cache.save();
+
+        let loaded = IntegrityCache::load(&base, "vault", &MAC_KEY);
+        let fp = fingerprint(&version_dir).unwrap();
+        fs::remove_dir_all(&base).unwrap();
+
+        assert_eq!(loaded.cached_hash("pk/notes/1.0.0", &fp), Some("abc"));
+        assert_eq!(loaded.cached_hash("pk/notes/0.9.0", &fp), None);
+    }
+
+    #[test]
+    fn forged_entries_are_cache_misses() {
+        let base = temp_dir();
+        let version_dir = base.join("pk/notes/1.0.0");
+        fs::create_dir_all(&version_dir).unwrap();
+        fs::write(version_dir.join("index.html"), "<html></html>").unwrap();
+
+        let mut cache = IntegrityCache::load(&base, "vault", &MAC_KEY);
+        cache.record("pk/notes/1.0.0", &version_dir, "abc");
+        cache.save();
+
+        // Ohne den Schlüssel der Vault passt kein MAC
+        let fp = fingerprint(&version_dir).unwrap();
+        let other_key = IntegrityCache::load(&base, "vault", &[9u8; 32]);
+        assert_eq!(other_key.cached_hash("pk/notes/1.0.0", &fp), None);
+
+        // Angreifer ändert Dateien und passt den Fingerprint im Cache an
+        fs::write(version_dir.join("index.html"), "<script>evil()</script>").unwrap();
+        let forged_fp = fingerprint(&version_dir).unwrap();
+        let cache_path = base.join("integrity-cache-vault.json");
+        let mut raw: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&cache_path).unwrap()).unwrap();
+        raw["pk/notes/1.0.0"]["fingerprint"] = serde_json::to_value(forged_fp).unwrap();
+        fs::write(&cache_path, raw.to_string()).unwrap();
+
+        let loaded = IntegrityCache::load(&base, "vault", &MAC_KEY);
+        fs::remove_dir_all(&base).unwrap();
+
+        assert_eq!(loaded.cached_hash("pk/notes/1.0.0", &forged_fp), None);
+    }
+
+    #[test]
+    fn tampered_registry() {
+        let key = key("pk", "tamper-test", "1.0.0");
+        assert_eq!(tampered_reason(&key), None);
+        mark_tampered(&key, "hash mismatch");
+        assert_eq!(tampered_reason(&key).as_deref(), Some("hash mismatch"));
+        clear_tampered(&key);
+        assert_eq!(tampered_reason(&key), None);
+    }
+}
@@ -1,22 +1,36 @@
-use crate::database::core::with_connection;
+use crate::crdt::hlc::HlcService;
+use crate::database::core::{with_connection, SqlParams};
+use crate::database::vault::VaultSession;
 use crate::database::error::DatabaseError;
+use crate::database::live::LiveQueryOwner;
 use crate::extension::core::manifest::{EditablePermissions, ExtensionManifest, ExtensionPreview};
 use crate::extension::core::types::{copy_directory, Extension, ExtensionSource};
+use crate::extension::core::archive::{self, ArchiveLimits};
+use crate::extension::core::dependencies;
+use crate::extension::core::integrity::{self, IntegrityCache};
+use crate::extension::core::progress::{InstallProgressReporter, InstallStage};
+use crate::extension::core::trust;
 use crate::extension::core::{DisplayMode, ExtensionPermissions};
 use crate::extension::crypto::ExtensionCrypto;
 use crate::extension::database::executor::SqlExecutor;
+use crate::extension::database::migrations::{self, ExtensionMigration};
 use crate::extension::error::ExtensionError;
+use crate::extension::registry::client::{compare_versions, RegistryExtension, RegistryVersion};
+use crate::logging;
 use crate::extension::permissions::manager::PermissionManager;
 use crate::extension::permissions::types::ExtensionPermission;
 use crate::table_names::{TABLE_EXTENSIONS, TABLE_EXTENSION_PERMISSIONS};
-use crate::AppState;
+use rusqlite::OptionalExtension;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
-use tauri::{AppHandle, Manager, State};
-use zip::ZipArchive;
+use tauri::{AppHandle, Manager};
+
+/// Anzahl der Versionen einer Extension, die (inkl. der aktiven) für Rollbacks auf der Platte bleiben
+pub const KEPT_EXTENSION_VERSIONS: usize = 3;
 
 #[derive(Debug, Clone)]
 pub struct CachedPermission {
@@ -51,6 +65,7 @@ struct ExtractedExtension {
     temp_dir: PathBuf,
     manifest: ExtensionManifest,
     content_hash: String,
+    progress: InstallProgressReporter,
 }
 
 impl Drop for ExtractedExtension {
@@ -59,6 +74,32 @@ impl Drop for ExtractedExtension {
     }
 }
 
+/// Versionsverzeichnis eines laufenden Updates; wird entfernt, falls das Update scheitert
+struct PendingVersionDir {
+    path: PathBuf,
+    keep: bool,
+}
+
+impl PendingVersionDir {
+    fn new(path: PathBuf) -> Self {
+        Self { path, keep: false }
+    }
+
+    /// Das Update ist registriert, das Verzeichnis bleibt erhalten
+    fn keep(mut self) -> PathBuf {
+        self.keep = true;
+        std::mem::take(&mut self.path)
+    }
+}
+
+impl Drop for PendingVersionDir {
+    fn drop(&mut self) {
+        if !self.keep {
+            std::fs::remove_dir_all(&self.path).ok();
+        }
+    }
+}
+
 impl ExtensionManager {
     pub fn new() -> Self {
         Self::default()
@@ -129,7 +170,7 @@ impl ExtensionManager {
             if let Some(clean_path) = Self::validate_path_in_directory(extension_dir, icon, true)? {
                 return Ok(Some(clean_path.to_string_lossy().to_string()));
             } else {
-                eprintln!("WARNING: Icon path specified in manifest not found: {icon}");
+                tracing::warn!("Icon path specified in manifest not found: {icon}");
                 // Continue to fallback logic
             }
         }
@@ -153,20 +194,23 @@ impl ExtensionManager {
         Ok(None)
     }
 
+    fn install_cache_dir(app_handle: &AppHandle) -> Result<PathBuf, ExtensionError> {
+        // Use app_cache_dir for better Android compatibility
+        app_handle
+            .path()
+            .app_cache_dir()
+            .map_err(|e| ExtensionError::InstallationFailed {
+                reason: format!("Cannot get app cache dir: {e}"),
+            })
+    }
+
     /// Extrahiert eine Extension-ZIP-Datei und validiert das Manifest
     fn extract_and_validate_extension(
         bytes: Vec<u8>,
         temp_prefix: &str,
         app_handle: &AppHandle,
     ) -> Result<ExtractedExtension, ExtensionError> {
-        // Use app_cache_dir for better Android compatibility
-        let cache_dir =
-            app_handle
-                .path()
-                .app_cache_dir()
-                .map_err(|e| ExtensionError::InstallationFailed {
-                    reason: format!("Cannot get app cache dir: {e}"),
-                })?;
+        let cache_dir = Self::install_cache_dir(app_handle)?;
 
         let temp_id = uuid::Uuid::new_v4();
         let temp = cache_dir.join(format!("{temp_prefix}_{temp_id}"));
@@ -179,32 +223,86 @@ impl ExtensionManager {
         fs::write(&zip_file_path, &bytes).map_err(|e| {
             ExtensionError::filesystem_with_path(zip_file_path.display().to_string(), e)
         })?;
+        drop(bytes);
+
+        let extracted =
+            Self::extract_package(&zip_file_path, temp, InstallProgressReporter::silent());
+
+        // Clean up temporary ZIP file
+        let _ = fs::remove_file(&zip_file_path);
+
+        extracted
+    }
+
+    /// Extrahiert ein vom Nutzer gewähltes Paket direkt von der Platte, ohne es in den Speicher
+    /// zu laden oder zu kopieren. Entpacken und Hashen laufen auf dem Blocking-Threadpool und
+    /// melden ihren Fortschritt von dort.
+    async fn extract_and_validate_file(
+        file_path: &Path,
+        temp_prefix: &str,
+        app_handle: &AppHandle,
+        progress: InstallProgressReporter,
+    ) -> Result<ExtractedExtension, ExtensionError> {
+        if !file_path.is_file() {
+            return Err(ExtensionError::ValidationError {
+                reason: format!("{} is not a file", file_path.display()),
+            });
+        }
+
+        let temp = Self::install_cache_dir(app_handle)?
+            .join(format!("{temp_prefix}_{}", uuid::Uuid::new_v4()));
+        let file_path = file_path.to_path_buf();
+        tokio::task::spawn_blocking(move || Self::extract_package(&file_path, temp, progress))
+            .await
+            .map_err(|e| ExtensionError::InstallationFailed {
+                reason: format!("Extraction task failed: {e}"),
+            })?
+    }
 
+    /// Entpackt das Paket nach `temp`; schlägt ein Schritt fehl, wird `temp` wieder entfernt
+    fn extract_package(
+        zip_file_path: &Path,
+        temp: PathBuf,
+        progress: InstallProgressReporter,
+    ) -> Result<ExtractedExtension, ExtensionError> {
         // Create extraction directory
         fs::create_dir_all(&temp)
             .map_err(|e| ExtensionError::filesystem_with_path(temp.display().to_string(), e))?;
 
+        let result = Self::extract_package_into(zip_file_path, &temp, &progress);
+        match result {
+            Ok((manifest, content_hash)) => Ok(ExtractedExtension {
+                temp_dir: temp,
+                manifest,
+                content_hash,
+                progress,
+            }),
+            Err(e) => {
+                let _ = fs::remove_dir_all(&temp);
+                Err(e)
+            }
+        }
+    }
+
+    fn extract_package_into(
+        zip_file_path: &Path,
+        temp: &Path,
+        progress: &InstallProgressReporter,
+    ) -> Result<(ExtensionManifest, String), ExtensionError> {
+        progress.report(InstallStage::Extracting);
+
         // Open ZIP file from disk (more reliable on Android than from memory)
-        let zip_file = fs::File::open(&zip_file_path).map_err(|e| {
+        let zip_file = fs::File::open(zip_file_path).map_err(|e| {
             ExtensionError::filesystem_with_path(zip_file_path.display().to_string(), e)
         })?;
 
-        let mut archive =
-            ZipArchive::new(zip_file).map_err(|e| ExtensionError::InstallationFailed {
-                reason: format!("Invalid ZIP: {e}"),
-            })?;
-
-        archive
-            .extract(&temp)
-            .map_err(|e| ExtensionError::InstallationFailed {
-                reason: format!("Cannot extract ZIP: {e}"),
-            })?;
+        // Archive sind nicht vertrauenswürdig - nur geprüft entpacken
+        archive::extract_archive(zip_file, temp, &ArchiveLimits::default())?;
 
-        // Clean up temporary ZIP file
-        let _ = fs::remove_file(&zip_file_path);
+        let actual_dir = temp.to_path_buf();
 
         // Read haextension_dir from config if it exists, otherwise use default
-        let config_path = temp.join("haextension.config.json");
+        let config_path = actual_dir.join("haextension.config.json");
         let haextension_dir = if config_path.exists() {
             let config_content = std::fs::read_to_string(&config_path).map_err(|e| {
                 ExtensionError::ManifestError {
@@ -232,12 +330,12 @@ impl ExtensionManager {
 
         // Validate manifest path using helper function
         let manifest_relative_path = format!("{haextension_dir}/manifest.json");
-        let manifest_path = Self::validate_path_in_directory(&temp, &manifest_relative_path, true)?
-            .ok_or_else(|| ExtensionError::ManifestError {
-                reason: format!("manifest.json not found at {haextension_dir}/manifest.json"),
-            })?;
+        let manifest_path =
+            Self::validate_path_in_directory(&actual_dir, &manifest_relative_path, true)?
+                .ok_or_else(|| ExtensionError::ManifestError {
+                    reason: format!("manifest.json not found at {haextension_dir}/manifest.json"),
+                })?;
 
-        let actual_dir = temp.clone();
         let manifest_content =
             std::fs::read_to_string(&manifest_path).map_err(|e| ExtensionError::ManifestError {
                 reason: format!("Cannot read manifest: {e}"),
@@ -253,6 +351,7 @@ impl ExtensionManager {
         )?;
         manifest.icon = validated_icon;
 
+        progress.report(InstallStage::Hashing);
         let content_hash =
             ExtensionCrypto::hash_directory(&actual_dir, &manifest_path).map_err(|e| {
                 ExtensionError::SignatureVerificationFailed {
@@ -260,15 +359,10 @@ impl ExtensionManager {
                 }
             })?;
 
-        Ok(ExtractedExtension {
-            temp_dir: actual_dir,
-            manifest,
-            content_hash,
-        })
+        Ok((manifest, content_hash))
     }
 
     pub fn get_base_extension_dir(
-        &self,
         app_handle: &AppHandle,
     ) -> Result<PathBuf, ExtensionError> {
         let path = app_handle
@@ -288,14 +382,12 @@ impl ExtensionManager {
     }
 
     pub fn get_extension_dir(
-        &self,
         app_handle: &AppHandle,
         public_key: &str,
         extension_name: &str,
         extension_version: &str,
     ) -> Result<PathBuf, ExtensionError> {
-        let specific_extension_dir = self
-            .get_base_extension_dir(app_handle)?
+        let specific_extension_dir = Self::get_base_extension_dir(app_handle)?
             .join(public_key)
             .join(extension_name)
             .join(extension_version);
@@ -367,6 +459,7 @@ impl ExtensionManager {
 
         for (id, ext) in dev_extensions.iter() {
             if ext.manifest.public_key == public_key && ext.manifest.name == name {
+                logging::record_extension_id(id);
                 return Ok(Some((id.clone(), ext.clone())));
             }
         }
@@ -381,6 +474,7 @@ impl ExtensionManager {
 
         for (id, ext) in prod_extensions.iter() {
             if ext.manifest.public_key == public_key && ext.manifest.name == name {
+                logging::record_extension_id(id);
                 return Ok(Some((id.clone(), ext.clone())));
             }
         }
@@ -399,6 +493,156 @@ impl ExtensionManager {
             .map(|(_, ext)| ext))
     }
 
+    /// Extension für einen Aufruf der Extension selbst: deaktivierte Extensions werden abgewiesen
+    pub fn get_active_extension(
+        &self,
+        public_key: &str,
+        name: &str,
+    ) -> Result<Extension, ExtensionError> {
+        let extension = self
+            .get_extension_by_public_key_and_name(public_key, name)?
+            .ok_or_else(|| ExtensionError::NotFound {
+                public_key: public_key.to_string(),
+                name: name.to_string(),
+            })?;
+
+        if !extension.enabled {
+            return Err(ExtensionError::Disabled {
+                extension_id: extension.id,
+            });
+        }
+        Ok(extension)
+    }
+
+    /// Weist deaktivierte (oder wegen Manipulation gesperrte) Extensions ab
+    pub fn ensure_enabled(&self, extension_id: &str) -> Result<(), ExtensionError> {
+        let extension =
+            self.get_extension(extension_id)
+                .ok_or_else(|| ExtensionError::NotFound {
+                    public_key: "".to_string(),
+                    name: extension_id.to_string(),
+                })?;
+
+        if !extension.enabled {
+            return Err(ExtensionError::Disabled {
+                extension_id: extension_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Aktiviert oder deaktiviert eine installierte Extension, ohne sie zu deinstallieren.
+    /// Manipulierte Versionen lassen sich nicht aktivieren - sie müssen neu installiert werden.
+    pub async fn set_extension_enabled_internal(
+        &self,
+        extension_id: &str,
+        enabled: bool,
+        vault: &Arc<VaultSession>,
+    ) -> Result<(), ExtensionError> {
+        let extension =
+            self.get_extension(extension_id)
+                .ok_or_else(|| ExtensionError::NotFound {
+                    public_key: "".to_string(),
+                    name: extension_id.to_string(),
+                })?;
+
+        let version = match &extension.source {
+            ExtensionSource::Production { version, .. } => version.clone(),
+            ExtensionSource::Development { .. } => {
+                return Err(ExtensionError::ValidationError {
+                    reason: "Development extensions cannot be disabled".to_string(),
+                })
+            }
+        };
+
+        if enabled {
+            if let Some(reason) = integrity::tampered_reason(&integrity::key(
+                &extension.manifest.public_key,
+                &extension.manifest.name,
+                &version,
+            )) {
+                return Err(ExtensionError::SecurityViolation {
+                    reason: format!("Extension failed integrity check: {reason}"),
+                });
+            }
+        }
+
+        let row_id = extension_id.to_string();
+        vault
+            .write(move |vault| {
+                with_connection(&vault.db, |conn| {
+                    let tx = conn.transaction().map_err(DatabaseError::from)?;
+
+                    let hlc_service_guard =
+                        vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                            reason: "Failed to lock HLC service".to_string(),
+                        })?;
+                    let hlc_service = hlc_service_guard.clone();
+                    drop(hlc_service_guard);
+
+                    SqlExecutor::execute_internal_typed(
+                        &tx,
+                        &hlc_service,
+                        &format!("UPDATE {TABLE_EXTENSIONS} SET enabled = ? WHERE id = ?"),
+                        rusqlite::params![enabled, row_id],
+                    )?;
+
+                    tx.commit().map_err(DatabaseError::from)
+                })
+            })
+            .await?;
+
+        if let Some(ext) = self
+            .production_extensions
+            .lock()
+            .map_err(|e| ExtensionError::MutexPoisoned {
+                reason: e.to_string(),
+            })?
+            .get_mut(extension_id)
+        {
+            ext.enabled = enabled;
+        }
+
+        if let Ok(mut cache) = self.permission_cache.lock() {
+            cache.remove(extension_id);
+        }
+
+        // Eine deaktivierte Extension darf keine Ergebnisse ihrer Live-Queries mehr erhalten
+        if !enabled {
+            vault
+                .live_queries
+                .unsubscribe_owner(&LiveQueryOwner::Extension(extension_id.to_string()))?;
+        }
+
+        tracing::info!(%extension_id, enabled, "Extension enabled state changed");
+        Ok(())
+    }
+
+    /// Vom Nutzer gesetzter Zustand aus `haex_extensions` - im Speicher kann eine Extension
+    /// zusätzlich wegen Manipulation deaktiviert sein
+    async fn stored_enabled(
+        vault: &Arc<VaultSession>,
+        extension_id: &str,
+    ) -> Result<bool, ExtensionError> {
+        let extension_id = extension_id.to_string();
+        let enabled = vault
+            .read(move |vault| {
+                vault.readers.with_reader(|conn| {
+                    let enabled = conn
+                        .query_row(
+                            &format!("SELECT enabled FROM {TABLE_EXTENSIONS} WHERE id = ?"),
+                            rusqlite::params![extension_id],
+                            |row| row.get::<_, Option<bool>>(0),
+                        )
+                        .optional()?
+                        .flatten();
+                    Ok(enabled)
+                })
+            })
+            .await?;
+        Ok(enabled.unwrap_or(true))
+    }
+
     pub fn remove_extension(&self, public_key: &str, name: &str) -> Result<(), ExtensionError> {
         let (id, _) = self
             .find_extension_id_by_public_key_and_name(public_key, name)?
@@ -440,7 +684,7 @@ impl ExtensionManager {
         public_key: &str,
         extension_name: &str,
         extension_version: &str,
-        state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
     ) -> Result<(), ExtensionError> {
         // Get the extension from memory to get its ID
         let extension = self
@@ -450,27 +694,30 @@ impl ExtensionManager {
                 name: extension_name.to_string(),
             })?;
 
-        eprintln!("DEBUG: Removing extension with ID: {}", extension.id);
-        eprintln!("DEBUG: Extension name: {extension_name}, version: {extension_version}");
+        // Solange andere Extensions davon abhängen, bleibt die Extension installiert
+        dependencies::ensure_not_required(self, public_key, extension_name)?;
+
+        tracing::debug!("Removing extension with ID: {}", extension.id);
+        tracing::debug!("Extension name: {extension_name}, version: {extension_version}");
 
         // Lösche Permissions und Extension-Eintrag in einer Transaktion
-        with_connection(&state.db, |conn| {
+        with_connection(&vault.db, |conn| {
             let tx = conn.transaction().map_err(DatabaseError::from)?;
 
-            let hlc_service = state.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+            let hlc_service = vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
                 reason: "Failed to lock HLC service".to_string(),
             })?;
 
             // Lösche alle Permissions mit extension_id
-            eprintln!(
-                "DEBUG: Deleting permissions for extension_id: {}",
+            tracing::debug!(
+                "Deleting permissions for extension_id: {}",
                 extension.id
             );
             PermissionManager::delete_permissions_in_transaction(&tx, &hlc_service, &extension.id)?;
 
             // Lösche Extension-Eintrag mit extension_id
             let sql = format!("DELETE FROM {TABLE_EXTENSIONS} WHERE id = ?");
-            eprintln!("DEBUG: Executing SQL: {} with id = {}", sql, extension.id);
+            tracing::debug!("Executing SQL: {} with id = {}", sql, extension.id);
             SqlExecutor::execute_internal_typed(
                 &tx,
                 &hlc_service,
@@ -478,39 +725,71 @@ impl ExtensionManager {
                 rusqlite::params![&extension.id],
             )?;
 
-            eprintln!("DEBUG: Committing transaction");
+            tracing::debug!("Committing transaction");
             tx.commit().map_err(DatabaseError::from)
         })?;
 
-        eprintln!("DEBUG: Transaction committed successfully");
+        tracing::debug!("Transaction committed successfully");
 
         // Entferne aus dem In-Memory-Manager
         self.remove_extension(public_key, extension_name)?;
 
-        // Lösche nur den spezifischen Versions-Ordner: public_key/name/version
+        // Lösche den Versions-Ordner: public_key/name/version
         let extension_dir =
-            self.get_extension_dir(app_handle, public_key, extension_name, extension_version)?;
+            Self::get_extension_dir(app_handle, public_key, extension_name, extension_version)?;
+        Self::remove_version_dir(&extension_dir)?;
 
-        if extension_dir.exists() {
-            std::fs::remove_dir_all(&extension_dir).map_err(|e| {
-                ExtensionError::filesystem_with_path(extension_dir.display().to_string(), e)
-            })?;
+        // Für Rollbacks aufbewahrte Versionen werden mit entfernt
+        for version in Self::list_installed_versions(app_handle, public_key, extension_name)? {
+            let dir = Self::get_extension_dir(app_handle, public_key, extension_name, &version)?;
+            Self::remove_version_dir(&dir)?;
+        }
+
+        Ok(())
+    }
+
+    fn load_dependency_declarations(manifest_path: &Path, manifest: &mut ExtensionManifest) {
+        match std::fs::read_to_string(manifest_path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                serde_json::from_str::<ExtensionManifest>(&content).map_err(|e| e.to_string())
+            }) {
+            Ok(on_disk) => {
+                manifest.dependencies = on_disk.dependencies;
+                manifest.exported_tables = on_disk.exported_tables;
+            }
+            Err(e) => tracing::warn!(
+                "Cannot read dependencies of {} from {}: {e}",
+                manifest.name,
+                manifest_path.display()
+            ),
+        }
+    }
 
-            // Versuche, leere Parent-Ordner zu löschen
-            // 1. Extension-Name-Ordner (key_hash/name)
-            if let Some(name_dir) = extension_dir.parent() {
-                if name_dir.exists() {
-                    if let Ok(entries) = std::fs::read_dir(name_dir) {
-                        if entries.count() == 0 {
-                            let _ = std::fs::remove_dir(name_dir);
-
-                            // 2. Key-Hash-Ordner (key_hash) - nur wenn auch leer
-                            if let Some(key_hash_dir) = name_dir.parent() {
-                                if key_hash_dir.exists() {
-                                    if let Ok(entries) = std::fs::read_dir(key_hash_dir) {
-                                        if entries.count() == 0 {
-                                            let _ = std::fs::remove_dir(key_hash_dir);
-                                        }
+    /// Löscht den Ordner einer Extension-Version und danach leere Parent-Ordner (name, public_key)
+    fn remove_version_dir(extension_dir: &Path) -> Result<(), ExtensionError> {
+        if !extension_dir.exists() {
+            return Ok(());
+        }
+
+        std::fs::remove_dir_all(extension_dir).map_err(|e| {
+            ExtensionError::filesystem_with_path(extension_dir.display().to_string(), e)
+        })?;
+
+        // Versuche, leere Parent-Ordner zu löschen
+        // 1. Extension-Name-Ordner (key_hash/name)
+        if let Some(name_dir) = extension_dir.parent() {
+            if name_dir.exists() {
+                if let Ok(entries) = std::fs::read_dir(name_dir) {
+                    if entries.count() == 0 {
+                        let _ = std::fs::remove_dir(name_dir);
+
+                        // 2. Key-Hash-Ordner (key_hash) - nur wenn auch leer
+                        if let Some(key_hash_dir) = name_dir.parent() {
+                            if key_hash_dir.exists() {
+                                if let Ok(entries) = std::fs::read_dir(key_hash_dir) {
+                                    if entries.count() == 0 {
+                                        let _ = std::fs::remove_dir(key_hash_dir);
                                     }
                                 }
                             }
@@ -523,14 +802,84 @@ impl ExtensionManager {
         Ok(())
     }
 
+    /// Kopiert den Inhalt der entpackten Extension in ihr Versions-Verzeichnis
+    fn copy_extracted_files(
+        extracted: &ExtractedExtension,
+        extensions_dir: &Path,
+    ) -> Result<(), ExtensionError> {
+        std::fs::create_dir_all(extensions_dir).map_err(|e| {
+            ExtensionError::filesystem_with_path(extensions_dir.display().to_string(), e)
+        })?;
+
+        // Copy contents of extracted.temp_dir to extensions_dir
+        // Note: extracted.temp_dir already points to the correct directory with manifest.json
+        for entry in fs::read_dir(&extracted.temp_dir).map_err(|e| {
+            ExtensionError::filesystem_with_path(extracted.temp_dir.display().to_string(), e)
+        })? {
+            let entry = entry.map_err(|e| ExtensionError::Filesystem { source: e })?;
+            let path = entry.path();
+            let file_name = entry.file_name();
+            let dest_path = extensions_dir.join(&file_name);
+
+            if path.is_dir() {
+                copy_directory(
+                    path.to_string_lossy().to_string(),
+                    dest_path.to_string_lossy().to_string(),
+                )?;
+            } else {
+                fs::copy(&path, &dest_path).map_err(|e| {
+                    ExtensionError::filesystem_with_path(path.display().to_string(), e)
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub async fn preview_extension_internal(
-        &self,
         app_handle: &AppHandle,
         file_bytes: Vec<u8>,
+        vault: Option<&Arc<VaultSession>>,
+    ) -> Result<ExtensionPreview, ExtensionError> {
+        let extracted =
+            Self::extract_and_validate_extension(file_bytes, "haexhub_preview", app_handle)?;
+        Self::preview_extracted(extracted, vault).await
+    }
+
+    /// Vorschau eines Pakets, das direkt von der Platte gelesen wird
+    pub async fn preview_extension_from_path_internal(
+        app_handle: &AppHandle,
+        file_path: &Path,
+        vault: Option<&Arc<VaultSession>>,
+    ) -> Result<ExtensionPreview, ExtensionError> {
+        let extracted = Self::extract_and_validate_file(
+            file_path,
+            "haexhub_preview",
+            app_handle,
+            InstallProgressReporter::silent(),
+        )
+        .await?;
+        Self::preview_extracted(extracted, vault).await
+    }
+
+    /// Vorschau eines Pakets aus einem Katalog; es muss zum Katalog-Eintrag passen
+    pub async fn preview_registry_extension_internal(
+        app_handle: &AppHandle,
+        file_bytes: Vec<u8>,
+        entry: &RegistryExtension,
+        version: &RegistryVersion,
+        vault: Option<&Arc<VaultSession>>,
     ) -> Result<ExtensionPreview, ExtensionError> {
         let extracted =
             Self::extract_and_validate_extension(file_bytes, "haexhub_preview", app_handle)?;
+        Self::ensure_matches_registry(&extracted.manifest, entry, version)?;
+        Self::preview_extracted(extracted, vault).await
+    }
 
+    async fn preview_extracted(
+        extracted: ExtractedExtension,
+        vault: Option<&Arc<VaultSession>>,
+    ) -> Result<ExtensionPreview, ExtensionError> {
         let is_valid_signature = ExtensionCrypto::verify_signature(
             &extracted.manifest.public_key,
             &extracted.content_hash,
@@ -538,12 +887,44 @@ impl ExtensionManager {
         )
         .is_ok();
 
-        let editable_permissions = extracted.manifest.to_editable_permissions();
+        let mut editable_permissions = extracted.manifest.to_editable_permissions();
+
+        // Bei einem Update nur die neu angeforderten Permissions zur Bestätigung anzeigen
+        let mut installed_version = None;
+        let mut publisher_trust = None;
+        let mut dependency_status = Vec::new();
+        if let Some(vault) = vault {
+            dependencies::validate_manifest(&extracted.manifest)?;
+            dependency_status =
+                dependencies::status(&vault.extension_manager, &extracted.manifest)?;
+
+            let trust = trust::assess(vault, &extracted.manifest).await?;
+
+            // Nach einem Schlüsselwechsel ist die Extension noch unter dem alten Schlüssel installiert
+            let installed_key = trust
+                .rotated_from
+                .clone()
+                .unwrap_or_else(|| extracted.manifest.public_key.clone());
+            publisher_trust = Some(trust);
+
+            if let Some(installed) = vault
+                .extension_manager
+                .find_production_extension(&installed_key, &extracted.manifest.name)?
+            {
+                let existing = PermissionManager::get_permissions(vault, &installed.id).await?;
+                editable_permissions
+                    .retain_permissions(|p| !existing.iter().any(|e| e.is_same_grant(p)));
+                installed_version = Some(installed.manifest.version);
+            }
+        }
 
         Ok(ExtensionPreview {
             manifest: extracted.manifest.clone(),
             is_valid_signature,
             editable_permissions,
+            installed_version,
+            publisher_trust,
+            dependency_status,
         })
     }
 
@@ -552,10 +933,80 @@ impl ExtensionManager {
         app_handle: AppHandle,
         file_bytes: Vec<u8>,
         custom_permissions: EditablePermissions,
-        state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
+    ) -> Result<String, ExtensionError> {
+        let extracted =
+            Self::extract_and_validate_extension(file_bytes, "haexhub_ext", &app_handle)?;
+        self.install_extracted(app_handle, extracted, custom_permissions, vault)
+            .await
+    }
+
+    /// Installiert ein Paket direkt von der Platte und meldet die einzelnen Schritte über `progress`
+    pub async fn install_extension_from_path_internal(
+        &self,
+        app_handle: AppHandle,
+        file_path: &Path,
+        custom_permissions: EditablePermissions,
+        progress: InstallProgressReporter,
+        vault: &Arc<VaultSession>,
+    ) -> Result<String, ExtensionError> {
+        let extracted =
+            Self::extract_and_validate_file(file_path, "haexhub_ext", &app_handle, progress)
+                .await?;
+        self.install_extracted(app_handle, extracted, custom_permissions, vault)
+            .await
+    }
+
+    /// Installiert ein Paket aus einem Katalog; es muss zum Katalog-Eintrag passen
+    pub async fn install_registry_extension_internal(
+        &self,
+        app_handle: AppHandle,
+        file_bytes: Vec<u8>,
+        entry: &RegistryExtension,
+        version: &RegistryVersion,
+        custom_permissions: EditablePermissions,
+        vault: &Arc<VaultSession>,
     ) -> Result<String, ExtensionError> {
         let extracted =
             Self::extract_and_validate_extension(file_bytes, "haexhub_ext", &app_handle)?;
+        Self::ensure_matches_registry(&extracted.manifest, entry, version)?;
+        self.install_extracted(app_handle, extracted, custom_permissions, vault)
+            .await
+    }
+
+    /// Ein Katalog darf kein Paket unter fremdem Namen, Schlüssel oder Versionsnummer ausliefern
+    fn ensure_matches_registry(
+        manifest: &ExtensionManifest,
+        entry: &RegistryExtension,
+        version: &RegistryVersion,
+    ) -> Result<(), ExtensionError> {
+        if manifest.name != entry.name
+            || manifest.public_key != entry.public_key
+            || manifest.version != version.version
+        {
+            return Err(ExtensionError::Registry {
+                reason: format!(
+                    "Package does not match registry entry: expected {} {} ({}), got {} {} ({})",
+                    entry.name,
+                    version.version,
+                    entry.public_key,
+                    manifest.name,
+                    manifest.version,
+                    manifest.public_key
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    async fn install_extracted(
+        &self,
+        app_handle: AppHandle,
+        extracted: ExtractedExtension,
+        custom_permissions: EditablePermissions,
+        vault: &Arc<VaultSession>,
+    ) -> Result<String, ExtensionError> {
+        extracted.progress.report(InstallStage::Verifying);
 
         // Signatur verifizieren (bei Installation wird ein Fehler geworfen, nicht nur geprüft)
         ExtensionCrypto::verify_signature(
@@ -565,7 +1016,54 @@ impl ExtensionManager {
         )
         .map_err(|e| ExtensionError::SignatureVerificationFailed { reason: e })?;
 
-        let extensions_dir = self.get_extension_dir(
+        // Die Signatur allein sagt nichts über den Publisher - Trust-Store und Policy entscheiden
+        let publisher_trust = trust::assess(vault, &extracted.manifest).await?;
+        publisher_trust.ensure_allowed()?;
+        if let Some(reason) = &publisher_trust.reason {
+            tracing::warn!("Installing {} anyway: {reason}", extracted.manifest.name);
+        }
+
+        // Abhängigkeiten müssen vorher in passender Version installiert sein
+        dependencies::ensure_satisfied(self, &extracted.manifest)?;
+
+        // Ist die Extension bereits installiert, wird sie aktualisiert statt neu angelegt
+        if let Some(installed) = self.find_production_extension(
+            &extracted.manifest.public_key,
+            &extracted.manifest.name,
+        )? {
+            return self
+                .update_extension_internal(
+                    &app_handle,
+                    extracted,
+                    custom_permissions,
+                    installed,
+                    None,
+                    vault,
+                )
+                .await;
+        }
+
+        // Key-Pinning: eine Extension ist über Schlüssel und Name identifiziert. Nur ein gültiger
+        // Schlüsselwechsel des installierten Schlüssels führt sie unter dem neuen Schlüssel fort;
+        // gleichnamige Extensions anderer Publisher sind eigenständige Extensions.
+        if let Some(previous_key) = publisher_trust.rotated_from.clone() {
+            if let Some(installed) =
+                self.find_production_extension(&previous_key, &extracted.manifest.name)?
+            {
+                return self
+                    .update_extension_internal(
+                        &app_handle,
+                        extracted,
+                        custom_permissions,
+                        installed,
+                        Some(previous_key.as_str()),
+                        vault,
+                    )
+                    .await;
+            }
+        }
+
+        let extensions_dir = Self::get_extension_dir(
             &app_handle,
             &extracted.manifest.public_key,
             &extracted.manifest.name,
@@ -574,7 +1072,7 @@ impl ExtensionManager {
 
         // If extension version already exists, remove it completely before installing
         if extensions_dir.exists() {
-            eprintln!(
+            tracing::info!(
                 "Extension version already exists at {}, removing old version",
                 extensions_dir.display()
             );
@@ -583,101 +1081,115 @@ impl ExtensionManager {
             })?;
         }
 
-        std::fs::create_dir_all(&extensions_dir).map_err(|e| {
-            ExtensionError::filesystem_with_path(extensions_dir.display().to_string(), e)
-        })?;
-
-        // Copy contents of extracted.temp_dir to extensions_dir
-        // Note: extracted.temp_dir already points to the correct directory with manifest.json
-        for entry in fs::read_dir(&extracted.temp_dir).map_err(|e| {
-            ExtensionError::filesystem_with_path(extracted.temp_dir.display().to_string(), e)
-        })? {
-            let entry = entry.map_err(|e| ExtensionError::Filesystem { source: e })?;
-            let path = entry.path();
-            let file_name = entry.file_name();
-            let dest_path = extensions_dir.join(&file_name);
+        let extension_migrations =
+            Self::read_extension_migrations(&extracted.temp_dir, &extracted.manifest)?;
+        let table_prefix =
+            migrations::table_prefix(&extracted.manifest.public_key, &extracted.manifest.name);
 
-            if path.is_dir() {
-                copy_directory(
-                    path.to_string_lossy().to_string(),
-                    dest_path.to_string_lossy().to_string(),
-                )?;
-            } else {
-                fs::copy(&path, &dest_path).map_err(|e| {
-                    ExtensionError::filesystem_with_path(path.display().to_string(), e)
-                })?;
-            }
+        extracted.progress.report(InstallStage::Copying);
+        if let Err(e) = Self::copy_extracted_files(&extracted, &extensions_dir) {
+            let _ = std::fs::remove_dir_all(&extensions_dir);
+            return Err(e);
         }
 
+        extracted.progress.report(InstallStage::Registering);
+
         // Generate UUID for extension (Drizzle's $defaultFn only works from JS, not raw SQL)
         let extension_id = uuid::Uuid::new_v4().to_string();
         let permissions = custom_permissions.to_internal_permissions(&extension_id);
 
-        // Extension-Eintrag und Permissions in einer Transaktion speichern
-        let actual_extension_id = with_connection(&state.db, |conn| {
-            let tx = conn.transaction().map_err(DatabaseError::from)?;
-
-            let hlc_service_guard = state.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
-                reason: "Failed to lock HLC service".to_string(),
-            })?;
-            // Klonen, um den MutexGuard freizugeben, bevor potenziell lange DB-Operationen stattfinden
-            let hlc_service = hlc_service_guard.clone();
-            drop(hlc_service_guard);
-
-            // 1. Extension-Eintrag erstellen mit generierter UUID
-            let insert_ext_sql = format!(
-                "INSERT INTO {TABLE_EXTENSIONS} (id, name, version, author, entry, icon, public_key, signature, homepage, description, enabled, single_instance, display_mode) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
-            );
-
-            SqlExecutor::execute_internal_typed(
-                &tx,
-                &hlc_service,
-                &insert_ext_sql,
-                rusqlite::params![
-                    extension_id,
-                    extracted.manifest.name,
-                    extracted.manifest.version,
-                    extracted.manifest.author,
-                    extracted.manifest.entry,
-                    extracted.manifest.icon,
-                    extracted.manifest.public_key,
-                    extracted.manifest.signature,
-                    extracted.manifest.homepage,
-                    extracted.manifest.description,
-                    true, // enabled
-                    extracted.manifest.single_instance.unwrap_or(false),
-                    extracted.manifest.display_mode.as_ref().map(|dm| format!("{:?}", dm).to_lowercase()).unwrap_or_else(|| "auto".to_string()),
-                ],
-            )?;
+        // Extension-Eintrag und Permissions in einer Transaktion auf dem Schreib-Thread speichern
+        let manifest = extracted.manifest.clone();
+        let new_extension_id = extension_id.clone();
+        let registered = vault
+            .write(move |vault| {
+                with_connection(&vault.db, |conn| {
+                    let tx = conn.transaction().map_err(DatabaseError::from)?;
+
+                    let hlc_service_guard =
+                        vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                            reason: "Failed to lock HLC service".to_string(),
+                        })?;
+                    // Klonen, um den MutexGuard freizugeben, bevor lange DB-Operationen stattfinden
+                    let hlc_service = hlc_service_guard.clone();
+                    drop(hlc_service_guard);
+
+                    // 1. Extension-Eintrag erstellen mit generierter UUID
+                    let insert_ext_sql = format!(
+                        "INSERT INTO {TABLE_EXTENSIONS} (id, name, version, author, entry, icon, public_key, signature, homepage, description, enabled, single_instance, display_mode) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+                    );
+
+                    SqlExecutor::execute_internal_typed(
+                        &tx,
+                        &hlc_service,
+                        &insert_ext_sql,
+                        rusqlite::params![
+                            new_extension_id,
+                            manifest.name,
+                            manifest.version,
+                            manifest.author,
+                            manifest.entry,
+                            manifest.icon,
+                            manifest.public_key,
+                            manifest.signature,
+                            manifest.homepage,
+                            manifest.description,
+                            true, // enabled
+                            manifest.single_instance.unwrap_or(false),
+                            manifest
+                                .display_mode
+                                .as_ref()
+                                .map(|dm| format!("{:?}", dm).to_lowercase())
+                                .unwrap_or_else(|| "auto".to_string()),
+                        ],
+                    )?;
+
+                    // 2. Permissions speichern
+                    let insert_perm_sql = format!(
+                        "INSERT INTO {TABLE_EXTENSION_PERMISSIONS} (id, extension_id, resource_type, action, target, constraints, status) VALUES (?, ?, ?, ?, ?, ?, ?)"
+                    );
+
+                    for perm in &permissions {
+                        use crate::database::generated::HaexExtensionPermissions;
+                        let db_perm: HaexExtensionPermissions = perm.into();
+
+                        SqlExecutor::execute_internal_typed(
+                            &tx,
+                            &hlc_service,
+                            &insert_perm_sql,
+                            rusqlite::params![
+                                db_perm.id,
+                                db_perm.extension_id,
+                                db_perm.resource_type,
+                                db_perm.action,
+                                db_perm.target,
+                                db_perm.constraints,
+                                db_perm.status,
+                            ],
+                        )?;
+                    }
 
-            // 2. Permissions speichern
-            let insert_perm_sql = format!(
-                "INSERT INTO {TABLE_EXTENSION_PERMISSIONS} (id, extension_id, resource_type, action, target, constraints, status) VALUES (?, ?, ?, ?, ?, ?, ?)"
-            );
+                    // 3. Schema-Migrationen der Extension anwenden
+                    migrations::apply_pending(
+                        &tx,
+                        &hlc_service,
+                        &table_prefix,
+                        &extension_migrations,
+                    )?;
 
-            for perm in &permissions {
-                use crate::database::generated::HaexExtensionPermissions;
-                let db_perm: HaexExtensionPermissions = perm.into();
-
-                SqlExecutor::execute_internal_typed(
-                    &tx,
-                    &hlc_service,
-                    &insert_perm_sql,
-                    rusqlite::params![
-                        db_perm.id,
-                        db_perm.extension_id,
-                        db_perm.resource_type,
-                        db_perm.action,
-                        db_perm.target,
-                        db_perm.constraints,
-                        db_perm.status,
-                    ],
-                )?;
+                    tx.commit().map_err(DatabaseError::from)?;
+                    Ok(new_extension_id)
+                })
+            })
+            .await;
+
+        let actual_extension_id = match registered {
+            Ok(id) => id,
+            Err(e) => {
+                let _ = std::fs::remove_dir_all(&extensions_dir);
+                return Err(e.into());
             }
-
-            tx.commit().map_err(DatabaseError::from)?;
-            Ok(extension_id.clone())
-        })?;
+        };
 
         let extension = Extension {
             id: extension_id.clone(),
@@ -691,15 +1203,714 @@ impl ExtensionManager {
         };
 
         self.add_production_extension(extension)?;
+        Self::record_integrity(
+            &app_handle,
+            vault,
+            &extracted.manifest,
+            &extensions_dir,
+            &extracted.content_hash,
+        )?;
 
         Ok(actual_extension_id) // Gebe die actual_extension_id an den Caller zurück
     }
 
-    /// Scannt das Dateisystem beim Start und lädt alle installierten Erweiterungen.
+    /// Sucht eine installierte Production-Extension anhand von public_key und name.
+    /// Dev-Extensions werden bewusst ignoriert, da sie nicht über Pakete aktualisiert werden.
+    fn find_production_extension(
+        &self,
+        public_key: &str,
+        name: &str,
+    ) -> Result<Option<Extension>, ExtensionError> {
+        let prod_extensions =
+            self.production_extensions
+                .lock()
+                .map_err(|e| ExtensionError::MutexPoisoned {
+                    reason: e.to_string(),
+                })?;
+
+        Ok(prod_extensions
+            .values()
+            .find(|ext| ext.manifest.public_key == public_key && ext.manifest.name == name)
+            .cloned())
+    }
+
+    /// Aktualisiert eine installierte Extension auf die Version aus `extracted`.
+    ///
+    /// Die neue Version wird neben der alten installiert. ID, bereits erteilte Permissions
+    /// und die Tabellen (Präfix ohne Version) bleiben erhalten. Permissions, die das neue
+    /// Manifest nicht mehr anfordert, werden entfernt; neu angeforderte werden aus
+    /// `custom_permissions` übernommen. Die alte Version bleibt für `rollback_extension` erhalten;
+    /// ältere Pakete werden abgelehnt, solange die installierte Version nicht manipuliert wurde.
+    ///
+    /// Mit `rotated_from` wechselt die Extension vom bisherigen auf den neuen Publisher-Schlüssel:
+    /// Tabellen und Eintrag ziehen auf den neuen Schlüssel um, alte Versionen werden entfernt.
+    async fn update_extension_internal(
+        &self,
+        app_handle: &AppHandle,
+        extracted: ExtractedExtension,
+        custom_permissions: EditablePermissions,
+        installed: Extension,
+        rotated_from: Option<&str>,
+        vault: &Arc<VaultSession>,
+    ) -> Result<String, ExtensionError> {
+        let old_version = match &installed.source {
+            ExtensionSource::Production { version, .. } => version.clone(),
+            ExtensionSource::Development { .. } => {
+                return Err(ExtensionError::ValidationError {
+                    reason: "Development extensions cannot be updated".to_string(),
+                })
+            }
+        };
+
+        // Manipulierte Versionen dürfen auch in derselben Version neu installiert werden; danach
+        // gilt wieder der vom Nutzer gesetzte Zustand aus der Datenbank
+        let was_tampered = integrity::tampered_reason(&integrity::key(
+            &installed.manifest.public_key,
+            &installed.manifest.name,
+            &old_version,
+        ))
+        .is_some();
+
+        if old_version == extracted.manifest.version && !was_tampered && rotated_from.is_none() {
+            return Err(ExtensionError::InstallationFailed {
+                reason: format!(
+                    "Version {} of {} is already installed",
+                    old_version, extracted.manifest.name
+                ),
+            });
+        }
+
+        // Zurück geht es nur über `rollback_extension`, das auch die Down-Migrationen ausführt
+        if compare_versions(&extracted.manifest.version, &old_version) == Ordering::Less
+            && !was_tampered
+        {
+            return Err(ExtensionError::InstallationFailed {
+                reason: format!(
+                    "Version {} of {} is older than the installed version {}; use rollback_extension to downgrade",
+                    extracted.manifest.version, extracted.manifest.name, old_version
+                ),
+            });
+        }
+
+        // Abhängige Extensions müssen mit der neuen Version weiterhin funktionieren
+        dependencies::ensure_dependents_accept(
+            self,
+            &installed.manifest.public_key,
+            &installed.manifest.name,
+            &extracted.manifest,
+        )?;
+
+        let extension_id = installed.id.clone();
+        logging::record_extension_id(&extension_id);
+        tracing::info!(
+            "Updating extension {} from {} to {}",
+            extracted.manifest.name,
+            old_version,
+            extracted.manifest.version
+        );
+
+        let new_dir = Self::get_extension_dir(
+            app_handle,
+            &extracted.manifest.public_key,
+            &extracted.manifest.name,
+            &extracted.manifest.version,
+        )?;
+
+        // Reste eines abgebrochenen Updates oder eine aufbewahrte Kopie dieser Version ersetzen
+        if new_dir.exists() {
+            std::fs::remove_dir_all(&new_dir).map_err(|e| {
+                ExtensionError::filesystem_with_path(new_dir.display().to_string(), e)
+            })?;
+        }
+
+        let extension_migrations =
+            Self::read_extension_migrations(&extracted.temp_dir, &extracted.manifest)?;
+        let table_prefix =
+            migrations::table_prefix(&extracted.manifest.public_key, &extracted.manifest.name);
+
+        // Bis zum Commit der Datenbank bleibt die alte Version aktiv und die neue wird bei
+        // jedem Fehler (auch per `?`) wieder entfernt
+        let pending_dir = PendingVersionDir::new(new_dir);
+
+        extracted.progress.report(InstallStage::Copying);
+        Self::copy_extracted_files(&extracted, &pending_dir.path)?;
+
+        extracted.progress.report(InstallStage::Registering);
+        let existing = PermissionManager::get_permissions(vault, &extension_id).await?;
+        let requested = extracted
+            .manifest
+            .permissions
+            .to_internal_permissions(&extension_id);
+
+        // Nicht mehr angeforderte Permissions entfernen, bestehende Grants (inkl. Status) behalten
+        let obsolete: Vec<ExtensionPermission> = existing
+            .iter()
+            .filter(|e| !requested.iter().any(|r| r.is_same_grant(e)))
+            .cloned()
+            .collect();
+        let added: Vec<ExtensionPermission> = custom_permissions
+            .to_internal_permissions(&extension_id)
+            .into_iter()
+            .filter(|p| !existing.iter().any(|e| e.is_same_grant(p)))
+            .collect();
+
+        // Die Transaktion läuft auf dem Schreib-Thread der Vault
+        let manifest = extracted.manifest.clone();
+        let rotated_key = rotated_from.map(str::to_string);
+        let row_id = extension_id.clone();
+        vault
+            .write(move |vault| {
+                with_connection(&vault.db, |conn| {
+                    let tx = conn.transaction().map_err(DatabaseError::from)?;
+
+                    let hlc_service_guard =
+                        vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                            reason: "Failed to lock HLC service".to_string(),
+                        })?;
+                    let hlc_service = hlc_service_guard.clone();
+                    drop(hlc_service_guard);
+
+                    // 0. Schlüsselwechsel: Tabellen, Eintrag und Publisher-Vertrauen umziehen
+                    if let Some(previous_key) = &rotated_key {
+                        Self::rotate_publisher_key(
+                            &tx,
+                            &hlc_service,
+                            &row_id,
+                            previous_key,
+                            &manifest,
+                        )?;
+                    }
+
+                    // 1. Extension-Eintrag auf die neue Version bringen (ID und enabled bleiben)
+                    Self::update_extension_row(&tx, &hlc_service, &row_id, &manifest)?;
+
+                    // 2. Nicht mehr angeforderte Permissions löschen
+                    let delete_perm_sql =
+                        format!("DELETE FROM {TABLE_EXTENSION_PERMISSIONS} WHERE id = ?");
+                    for perm in &obsolete {
+                        SqlExecutor::execute_internal_typed(
+                            &tx,
+                            &hlc_service,
+                            &delete_perm_sql,
+                            rusqlite::params![perm.id],
+                        )?;
+                    }
+
+                    // 3. Neu angeforderte Permissions speichern
+                    let insert_perm_sql = format!(
+                        "INSERT INTO {TABLE_EXTENSION_PERMISSIONS} (id, extension_id, resource_type, action, target, constraints, status) VALUES (?, ?, ?, ?, ?, ?, ?)"
+                    );
+
+                    for perm in &added {
+                        use crate::database::generated::HaexExtensionPermissions;
+                        let db_perm: HaexExtensionPermissions = perm.into();
+
+                        SqlExecutor::execute_internal_typed(
+                            &tx,
+                            &hlc_service,
+                            &insert_perm_sql,
+                            rusqlite::params![
+                                db_perm.id,
+                                db_perm.extension_id,
+                                db_perm.resource_type,
+                                db_perm.action,
+                                db_perm.target,
+                                db_perm.constraints,
+                                db_perm.status,
+                            ],
+                        )?;
+                    }
+
+                    // 4. Neue Schema-Migrationen anwenden
+                    migrations::apply_pending(
+                        &tx,
+                        &hlc_service,
+                        &table_prefix,
+                        &extension_migrations,
+                    )?;
+
+                    tx.commit().map_err(DatabaseError::from)
+                })
+            })
+            .await?;
+        let new_dir = pending_dir.keep();
+
+        Self::record_integrity(
+            app_handle,
+            vault,
+            &extracted.manifest,
+            &new_dir,
+            &extracted.content_hash,
+        )?;
+
+        let extension = Extension {
+            id: extension_id.clone(),
+            source: ExtensionSource::Production {
+                path: new_dir,
+                version: extracted.manifest.version.clone(),
+            },
+            manifest: extracted.manifest.clone(),
+            enabled: Self::stored_enabled(vault, &extension_id).await?,
+            last_accessed: SystemTime::now(),
+        };
+        self.add_production_extension(extension)?;
+
+        if let Ok(mut cache) = self.permission_cache.lock() {
+            cache.remove(&extension_id);
+        }
+
+        // Versionen unter dem alten Schlüssel passen nicht mehr zu den umbenannten Tabellen
+        if let Some(previous_key) = rotated_from {
+            let name = &extracted.manifest.name;
+            for version in Self::list_installed_versions(app_handle, previous_key, name)? {
+                let dir = Self::get_extension_dir(app_handle, previous_key, name, &version)?;
+                if let Err(e) = Self::remove_version_dir(&dir) {
+                    tracing::warn!("Failed to remove {}: {e}", dir.display());
+                }
+            }
+        }
+
+        // Die alte Version bleibt für einen Rollback erhalten, nur ältere werden aufgeräumt
+        Self::prune_old_versions(
+            app_handle,
+            &extracted.manifest.public_key,
+            &extracted.manifest.name,
+            &extracted.manifest.version,
+        );
+
+        Ok(extension_id)
+    }
+
+    /// Zieht eine Extension innerhalb der Update-Transaktion auf den neuen Publisher-Schlüssel um
+    fn rotate_publisher_key(
+        tx: &rusqlite::Transaction,
+        hlc_service: &HlcService,
+        extension_id: &str,
+        previous_key: &str,
+        manifest: &ExtensionManifest,
+    ) -> Result<(), DatabaseError> {
+        tracing::info!(
+            "Rotating publisher key of {} from {previous_key} to {}",
+            manifest.name,
+            manifest.public_key
+        );
+
+        migrations::rename_table_prefix(
+            tx,
+            &migrations::table_prefix(previous_key, &manifest.name),
+            &migrations::table_prefix(&manifest.public_key, &manifest.name),
+        )?;
+
+        SqlExecutor::execute_internal_typed(
+            tx,
+            hlc_service,
+            &format!("UPDATE {TABLE_EXTENSIONS} SET public_key = ? WHERE id = ?"),
+            rusqlite::params![manifest.public_key, extension_id],
+        )?;
+
+        trust::inherit_publisher(tx, hlc_service, previous_key, &manifest.public_key)
+    }
+
+    /// Schreibt die Manifest-Daten einer Version in den bestehenden Extension-Eintrag
+    fn update_extension_row(
+        tx: &rusqlite::Transaction,
+        hlc_service: &HlcService,
+        extension_id: &str,
+        manifest: &ExtensionManifest,
+    ) -> Result<(), DatabaseError> {
+        let update_ext_sql = format!(
+            "UPDATE {TABLE_EXTENSIONS} SET version = ?, author = ?, entry = ?, icon = ?, signature = ?, homepage = ?, description = ?, single_instance = ?, display_mode = ? WHERE id = ?"
+        );
+
+        SqlExecutor::execute_internal_typed(
+            tx,
+            hlc_service,
+            &update_ext_sql,
+            rusqlite::params![
+                manifest.version,
+                manifest.author,
+                manifest.entry,
+                manifest.icon,
+                manifest.signature,
+                manifest.homepage,
+                manifest.description,
+                manifest.single_instance.unwrap_or(false),
+                manifest.display_mode.as_ref().map(|dm| format!("{:?}", dm).to_lowercase()).unwrap_or_else(|| "auto".to_string()),
+                extension_id,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Liefert die auf der Platte vorhandenen Versionen einer Extension, neueste Version zuerst
+    pub fn list_installed_versions(
+        app_handle: &AppHandle,
+        public_key: &str,
+        name: &str,
+    ) -> Result<Vec<String>, ExtensionError> {
+        let name_dir = Self::get_base_extension_dir(app_handle)?
+            .join(public_key)
+            .join(name);
+
+        if !name_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        // Nach Semver statt Änderungszeit: Kopieren oder Backups setzen die mtime neu
+        let mut versions: Vec<String> = fs::read_dir(&name_dir)
+            .map_err(|e| ExtensionError::filesystem_with_path(name_dir.display().to_string(), e))?
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+
+        versions.sort_by(|a, b| compare_versions(b, a));
+        Ok(versions)
+    }
+
+    /// Behält neben der aktiven Version die letzten `KEPT_EXTENSION_VERSIONS - 1` Versionen
+    fn prune_old_versions(app_handle: &AppHandle, public_key: &str, name: &str, active_version: &str) {
+        let versions = match Self::list_installed_versions(app_handle, public_key, name) {
+            Ok(versions) => versions,
+            Err(e) => {
+                tracing::warn!("Failed to list versions of {name}: {e}");
+                return;
+            }
+        };
+
+        let obsolete = versions
+            .into_iter()
+            .filter(|version| version != active_version)
+            .skip(KEPT_EXTENSION_VERSIONS.saturating_sub(1));
+
+        for version in obsolete {
+            let result = Self::get_extension_dir(app_handle, public_key, name, &version)
+                .and_then(|dir| Self::remove_version_dir(&dir));
+            if let Err(e) = result {
+                tracing::warn!("Failed to remove old version {version} of {name}: {e}");
+            }
+        }
+    }
+
+    /// Wechselt eine installierte Extension auf eine noch vorhandene ältere Version.
+    ///
+    /// Ohne `target_version` wird die nächstältere vorhandene Version gewählt. Die
+    /// `down_migration` jeder übersprungenen Version zwischen Ziel und aktiver Version läuft,
+    /// neueste zuerst, in derselben Transaktion wie die Umstellung des Extension-Eintrags.
+    /// Die bisherige Version bleibt auf der Platte.
+    pub async fn rollback_extension_internal(
+        &self,
+        app_handle: &AppHandle,
+        public_key: &str,
+        name: &str,
+        target_version: Option<&str>,
+        vault: &Arc<VaultSession>,
+    ) -> Result<String, ExtensionError> {
+        let installed = self
+            .find_production_extension(public_key, name)?
+            .ok_or_else(|| ExtensionError::NotFound {
+                public_key: public_key.to_string(),
+                name: name.to_string(),
+            })?;
+        logging::record_extension_id(&installed.id);
+
+        let (current_dir, current_version) = match &installed.source {
+            ExtensionSource::Production { path, version } => (path.clone(), version.clone()),
+            ExtensionSource::Development { .. } => {
+                return Err(ExtensionError::ValidationError {
+                    reason: "Development extensions cannot be rolled back".to_string(),
+                })
+            }
+        };
+
+        let versions = Self::list_installed_versions(app_handle, public_key, name)?;
+        let target_version = match target_version {
+            Some(version) if versions.iter().any(|v| v == version) => version.to_string(),
+            Some(version) => {
+                return Err(ExtensionError::ValidationError {
+                    reason: format!("Version {version} of {name} is not available for rollback"),
+                })
+            }
+            None => versions
+                .iter()
+                .find(|v| compare_versions(v, &current_version) == Ordering::Less)
+                .cloned()
+                .ok_or_else(|| ExtensionError::ValidationError {
+                    reason: format!("No previous version of {name} available"),
+                })?,
+        };
+
+        if target_version == current_version {
+            return Err(ExtensionError::ValidationError {
+                reason: format!("Version {target_version} of {name} is already active"),
+            });
+        }
+        if compare_versions(&target_version, &current_version) == Ordering::Greater {
+            return Err(ExtensionError::ValidationError {
+                reason: format!(
+                    "Version {target_version} of {name} is newer than the active version {current_version}"
+                ),
+            });
+        }
+
+        tracing::info!("Rolling back {name} from {current_version} to {target_version}");
+
+        let target_dir = Self::get_extension_dir(app_handle, public_key, name, &target_version)?;
+        let (target_manifest, target_manifest_path) = Self::read_installed_manifest(&target_dir)?;
+
+        if target_manifest.public_key != public_key
+            || target_manifest.name != name
+            || target_manifest.version != target_version
+        {
+            return Err(ExtensionError::ValidationError {
+                reason: format!(
+                    "Manifest in {} does not match version {target_version}",
+                    target_dir.display()
+                ),
+            });
+        }
+
+        // Auch eine ältere Version wird nur mit gültiger Signatur wieder aktiviert
+        let content_hash = ExtensionCrypto::hash_directory(&target_dir, &target_manifest_path)
+            .map_err(|e| ExtensionError::SignatureVerificationFailed {
+                reason: e.to_string(),
+            })?;
+        ExtensionCrypto::verify_signature(
+            &target_manifest.public_key,
+            &content_hash,
+            &target_manifest.signature,
+        )
+        .map_err(|e| ExtensionError::SignatureVerificationFailed { reason: e })?;
+
+        // Inzwischen blockierte Publisher bleiben auch für ältere Versionen gesperrt
+        trust::assess(vault, &target_manifest)
+            .await?
+            .ensure_allowed()?;
+
+        dependencies::ensure_satisfied(self, &target_manifest)?;
+        dependencies::ensure_dependents_accept(self, public_key, name, &target_manifest)?;
+
+        // Migrationen der Zielversion: was sie nicht kennt, wird zurückgenommen
+        let target_migrations = Self::read_extension_migrations(&target_dir, &target_manifest)?;
+
+        // Alle Versionen oberhalb der Zielversion bis einschließlich der aktiven, neueste zuerst.
+        // Ihre Down-Migrationen laufen in dieser Reihenfolge, ihre Migrations-Ordner liefern
+        // die Down-Skripte für revert_unknown.
+        let mut available_migrations: Vec<ExtensionMigration> = Vec::new();
+        let mut down_scripts: Vec<String> = Vec::new();
+        for version in versions.iter().filter(|v| {
+            compare_versions(v, &target_version) == Ordering::Greater
+                && compare_versions(v, &current_version) != Ordering::Greater
+        }) {
+            let version_dir = if *version == current_version {
+                current_dir.clone()
+            } else {
+                Self::get_extension_dir(app_handle, public_key, name, version)?
+            };
+            let manifest = match Self::read_installed_manifest(&version_dir) {
+                Ok((manifest, _)) => manifest,
+                Err(e) => {
+                    tracing::warn!("Cannot read manifest of version {version}: {e}");
+                    continue;
+                }
+            };
+
+            for migration in Self::read_extension_migrations(&version_dir, &manifest)? {
+                if !available_migrations
+                    .iter()
+                    .any(|m| m.name == migration.name)
+                {
+                    available_migrations.push(migration);
+                }
+            }
+            if let Some(script) = Self::read_down_migration(&version_dir, &manifest)? {
+                down_scripts.push(script);
+            }
+        }
+
+        let extension_id = installed.id.clone();
+        let prefix = migrations::table_prefix(public_key, name);
+
+        // Die Transaktion läuft auf dem Schreib-Thread der Vault
+        let manifest = target_manifest.clone();
+        let row_id = extension_id.clone();
+        vault
+            .write(move |vault| {
+                with_connection(&vault.db, |conn| {
+                    let tx = conn.transaction().map_err(DatabaseError::from)?;
+
+                    let hlc_service_guard =
+                        vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                            reason: "Failed to lock HLC service".to_string(),
+                        })?;
+                    let hlc_service = hlc_service_guard.clone();
+                    drop(hlc_service_guard);
+
+                    migrations::revert_unknown(
+                        &tx,
+                        &hlc_service,
+                        &prefix,
+                        &available_migrations,
+                        &target_migrations,
+                    )?;
+
+                    for script in &down_scripts {
+                        migrations::execute_script(
+                            &tx,
+                            &hlc_service,
+                            &prefix,
+                            "down_migration",
+                            script,
+                        )?;
+                    }
+
+                    // Erteilte Permissions bleiben unverändert, damit ein erneutes Update nichts
+                    // neu abfragt
+                    Self::update_extension_row(&tx, &hlc_service, &row_id, &manifest)?;
+
+                    tx.commit().map_err(DatabaseError::from)
+                })
+            })
+            .await?;
+
+        Self::record_integrity(
+            app_handle,
+            vault,
+            &target_manifest,
+            &target_dir,
+            &content_hash,
+        )?;
+
+        let extension = Extension {
+            id: extension_id.clone(),
+            source: ExtensionSource::Production {
+                path: target_dir,
+                version: target_version.clone(),
+            },
+            manifest: target_manifest,
+            enabled: Self::stored_enabled(vault, &extension_id).await?,
+            last_accessed: SystemTime::now(),
+        };
+        self.add_production_extension(extension)?;
+
+        if let Ok(mut cache) = self.permission_cache.lock() {
+            cache.remove(&extension_id);
+        }
+
+        Ok(target_version)
+    }
+
+    /// Merkt sich den geprüften Hash einer frisch aktivierten Version für die Prüfung beim Start
+    fn record_integrity(
+        app_handle: &AppHandle,
+        vault: &Arc<VaultSession>,
+        manifest: &ExtensionManifest,
+        extension_dir: &Path,
+        content_hash: &str,
+    ) -> Result<(), ExtensionError> {
+        let key = integrity::key(&manifest.public_key, &manifest.name, &manifest.version);
+        let mut cache = IntegrityCache::load(
+            &Self::get_base_extension_dir(app_handle)?,
+            &vault.id,
+            &vault.integrity_key,
+        );
+        cache.record(&key, extension_dir, content_hash);
+        cache.save();
+        integrity::clear_tampered(&key);
+        Ok(())
+    }
+
+    /// Liest den haextension-Ordner aus der haextension.config.json, sonst den Standard
+    fn read_haextension_dir(extension_dir: &Path) -> String {
+        std::fs::read_to_string(extension_dir.join("haextension.config.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|config| {
+                config
+                    .get("dev")
+                    .and_then(|dev| dev.get("haextension_dir"))
+                    .and_then(|dir| dir.as_str())
+                    .map(String::from)
+            })
+            .unwrap_or_else(|| "haextension".to_string())
+    }
+
+    /// Liest das Manifest einer installierten Version von der Platte
+    fn read_installed_manifest(
+        extension_dir: &PathBuf,
+    ) -> Result<(ExtensionManifest, PathBuf), ExtensionError> {
+        let haextension_dir = Self::read_haextension_dir(extension_dir);
+        let manifest_relative_path = format!("{haextension_dir}/manifest.json");
+        let manifest_path =
+            Self::validate_path_in_directory(extension_dir, &manifest_relative_path, true)?
+                .ok_or_else(|| ExtensionError::ManifestError {
+                    reason: format!("manifest.json not found at {manifest_relative_path}"),
+                })?;
+
+        let manifest_content =
+            std::fs::read_to_string(&manifest_path).map_err(|e| ExtensionError::ManifestError {
+                reason: format!("Cannot read manifest: {e}"),
+            })?;
+        let mut manifest: ExtensionManifest = serde_json::from_str(&manifest_content)?;
+
+        manifest.icon = Self::validate_and_resolve_icon_path(
+            extension_dir,
+            &haextension_dir,
+            manifest.icon.as_deref(),
+        )?;
+
+        Ok((manifest, manifest_path))
+    }
+
+    /// Liest das im Manifest deklarierte `down_migration`-Skript einer Version
+    fn read_down_migration(
+        extension_dir: &PathBuf,
+        manifest: &ExtensionManifest,
+    ) -> Result<Option<String>, ExtensionError> {
+        let Some(relative_path) = &manifest.down_migration else {
+            return Ok(None);
+        };
+
+        let haextension_dir = Self::read_haextension_dir(extension_dir);
+        let script_path = Self::validate_path_in_directory(
+            extension_dir,
+            &format!("{haextension_dir}/{relative_path}"),
+            true,
+        )?
+        .ok_or_else(|| ExtensionError::ManifestError {
+            reason: format!("down_migration not found: {relative_path}"),
+        })?;
+
+        fs::read_to_string(&script_path)
+            .map(Some)
+            .map_err(|e| ExtensionError::filesystem_with_path(script_path.display().to_string(), e))
+    }
+
+    /// Liest die im Manifest deklarierten Schema-Migrationen aus einem Extension-Verzeichnis
+    fn read_extension_migrations(
+        extension_dir: &PathBuf,
+        manifest: &ExtensionManifest,
+    ) -> Result<Vec<ExtensionMigration>, ExtensionError> {
+        let Some(migrations_dir) = &manifest.migrations else {
+            return Ok(Vec::new());
+        };
+
+        let haextension_dir = Self::read_haextension_dir(extension_dir);
+        let relative_path = format!("{haextension_dir}/{migrations_dir}");
+        let dir = Self::validate_path_in_directory(extension_dir, &relative_path, true)?
+            .ok_or_else(|| ExtensionError::ManifestError {
+                reason: format!("migrations directory not found at {relative_path}"),
+            })?;
+
+        migrations::read_migrations(&dir)
+    }
+
+    /// Scannt das Dateisystem beim Start und lädt alle installierten Erweiterungen.
     pub async fn load_installed_extensions(
         &self,
         app_handle: &AppHandle,
-        state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
     ) -> Result<Vec<String>, ExtensionError> {
         // Clear existing data
         self.production_extensions
@@ -722,81 +1933,128 @@ impl ExtensionManager {
             .clear();
 
         // Lade alle Daten aus der Datenbank
-        let extensions = with_connection(&state.db, |conn| {
-            let sql = format!(
-            "SELECT id, name, version, author, entry, icon, public_key, signature, homepage, description, enabled, single_instance, display_mode FROM {TABLE_EXTENSIONS}"
-        );
-            eprintln!("DEBUG: SQL Query before transformation: {sql}");
-
-            let results = SqlExecutor::query_select(conn, &sql, &[])?;
-            eprintln!("DEBUG: Query returned {} results", results.len());
-
-            let mut data = Vec::new();
-            for row in results {
-                // Wir erwarten die Werte in der Reihenfolge der SELECT-Anweisung
-                let id = row[0]
-                    .as_str()
-                    .ok_or_else(|| DatabaseError::SerializationError {
-                        reason: "Missing id field".to_string(),
-                    })?
-                    .to_string();
-
-                let manifest = ExtensionManifest {
-                    name: row[1]
-                        .as_str()
-                        .ok_or_else(|| DatabaseError::SerializationError {
-                            reason: "Missing name field".to_string(),
-                        })?
-                        .to_string(),
-                    version: row[2]
-                        .as_str()
-                        .ok_or_else(|| DatabaseError::SerializationError {
-                            reason: "Missing version field".to_string(),
-                        })?
-                        .to_string(),
-                    author: row[3].as_str().map(String::from),
-                    entry: row[4].as_str().map(String::from),
-                    icon: row[5].as_str().map(String::from),
-                    public_key: row[6].as_str().unwrap_or("").to_string(),
-                    signature: row[7].as_str().unwrap_or("").to_string(),
-                    permissions: ExtensionPermissions::default(),
-                    homepage: row[8].as_str().map(String::from),
-                    description: row[9].as_str().map(String::from),
-                    single_instance: row[11]
-                        .as_bool()
-                        .or_else(|| row[11].as_i64().map(|v| v != 0)),
-                    display_mode: row[12].as_str().and_then(|s| match s {
-                        "window" => Some(DisplayMode::Window),
-                        "iframe" => Some(DisplayMode::Iframe),
-                        "auto" | _ => Some(DisplayMode::Auto),
-                    }),
-                };
-
-                let enabled = row[10]
-                    .as_bool()
-                    .or_else(|| row[10].as_i64().map(|v| v != 0))
-                    .unwrap_or(false);
-
-                data.push(ExtensionDataFromDb {
-                    id,
-                    manifest,
-                    enabled,
-                });
-            }
-            Ok(data)
-        })?;
+        let extensions = vault
+            .read(move |vault| {
+                vault.readers.with_reader(|conn| {
+                    let sql = format!(
+                        "SELECT id, name, version, author, entry, icon, public_key, signature, homepage, description, enabled, single_instance, display_mode FROM {TABLE_EXTENSIONS}"
+                    );
+                    tracing::debug!("SQL Query before transformation: {sql}");
+
+                    let results = SqlExecutor::query_select(conn, &sql, &SqlParams::default())?;
+                    tracing::debug!("Query returned {} results", results.len());
+
+                    let mut data = Vec::new();
+                    for row in results {
+                        // Wir erwarten die Werte in der Reihenfolge der SELECT-Anweisung
+                        let id = row[0]
+                            .as_str()
+                            .ok_or_else(|| DatabaseError::SerializationError {
+                                reason: "Missing id field".to_string(),
+                            })?
+                            .to_string();
+
+                        let manifest = ExtensionManifest {
+                            name: row[1]
+                                .as_str()
+                                .ok_or_else(|| DatabaseError::SerializationError {
+                                    reason: "Missing name field".to_string(),
+                                })?
+                                .to_string(),
+                            version: row[2]
+                                .as_str()
+                                .ok_or_else(|| DatabaseError::SerializationError {
+                                    reason: "Missing version field".to_string(),
+                                })?
+                                .to_string(),
+                            author: row[3].as_str().map(String::from),
+                            entry: row[4].as_str().map(String::from),
+                            icon: row[5].as_str().map(String::from),
+                            public_key: row[6].as_str().unwrap_or("").to_string(),
+                            signature: row[7].as_str().unwrap_or("").to_string(),
+                            permissions: ExtensionPermissions::default(),
+                            homepage: row[8].as_str().map(String::from),
+                            description: row[9].as_str().map(String::from),
+                            single_instance: row[11]
+                                .as_bool()
+                                .or_else(|| row[11].as_i64().map(|v| v != 0)),
+                            display_mode: row[12].as_str().and_then(|s| match s {
+                                "window" => Some(DisplayMode::Window),
+                                "iframe" => Some(DisplayMode::Iframe),
+                                "auto" | _ => Some(DisplayMode::Auto),
+                            }),
+                            migrations: None,
+                            down_migration: None,
+                            key_rotation: None,
+                            dependencies: None,
+                            exported_tables: None,
+                        };
+
+                        let enabled = row[10]
+                            .as_bool()
+                            .or_else(|| row[10].as_i64().map(|v| v != 0))
+                            .unwrap_or(false);
+
+                        data.push(ExtensionDataFromDb {
+                            id,
+                            manifest,
+                            enabled,
+                        });
+                    }
+                    Ok(data)
+                })
+            })
+            .await?;
+
+        tracing::debug!("Found {} extensions in database", extensions.len());
+
+        // Schritt 2: Dateisystem und Integrität prüfen. Das Hashen großer Extensions dauert,
+        // deshalb läuft es auf dem Blocking-Threadpool; State-Mutationen erst danach.
+        let check_app_handle = app_handle.clone();
+        let session = vault.clone();
+        let (loaded, missing) = tokio::task::spawn_blocking(move || {
+            Self::verify_installed_extensions(&check_app_handle, &session, extensions)
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+
+        self.missing_extensions
+            .lock()
+            .map_err(|e| ExtensionError::MutexPoisoned {
+                reason: e.to_string(),
+            })?
+            .extend(missing);
 
-        // Schritt 2: Die gesammelten Daten verarbeiten (Dateisystem, State-Mutationen).
         let mut loaded_extension_ids = Vec::new();
+        for extension in loaded {
+            loaded_extension_ids.push(extension.id.clone());
+            self.add_production_extension(extension)?;
+        }
+
+        Ok(loaded_extension_ids)
+    }
 
-        eprintln!("DEBUG: Found {} extensions in database", extensions.len());
+    /// Prüft Verzeichnis, Manifest und Integrität der installierten Versionen und liefert die
+    /// ladbaren sowie die fehlenden Extensions
+    fn verify_installed_extensions(
+        app_handle: &AppHandle,
+        vault: &VaultSession,
+        extensions: Vec<ExtensionDataFromDb>,
+    ) -> Result<(Vec<Extension>, Vec<MissingExtension>), ExtensionError> {
+        let mut loaded = Vec::new();
+        let mut missing = Vec::new();
+        let mut integrity_cache = IntegrityCache::load(
+            &Self::get_base_extension_dir(app_handle)?,
+            &vault.id,
+            &vault.integrity_key,
+        );
 
-        for extension_data in extensions {
+        for mut extension_data in extensions {
             let extension_id = extension_data.id;
-            eprintln!("DEBUG: Processing extension: {extension_id}");
+            tracing::debug!("Processing extension: {extension_id}");
 
             // Use public_key/name/version path structure
-            let extension_path = self.get_extension_dir(
+            let extension_path = Self::get_extension_dir(
                 app_handle,
                 &extension_data.manifest.public_key,
                 &extension_data.manifest.name,
@@ -805,67 +2063,69 @@ impl ExtensionManager {
 
             // Check if extension directory exists
             if !extension_path.exists() {
-                eprintln!(
-                    "DEBUG: Extension directory missing for: {extension_id} at {extension_path:?}"
+                tracing::warn!(
+                    "Extension directory missing for: {extension_id} at {extension_path:?}"
                 );
-                self.missing_extensions
-                    .lock()
-                    .map_err(|e| ExtensionError::MutexPoisoned {
-                        reason: e.to_string(),
-                    })?
-                    .push(MissingExtension {
-                        id: extension_id.clone(),
-                        public_key: extension_data.manifest.public_key.clone(),
-                        name: extension_data.manifest.name.clone(),
-                        version: extension_data.manifest.version.clone(),
-                    });
+                missing.push(MissingExtension {
+                    id: extension_id.clone(),
+                    public_key: extension_data.manifest.public_key.clone(),
+                    name: extension_data.manifest.name.clone(),
+                    version: extension_data.manifest.version.clone(),
+                });
                 continue;
             }
 
-            // Read haextension_dir from config if it exists, otherwise use default
-            let config_path = extension_path.join("haextension.config.json");
-            let haextension_dir = if config_path.exists() {
-                match std::fs::read_to_string(&config_path) {
-                    Ok(config_content) => {
-                        match serde_json::from_str::<serde_json::Value>(&config_content) {
-                            Ok(config) => config
-                                .get("dev")
-                                .and_then(|dev| dev.get("haextension_dir"))
-                                .and_then(|dir| dir.as_str())
-                                .unwrap_or("haextension")
-                                .to_string(),
-                            Err(_) => "haextension".to_string(),
-                        }
-                    }
-                    Err(_) => "haextension".to_string(),
-                }
-            } else {
-                "haextension".to_string()
-            };
+            let haextension_dir = Self::read_haextension_dir(&extension_path);
 
             // Validate manifest.json path using helper function
             let manifest_relative_path = format!("{haextension_dir}/manifest.json");
-            if Self::validate_path_in_directory(&extension_path, &manifest_relative_path, true)?
-                .is_none()
-            {
-                eprintln!(
-                    "DEBUG: manifest.json missing or invalid for: {extension_id} at {haextension_dir}/manifest.json"
+            let Some(manifest_path) =
+                Self::validate_path_in_directory(&extension_path, &manifest_relative_path, true)?
+            else {
+                tracing::warn!(
+                    "manifest.json missing or invalid for: {extension_id} at {haextension_dir}/manifest.json"
                 );
-                self.missing_extensions
-                    .lock()
-                    .map_err(|e| ExtensionError::MutexPoisoned {
-                        reason: e.to_string(),
-                    })?
-                    .push(MissingExtension {
-                        id: extension_id.clone(),
-                        public_key: extension_data.manifest.public_key.clone(),
-                        name: extension_data.manifest.name.clone(),
-                        version: extension_data.manifest.version.clone(),
-                    });
+                missing.push(MissingExtension {
+                    id: extension_id.clone(),
+                    public_key: extension_data.manifest.public_key.clone(),
+                    name: extension_data.manifest.name.clone(),
+                    version: extension_data.manifest.version.clone(),
+                });
                 continue;
-            }
+            };
 
-            eprintln!("DEBUG: Extension loaded successfully: {extension_id}");
+            // Inhalt gegen die bei der Installation gespeicherte Signatur prüfen
+            let integrity_key = integrity::key(
+                &extension_data.manifest.public_key,
+                &extension_data.manifest.name,
+                &extension_data.manifest.version,
+            );
+            let enabled = match integrity_cache.verify(
+                &integrity_key,
+                &extension_path,
+                &manifest_path,
+                &extension_data.manifest.public_key,
+                &extension_data.manifest.signature,
+            ) {
+                Ok(()) => {
+                    integrity::clear_tampered(&integrity_key);
+                    // Abhängigkeiten und Exporte stehen nur im (jetzt verifizierten) Manifest
+                    Self::load_dependency_declarations(
+                        &manifest_path,
+                        &mut extension_data.manifest,
+                    );
+                    extension_data.enabled
+                }
+                Err(reason) => {
+                    tracing::error!(
+                        "Integrity check failed for {extension_id}, disabling extension: {reason}"
+                    );
+                    integrity::mark_tampered(&integrity_key, &reason);
+                    false
+                }
+            };
+
+            tracing::debug!("Extension loaded successfully: {extension_id}");
 
             let extension = Extension {
                 id: extension_id.clone(),
@@ -874,14 +2134,15 @@ impl ExtensionManager {
                     version: extension_data.manifest.version.clone(),
                 },
                 manifest: extension_data.manifest,
-                enabled: extension_data.enabled,
+                enabled,
                 last_accessed: SystemTime::now(),
             };
 
-            loaded_extension_ids.push(extension_id.clone());
-            self.add_production_extension(extension)?;
+            loaded.push(extension);
         }
 
-        Ok(loaded_extension_ids)
+        integrity_cache.save();
+
+        Ok((loaded, missing))
     }
 }
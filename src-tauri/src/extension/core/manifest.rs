@@ -1,3 +1,6 @@
+use crate::extension::core::dependencies::DependencyStatus;
+use crate::extension::core::integrity;
+use crate::extension::core::trust::PublisherTrust;
 use crate::extension::error::ExtensionError;
 use crate::extension::permissions::types::{
     Action, DbAction, ExtensionPermission, FsAction, WebAction, PermissionConstraints,
@@ -33,7 +36,14 @@ pub struct PermissionEntry {
 pub struct ExtensionPreview {
     pub manifest: ExtensionManifest,
     pub is_valid_signature: bool,
+    /// Bei einem Update nur die Berechtigungen, die noch nicht entschieden wurden
     pub editable_permissions: EditablePermissions,
+    /// Version der bereits installierten Extension, falls die Installation ein Update ist
+    pub installed_version: Option<String>,
+    /// Vertrauensstufe des Publishers und Entscheidung der Signatur-Policy (nur mit geöffnetem Vault)
+    pub publisher_trust: Option<PublisherTrust>,
+    /// Stand der Abhängigkeiten gegenüber den installierten Extensions (nur mit geöffnetem Vault)
+    pub dependency_status: Vec<DependencyStatus>,
 }
 /// Definiert die einheitliche Struktur für alle Berechtigungsarten im Manifest und UI.
 #[derive(Serialize, Deserialize, Clone, Debug, Default, TS)]
@@ -89,6 +99,60 @@ pub struct ExtensionManifest {
     pub single_instance: Option<bool>,
     #[serde(default)]
     pub display_mode: Option<DisplayMode>,
+    /// Ordner (relativ zum haextension-Ordner) mit Schema-Migrationen `NNNN_name.sql` und optional
+    /// `NNNN_name.down.sql`. Ausstehende Migrationen werden bei Installation und Update angewendet.
+    #[serde(default)]
+    pub migrations: Option<String>,
+    /// SQL-Skript (relativ zum haextension-Ordner), das die Schemaänderungen dieser Version
+    /// rückgängig macht. Wird beim Rollback auf eine ältere Version ausgeführt.
+    #[serde(default)]
+    pub down_migration: Option<String>,
+    /// Schlüsselwechsel des Publishers: der bisherige Schlüssel bestätigt `public_key` als Nachfolger.
+    /// Nur damit darf eine installierte Extension auf einen anderen Schlüssel aktualisiert werden.
+    #[serde(default)]
+    pub key_rotation: Option<KeyRotation>,
+    /// Extensions, die vor dieser Extension in passender Version installiert sein müssen
+    #[serde(default)]
+    pub dependencies: Option<Vec<ExtensionDependency>>,
+    /// Tabellen (ohne Präfix), auf die abhängige Extensions ohne eigene Freigabe zugreifen dürfen
+    #[serde(default)]
+    pub exported_tables: Option<Vec<TableExport>>,
+}
+
+/// Vom bisherigen Schlüssel signierte Erklärung über den neuen Publisher-Schlüssel
+#[derive(Serialize, Deserialize, Clone, Debug, TS)]
+#[ts(export)]
+pub struct KeyRotation {
+    pub previous_public_key: String,
+    /// Signatur des bisherigen Schlüssels über `haex-key-rotation:{previous_public_key}:{public_key}`
+    pub signature: String,
+}
+
+/// Abhängigkeit auf eine andere Extension
+#[derive(Serialize, Deserialize, Clone, Debug, TS)]
+#[ts(export)]
+pub struct ExtensionDependency {
+    pub public_key: String,
+    pub name: String,
+    /// semver-Bereich, z.B. `^1.2.0` oder `>=1.0.0 <2.0.0`
+    pub version: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum TableAccess {
+    Read,
+    ReadWrite,
+}
+
+/// Für abhängige Extensions freigegebene Tabelle
+#[derive(Serialize, Deserialize, Clone, Debug, TS)]
+#[ts(export)]
+pub struct TableExport {
+    /// Tabellenname ohne das Präfix `{public_key}__{name}__`
+    pub name: String,
+    pub access: TableAccess,
 }
 
 fn default_entry_value() -> Option<String> {
@@ -159,6 +223,27 @@ impl ExtensionPermissions {
         permissions
     }
 
+    /// Behält nur die Einträge, deren interne Repräsentation `keep` erfüllt.
+    /// Einträge mit ungültiger Aktion werden dabei ebenfalls verworfen.
+    pub fn retain_permissions<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&ExtensionPermission) -> bool,
+    {
+        let mut retain = |list: Option<&mut Vec<PermissionEntry>>, resource_type: ResourceType| {
+            if let Some(entries) = list {
+                entries.retain(|entry| {
+                    Self::create_internal("", resource_type.clone(), entry)
+                        .is_some_and(|perm| keep(&perm))
+                });
+            }
+        };
+
+        retain(self.database.as_mut(), ResourceType::Db);
+        retain(self.filesystem.as_mut(), ResourceType::Fs);
+        retain(self.http.as_mut(), ResourceType::Web);
+        retain(self.shell.as_mut(), ResourceType::Shell);
+    }
+
     /// Parst einen einzelnen `PermissionEntry` und wandelt ihn in die interne, typsichere `ExtensionPermission`-Struktur um.
     fn create_internal(
         extension_id: &str,
@@ -217,6 +302,9 @@ pub struct ExtensionInfoResponse {
     pub display_mode: Option<DisplayMode>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dev_server_url: Option<String>,
+    /// Grund, falls die installierte Version die Integritätsprüfung nicht bestanden hat
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integrity_error: Option<String>,
 }
 
 impl ExtensionInfoResponse {
@@ -230,6 +318,13 @@ impl ExtensionInfoResponse {
             ExtensionSource::Production { .. } => None,
         };
 
+        let integrity_error = match &extension.source {
+            ExtensionSource::Production { version, .. } => integrity::tampered_reason(
+                &integrity::key(&extension.manifest.public_key, &extension.manifest.name, version),
+            ),
+            ExtensionSource::Development { .. } => None,
+        };
+
         Ok(Self {
             id: extension.id.clone(),
             public_key: extension.manifest.public_key.clone(),
@@ -244,6 +339,7 @@ impl ExtensionInfoResponse {
             single_instance: extension.manifest.single_instance,
             display_mode: extension.manifest.display_mode.clone(),
             dev_server_url,
+            integrity_error,
         })
     }
 }
@@ -1,8 +1,13 @@
 // src-tauri/src/extension/core/mod.rs
 
+pub mod archive;
+pub mod dependencies;
+pub mod integrity;
 pub mod manager;
 pub mod manifest;
+pub mod progress;
 pub mod protocol;
+pub mod trust;
 pub mod types;
 
 pub use manager::*;
@@ -0,0 +1,60 @@
+// src-tauri/src/extension/core/progress.rs
+// Fortschritts-Events einer Installation. Das Frontend übergibt eine `installId` und ordnet
+// die Events darüber seinem Dialog zu.
+
+use crate::event_names::EVENT_EXTENSION_INSTALL_PROGRESS;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use ts_rs::TS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallStage {
+    Extracting,
+    Hashing,
+    Verifying,
+    Copying,
+    Registering,
+}
+
+/// Payload von `extension-install-progress`
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallProgress {
+    pub install_id: String,
+    pub stage: InstallStage,
+}
+
+/// Meldet Installationsschritte an das Hauptfenster; ohne `installId` passiert nichts
+#[derive(Clone, Default)]
+pub struct InstallProgressReporter {
+    target: Option<(AppHandle, String)>,
+}
+
+impl InstallProgressReporter {
+    pub fn new(app_handle: &AppHandle, install_id: String) -> Self {
+        Self {
+            target: Some((app_handle.clone(), install_id)),
+        }
+    }
+
+    pub fn silent() -> Self {
+        Self::default()
+    }
+
+    pub fn report(&self, stage: InstallStage) {
+        let Some((app_handle, install_id)) = &self.target else {
+            return;
+        };
+
+        let progress = InstallProgress {
+            install_id: install_id.clone(),
+            stage,
+        };
+        if let Err(e) = app_handle.emit_to("main", EVENT_EXTENSION_INSTALL_PROGRESS, &progress) {
+            tracing::warn!("Failed to emit install progress: {e}");
+        }
+    }
+}
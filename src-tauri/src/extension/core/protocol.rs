@@ -1,5 +1,7 @@
 // src-tauri/src/extension/core/protocol.rs
 
+use crate::extension::core::integrity;
+use crate::extension::core::manager::ExtensionManager;
 use crate::extension::core::types::get_tauri_origin;
 use crate::extension::error::ExtensionError;
 use crate::AppState;
@@ -13,7 +15,7 @@ use std::path::PathBuf;
 use std::sync::Mutex;
 use tauri::http::Uri;
 use tauri::http::{Request, Response};
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager};
 
 // Extension protocol name constant
 pub const EXTENSION_PROTOCOL_NAME: &str = "haex-extension";
@@ -87,9 +89,39 @@ impl From<serde_json::Error> for DataProcessingError {
     }
 }
 
+/// Deaktivierte Extensions werden nicht ausgeliefert. Das Protokoll kennt die Vault der Anfrage
+/// nicht - ausgeliefert wird nur, was in mindestens einer geöffneten Vault aktiviert ist.
+fn ensure_enabled_in_open_vault(
+    app_handle: &AppHandle,
+    public_key: &str,
+    extension_name: &str,
+) -> Result<(), ExtensionError> {
+    let state = app_handle.state::<AppState>();
+    let mut disabled_id = None;
+
+    for vault in state.vaults.sessions()? {
+        match vault
+            .extension_manager
+            .get_active_extension(public_key, extension_name)
+        {
+            Ok(_) => return Ok(()),
+            Err(ExtensionError::Disabled { extension_id }) => disabled_id = Some(extension_id),
+            Err(ExtensionError::NotFound { .. }) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(match disabled_id {
+        Some(extension_id) => ExtensionError::Disabled { extension_id },
+        None => ExtensionError::NotFound {
+            public_key: public_key.to_string(),
+            name: extension_name.to_string(),
+        },
+    })
+}
+
 pub fn resolve_secure_extension_asset_path(
     app_handle: &AppHandle,
-    state: &State<AppState>,
     public_key: &str,
     extension_name: &str,
     extension_version: &str,
@@ -115,7 +147,17 @@ pub fn resolve_secure_extension_asset_path(
         });
     }
 
-    let specific_extension_dir = state.extension_manager.get_extension_dir(
+    // Manipulierte Versionen werden nicht ausgeliefert
+    let integrity_key = integrity::key(public_key, extension_name, extension_version);
+    if let Some(reason) = integrity::tampered_reason(&integrity_key) {
+        return Err(ExtensionError::SecurityViolation {
+            reason: format!("Extension failed integrity check: {reason}"),
+        });
+    }
+
+    ensure_enabled_in_open_vault(app_handle, public_key, extension_name)?;
+
+    let specific_extension_dir = ExtensionManager::get_extension_dir(
         app_handle,
         public_key,
         extension_name,
@@ -145,9 +187,7 @@ pub fn resolve_secure_extension_asset_path(
             if canonical_path.starts_with(&canonical_base) {
                 Ok(canonical_path)
             } else {
-                eprintln!(
-                    "SECURITY WARNING: Path traversal attempt blocked: {requested_asset_path}"
-                );
+                tracing::warn!("SECURITY: Path traversal attempt blocked: {requested_asset_path}");
                 Err(ExtensionError::SecurityViolation {
                     reason: format!("Path traversal attempt: {requested_asset_path}"),
                 })
@@ -157,9 +197,7 @@ pub fn resolve_secure_extension_asset_path(
             if final_path.starts_with(&specific_extension_dir) {
                 Ok(final_path)
             } else {
-                eprintln!(
-                    "SECURITY WARNING: Invalid asset path: {requested_asset_path}"
-                );
+                tracing::warn!("SECURITY: Invalid asset path: {requested_asset_path}");
                 Err(ExtensionError::SecurityViolation {
                     reason: format!("Invalid asset path: {requested_asset_path}"),
                 })
@@ -169,7 +207,6 @@ pub fn resolve_secure_extension_asset_path(
 }
 
 pub fn extension_protocol_handler(
-    state: State<AppState>,
     app_handle: &AppHandle,
     request: &Request<Vec<u8>>,
 ) -> Result<Response<Vec<u8>>, Box<dyn std::error::Error>> {
@@ -214,9 +251,7 @@ pub fn extension_protocol_handler(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
 
-    println!("Protokoll Handler für: {uri_ref}");
-    println!("Origin: {origin}");
-    println!("Referer: {referer}");
+    tracing::trace!(origin, referer, "Protokoll Handler für: {uri_ref}");
 
     let path_str = uri_ref.path();
 
@@ -225,24 +260,19 @@ pub fn extension_protocol_handler(
     // - Desktop: haex-extension://<base64>/{assetPath}
     // - Android: http://localhost/{base64}/{assetPath}
     let host = uri_ref.host().unwrap_or("");
-    println!("URI Host: {host}");
 
     let (info, segments_after_version) = if host == "localhost" || host == format!("{EXTENSION_PROTOCOL_NAME}.localhost").as_str() {
         // Android format: http://haex-extension.localhost/{base64}/{assetPath}
         // Extract base64 from first path segment
-        println!("Android format detected: http://{host}/...");
+        tracing::trace!("Android format detected: http://{host}/...");
         let mut segments_iter = path_str.split('/').filter(|s| !s.is_empty());
 
         if let Some(first_segment) = segments_iter.next() {
-            println!("First path segment (base64): {first_segment}");
             match BASE64_STANDARD.decode(first_segment) {
                 Ok(decoded_bytes) => match String::from_utf8(decoded_bytes) {
                     Ok(json_str) => match serde_json::from_str::<ExtensionInfo>(&json_str) {
                         Ok(info) => {
-                            println!("=== Extension Info from path (Android) ===");
-                            println!("  PublicKey: {}", info.public_key);
-                            println!("  Name: {}", info.name);
-                            println!("  Version: {}", info.version);
+                            tracing::trace!(?info, "Extension info from path (Android)");
                             cache_extension_info(&info);
 
                             // Remaining segments after base64 are the asset path
@@ -250,7 +280,7 @@ pub fn extension_protocol_handler(
                             (info, remaining)
                         }
                         Err(e) => {
-                            eprintln!("Failed to parse JSON from base64 path: {e}");
+                            tracing::warn!("Failed to parse JSON from base64 path: {e}");
                             return Response::builder()
                                 .status(400)
                                 .header("Access-Control-Allow-Origin", allowed_origin)
@@ -259,7 +289,7 @@ pub fn extension_protocol_handler(
                         }
                     },
                     Err(e) => {
-                        eprintln!("Failed to decode UTF-8 from base64 path: {e}");
+                        tracing::warn!("Failed to decode UTF-8 from base64 path: {e}");
                         return Response::builder()
                             .status(400)
                             .header("Access-Control-Allow-Origin", allowed_origin)
@@ -268,7 +298,7 @@ pub fn extension_protocol_handler(
                     }
                 },
                 Err(e) => {
-                    eprintln!("Failed to decode base64 from path: {e}");
+                    tracing::warn!("Failed to decode base64 from path: {e}");
                     return Response::builder()
                         .status(400)
                         .header("Access-Control-Allow-Origin", allowed_origin)
@@ -277,7 +307,7 @@ pub fn extension_protocol_handler(
                 }
             }
         } else {
-            eprintln!("No path segment found for Android format");
+            tracing::warn!("No path segment found for Android format");
             return Response::builder()
                 .status(400)
                 .header("Access-Control-Allow-Origin", allowed_origin)
@@ -286,15 +316,11 @@ pub fn extension_protocol_handler(
         }
     } else if host != "localhost" && !host.is_empty() {
         // Desktop format: haex-extension://<base64>/{assetPath}
-        println!("Desktop format detected: haex-extension://<base64>/...");
         match BASE64_STANDARD.decode(host) {
             Ok(decoded_bytes) => match String::from_utf8(decoded_bytes) {
                 Ok(json_str) => match serde_json::from_str::<ExtensionInfo>(&json_str) {
                     Ok(info) => {
-                        println!("=== Extension Info from base64-encoded host ===");
-                        println!("  PublicKey: {}", info.public_key);
-                        println!("  Name: {}", info.name);
-                        println!("  Version: {}", info.version);
+                        tracing::trace!(?info, "Extension info from base64-encoded host");
                         cache_extension_info(&info);
 
                         // Parse path segments as asset path
@@ -309,7 +335,7 @@ pub fn extension_protocol_handler(
                         (info, segments)
                     }
                     Err(e) => {
-                        eprintln!("Failed to parse JSON from base64 host: {e}");
+                        tracing::warn!("Failed to parse JSON from base64 host: {e}");
                         return Response::builder()
                             .status(400)
                             .header("Access-Control-Allow-Origin", allowed_origin)
@@ -318,7 +344,7 @@ pub fn extension_protocol_handler(
                     }
                 },
                 Err(e) => {
-                    eprintln!("Failed to decode UTF-8 from base64 host: {e}");
+                    tracing::warn!("Failed to decode UTF-8 from base64 host: {e}");
                     return Response::builder()
                         .status(400)
                         .header("Access-Control-Allow-Origin", allowed_origin)
@@ -327,7 +353,7 @@ pub fn extension_protocol_handler(
                 }
             },
             Err(e) => {
-                eprintln!("Failed to decode base64 host: {e}");
+                tracing::warn!("Failed to decode base64 host: {e}");
                 return Response::builder()
                     .status(400)
                     .header("Access-Control-Allow-Origin", allowed_origin)
@@ -351,20 +377,16 @@ pub fn extension_protocol_handler(
         &raw_asset_path
     };
 
-    println!("Path: {path_str}");
-    println!("Asset to load: {asset_to_load}");
+    tracing::trace!(path = path_str, "Asset to load: {asset_to_load}");
 
     let absolute_secure_path = resolve_secure_extension_asset_path(
         app_handle,
-        &state,
         &info.public_key,
         &info.name,
         &info.version,
         asset_to_load,
     )?;
 
-    println!("Resolved path: {}", absolute_secure_path.display());
-    println!("File exists: {}", absolute_secure_path.exists());
 
     if absolute_secure_path.exists() && absolute_secure_path.is_file() {
         match fs::read(&absolute_secure_path) {
@@ -377,8 +399,8 @@ pub fn extension_protocol_handler(
                 // No server-side HTML modification needed
 
                 let content_length = content.len();
-                println!(
-                    "Liefere {} ({}, {} bytes) ",
+                tracing::trace!(
+                    "Liefere {} ({}, {} bytes)",
                     absolute_secure_path.display(),
                     mime_type,
                     content_length
@@ -396,7 +418,7 @@ pub fn extension_protocol_handler(
                     .map_err(|e| e.into())
             }
             Err(e) => {
-                eprintln!(
+                tracing::warn!(
                     "Fehler beim Lesen der Datei {}: {}",
                     absolute_secure_path.display(),
                     e
@@ -421,14 +443,13 @@ pub fn extension_protocol_handler(
         // Asset not found - try index.html fallback for SPA routing
         // This allows client-side routing to work (e.g., /settings -> index.html)
         if asset_to_load != "index.html" {
-            eprintln!(
+            tracing::trace!(
                 "Asset nicht gefunden: {}, versuche index.html fallback für SPA routing",
                 absolute_secure_path.display()
             );
 
             let index_path = resolve_secure_extension_asset_path(
                 app_handle,
-                &state,
                 &info.public_key,
                 &info.name,
                 &info.version,
@@ -463,7 +484,7 @@ pub fn extension_protocol_handler(
         }
 
         // No fallback available - return 404
-        eprintln!(
+        tracing::debug!(
             "Asset nicht gefunden oder ist kein File: {}",
             absolute_secure_path.display()
         );
@@ -495,39 +516,33 @@ fn parse_encoded_info_from_origin_or_uri_or_referer_or_cache(
         if let Ok(hex) = parse_from_origin(origin) {
             if let Ok(info) = process_hex_encoded_json(&hex) {
                 cache_extension_info(&info); // Cache setzen
-                println!("Parsed und gecached aus Origin: {hex}");
+                tracing::trace!("Parsed und gecached aus Origin: {hex}");
                 return Ok(info);
             }
         }
     }
 
-    println!("Fallback zu URI-Parsing");
     if let Ok(hex) = parse_from_uri_path(uri_ref) {
         if let Ok(info) = process_hex_encoded_json(&hex) {
             cache_extension_info(&info); // Cache setzen
-            println!("Parsed und gecached aus URI: {hex}");
+            tracing::trace!("Parsed und gecached aus URI: {hex}");
             return Ok(info);
         }
     }
 
-    println!("Fallback zu Referer-Parsing: {referer}");
     if !referer.is_empty() && referer != "null" {
         if let Ok(hex) = parse_from_uri_string(referer) {
             if let Ok(info) = process_hex_encoded_json(&hex) {
                 cache_extension_info(&info); // Cache setzen
-                println!("Parsed und gecached aus Referer: {hex}");
+                tracing::trace!("Parsed und gecached aus Referer: {hex}");
                 return Ok(info);
             }
         }
     }
 
     // 4. Fallback: Globaler Cache (für Assets in derselben Session)
-    println!("Fallback zu Cache");
     if let Some(cached_info) = get_cached_extension_info() {
-        println!(
-            "Gecached Info verwendet: PublicKey={}, Name={}, Version={}",
-            cached_info.public_key, cached_info.name, cached_info.version
-        );
+        tracing::trace!(info = ?cached_info, "Gecached Info verwendet");
         return Ok(cached_info);
     }
 
@@ -618,12 +633,7 @@ fn parse_extension_info_from_path(
 
     match (segments_iter.next(), segments_iter.next(), segments_iter.next()) {
         (Some(public_key), Some(name), Some(version)) => {
-            println!("=== Extension Protocol Handler (path-based) ===");
-            println!("Full URI: {uri_ref}");
-            println!("Parsed from path segments:");
-            println!("  PublicKey: {public_key}");
-            println!("  Name: {name}");
-            println!("  Version: {version}");
+            tracing::trace!(public_key, name, version, "Extension info from path segments");
 
             let info = ExtensionInfo {
                 public_key: public_key.to_string(),
@@ -644,12 +654,7 @@ fn parse_extension_info_from_path(
                 origin, uri_ref, referer,
             ) {
                 Ok(decoded) => {
-                    println!("=== Extension Protocol Handler (legacy hex format) ===");
-                    println!("Full URI: {uri_ref}");
-                    println!("Decoded info:");
-                    println!("  PublicKey: {}", decoded.public_key);
-                    println!("  Name: {}", decoded.name);
-                    println!("  Version: {}", decoded.version);
+                    tracing::trace!(info = ?decoded, "Extension info from legacy hex format");
 
                     // For legacy format, collect all segments after parsing (owned strings)
                     let segments: Vec<String> = path_str
@@ -662,7 +667,7 @@ fn parse_extension_info_from_path(
                     Ok((decoded, segments))
                 }
                 Err(e) => {
-                    eprintln!("Fehler beim Parsen (alle Fallbacks): {e}");
+                    tracing::warn!(uri = %uri_ref, "Fehler beim Parsen (alle Fallbacks): {e}");
                     Err(format!("Ungültige Anfrage: {e}").into())
                 }
             }
@@ -0,0 +1,506 @@
+// src-tauri/src/extension/core/trust.rs
+// Vertrauen in Extension-Publisher. Eine gültige Signatur beweist nur, dass Paket und Schlüssel
+// zusammenpassen - nicht, wem der Schlüssel gehört. Der Vault führt deshalb eine (synchronisierte)
+// Liste bekannter Publisher-Schlüssel mit Vertrauensstufe. Die Signatur-Policy in den
+// Vault-Einstellungen legt fest, ob Extensions unbekannter Publisher installiert werden dürfen.
+// Ein Schlüsselwechsel wird über eine vom bisherigen Schlüssel signierte Erklärung im Manifest
+// nachgewiesen; der neue Schlüssel erbt dann die Vertrauensstufe des alten.
+
+use crate::crdt::hlc::HlcService;
+use crate::database::core::with_connection;
+use crate::database::error::DatabaseError;
+use crate::database::generated::HaexExtensionPublishers;
+use crate::database::vault::VaultSession;
+use crate::extension::core::manifest::ExtensionManifest;
+use crate::extension::crypto::ExtensionCrypto;
+use crate::extension::database::executor::SqlExecutor;
+use crate::extension::error::ExtensionError;
+use crate::table_names::{TABLE_EXTENSION_PUBLISHERS, TABLE_SETTINGS};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use ts_rs::TS;
+
+/// Schlüssel der Signatur-Policy in `haex_settings` (Typ `settings`)
+pub const SIGNATURE_POLICY_KEY: &str = "extensionSignaturePolicy";
+const SETTINGS_TYPE: &str = "settings";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustLevel {
+    /// Extensions dieses Publishers werden ohne Rückfrage installiert
+    Trusted,
+    /// Bekannt, aber nicht ausdrücklich vertraut - wird wie ein unbekannter Publisher behandelt
+    Known,
+    /// Extensions dieses Publishers werden nie installiert
+    Blocked,
+}
+
+impl TrustLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrustLevel::Trusted => "trusted",
+            TrustLevel::Known => "known",
+            TrustLevel::Blocked => "blocked",
+        }
+    }
+}
+
+impl FromStr for TrustLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "trusted" => Ok(TrustLevel::Trusted),
+            "known" => Ok(TrustLevel::Known),
+            "blocked" => Ok(TrustLevel::Blocked),
+            _ => Err(format!("Unknown trust level: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum SignaturePolicy {
+    /// Jede gültig signierte Extension darf installiert werden (außer blockierte Publisher)
+    AllowAnySigned,
+    /// Nur Extensions vertrauter Publisher
+    TrustedOnly,
+    /// Wie `AllowAnySigned`, aber mit Warnung bei nicht vertrauten Publishern
+    #[default]
+    Warn,
+}
+
+impl SignaturePolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignaturePolicy::AllowAnySigned => "allow_any_signed",
+            SignaturePolicy::TrustedOnly => "trusted_only",
+            SignaturePolicy::Warn => "warn",
+        }
+    }
+}
+
+impl FromStr for SignaturePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow_any_signed" => Ok(SignaturePolicy::AllowAnySigned),
+            "trusted_only" => Ok(SignaturePolicy::TrustedOnly),
+            "warn" => Ok(SignaturePolicy::Warn),
+            _ => Err(format!("Unknown signature policy: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustedPublisher {
+    pub public_key: String,
+    pub display_name: Option<String>,
+    pub trust_level: TrustLevel,
+    pub created_at: Option<String>,
+}
+
+impl From<HaexExtensionPublishers> for TrustedPublisher {
+    fn from(row: HaexExtensionPublishers) -> Self {
+        // Unbekannte Werte (z.B. von einer neueren Version synchronisiert) gelten nicht als vertraut
+        let trust_level = row.trust_level.parse().unwrap_or_else(|e| {
+            tracing::warn!("{e}, treating publisher {} as known", row.public_key);
+            TrustLevel::Known
+        });
+
+        Self {
+            public_key: row.public_key,
+            display_name: row.display_name,
+            trust_level,
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// Bewertung des Publishers einer Extension vor der Installation
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct PublisherTrust {
+    pub public_key: String,
+    /// Eintrag des Publishers - bei einem Schlüsselwechsel ggf. der des bisherigen Schlüssels
+    pub publisher: Option<TrustedPublisher>,
+    pub policy: SignaturePolicy,
+    /// `false`, wenn die Policy die Installation verbietet
+    pub allowed: bool,
+    /// Warnung bzw. Ablehnungsgrund für die Anzeige
+    pub reason: Option<String>,
+    /// Bisheriger Schlüssel, falls das Manifest einen gültigen Schlüsselwechsel enthält
+    pub rotated_from: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrustDecision {
+    Allowed,
+    Warn(String),
+    Rejected(String),
+}
+
+/// Wendet die Policy auf die Vertrauensstufe eines Publishers an (`None` = unbekannt)
+pub fn evaluate(policy: SignaturePolicy, trust_level: Option<TrustLevel>) -> TrustDecision {
+    match (policy, trust_level) {
+        (_, Some(TrustLevel::Blocked)) => {
+            TrustDecision::Rejected("The publisher is blocked".to_string())
+        }
+        (_, Some(TrustLevel::Trusted)) => TrustDecision::Allowed,
+        (SignaturePolicy::TrustedOnly, _) => TrustDecision::Rejected(
+            "Only extensions from trusted publishers may be installed".to_string(),
+        ),
+        (SignaturePolicy::Warn, Some(TrustLevel::Known)) => {
+            TrustDecision::Warn("The publisher is known but not trusted".to_string())
+        }
+        (SignaturePolicy::Warn, None) => {
+            TrustDecision::Warn("The publisher is unknown".to_string())
+        }
+        (SignaturePolicy::AllowAnySigned, _) => TrustDecision::Allowed,
+    }
+}
+
+/// Liest die Signatur-Policy des Vaults; ohne Eintrag gilt `Warn`
+pub fn get_policy(conn: &Connection) -> Result<SignaturePolicy, DatabaseError> {
+    let value: Option<String> = conn
+        .query_row(
+            &format!("SELECT value FROM {TABLE_SETTINGS} WHERE key = ? AND type = ?"),
+            params![SIGNATURE_POLICY_KEY, SETTINGS_TYPE],
+            |row| row.get(0),
+        )
+        .optional()?
+        .flatten();
+
+    Ok(match value {
+        Some(value) => value.parse().unwrap_or_else(|e| {
+            tracing::warn!("{e}, falling back to default policy");
+            SignaturePolicy::default()
+        }),
+        None => SignaturePolicy::default(),
+    })
+}
+
+pub fn get_publisher(
+    conn: &Connection,
+    public_key: &str,
+) -> Result<Option<TrustedPublisher>, DatabaseError> {
+    let publisher = conn
+        .query_row(
+            &format!("SELECT * FROM {TABLE_EXTENSION_PUBLISHERS} WHERE public_key = ?"),
+            params![public_key],
+            HaexExtensionPublishers::from_row,
+        )
+        .optional()?;
+    Ok(publisher.map(Into::into))
+}
+
+pub async fn list_publishers(
+    vault: &Arc<VaultSession>,
+) -> Result<Vec<TrustedPublisher>, ExtensionError> {
+    vault
+        .read(|vault| {
+            vault.readers.with_reader(|conn| {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT * FROM {TABLE_EXTENSION_PUBLISHERS} ORDER BY display_name, public_key"
+                ))?;
+                let publishers = stmt
+                    .query_map([], HaexExtensionPublishers::from_row)?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(publishers.into_iter().map(Into::into).collect())
+            })
+        })
+        .await
+        .map_err(ExtensionError::from)
+}
+
+/// Legt einen Publisher an oder ändert Anzeigename und Vertrauensstufe
+pub async fn set_publisher(
+    vault: &Arc<VaultSession>,
+    public_key: &str,
+    display_name: Option<&str>,
+    trust_level: TrustLevel,
+) -> Result<TrustedPublisher, ExtensionError> {
+    let public_key = public_key.trim().to_lowercase();
+    if !matches!(hex::decode(&public_key), Ok(bytes) if bytes.len() == 32) {
+        return Err(ExtensionError::InvalidPublicKey {
+            reason: "Expected 32 bytes as hex".to_string(),
+        });
+    }
+
+    let key = public_key.clone();
+    let display_name = display_name.map(str::to_string);
+    vault
+        .write(move |vault| {
+            with_connection(&vault.db, |conn| {
+                let tx = conn.transaction()?;
+                let hlc_service = lock_hlc(vault)?;
+
+                upsert_publisher(
+                    &tx,
+                    &hlc_service,
+                    &key,
+                    display_name.as_deref(),
+                    trust_level,
+                )?;
+                let publisher = get_publisher(&tx, &key)?;
+
+                tx.commit()?;
+                Ok(publisher)
+            })
+        })
+        .await?
+        .ok_or_else(|| ExtensionError::ValidationError {
+            reason: format!("Publisher {public_key} was not saved"),
+        })
+}
+
+pub async fn remove_publisher(
+    vault: &Arc<VaultSession>,
+    public_key: &str,
+) -> Result<(), ExtensionError> {
+    let public_key = public_key.to_string();
+    vault
+        .write(move |vault| {
+            with_connection(&vault.db, |conn| {
+                let tx = conn.transaction()?;
+                let hlc_service = lock_hlc(vault)?;
+
+                SqlExecutor::execute_internal_typed(
+                    &tx,
+                    &hlc_service,
+                    &format!("DELETE FROM {TABLE_EXTENSION_PUBLISHERS} WHERE public_key = ?"),
+                    params![public_key],
+                )?;
+
+                tx.commit()?;
+                Ok(())
+            })
+        })
+        .await
+        .map_err(ExtensionError::from)
+}
+
+fn lock_hlc(vault: &VaultSession) -> Result<HlcService, DatabaseError> {
+    let guard = vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+        reason: "Failed to lock HLC service".to_string(),
+    })?;
+    Ok(guard.clone())
+}
+
+fn upsert_publisher(
+    tx: &Transaction,
+    hlc_service: &HlcService,
+    public_key: &str,
+    display_name: Option<&str>,
+    trust_level: TrustLevel,
+) -> Result<(), DatabaseError> {
+    if get_publisher(tx, public_key)?.is_some() {
+        SqlExecutor::execute_internal_typed(
+            tx,
+            hlc_service,
+            &format!(
+                "UPDATE {TABLE_EXTENSION_PUBLISHERS} SET display_name = ?, trust_level = ? WHERE public_key = ?"
+            ),
+            params![display_name, trust_level.as_str(), public_key],
+        )?;
+    } else {
+        SqlExecutor::execute_internal_typed(
+            tx,
+            hlc_service,
+            &format!(
+                "INSERT INTO {TABLE_EXTENSION_PUBLISHERS} (id, public_key, display_name, trust_level) VALUES (?, ?, ?, ?)"
+            ),
+            params![
+                uuid::Uuid::new_v4().to_string(),
+                public_key,
+                display_name,
+                trust_level.as_str()
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Überträgt beim Schlüsselwechsel den Eintrag des bisherigen Schlüssels auf den neuen,
+/// sofern für den neuen noch keiner existiert. Der alte Eintrag bleibt bestehen.
+pub fn inherit_publisher(
+    tx: &Transaction,
+    hlc_service: &HlcService,
+    previous_public_key: &str,
+    public_key: &str,
+) -> Result<(), DatabaseError> {
+    if get_publisher(tx, public_key)?.is_some() {
+        return Ok(());
+    }
+    if let Some(previous) = get_publisher(tx, previous_public_key)? {
+        upsert_publisher(
+            tx,
+            hlc_service,
+            public_key,
+            previous.display_name.as_deref(),
+            previous.trust_level,
+        )?;
+    }
+    Ok(())
+}
+
+/// Bewertet den Publisher eines Manifests anhand von Policy, Trust-Store und Schlüsselwechsel
+pub async fn assess(
+    vault: &Arc<VaultSession>,
+    manifest: &ExtensionManifest,
+) -> Result<PublisherTrust, ExtensionError> {
+    let public_key = manifest.public_key.clone();
+
+    let rotation = manifest.key_rotation.as_ref().map(|rotation| {
+        ExtensionCrypto::verify_key_rotation(
+            &rotation.previous_public_key,
+            &public_key,
+            &rotation.signature,
+        )
+        .map(|_| rotation.previous_public_key.clone())
+    });
+
+    let own_key = public_key.clone();
+    let previous_key = match &rotation {
+        Some(Ok(previous_key)) => Some(previous_key.clone()),
+        _ => None,
+    };
+    let (policy, own, previous) = vault
+        .read(move |vault| {
+            vault.readers.with_reader(|conn| {
+                let policy = get_policy(conn)?;
+                let own = get_publisher(conn, &own_key)?;
+                let previous = match &previous_key {
+                    Some(previous_key) => get_publisher(conn, previous_key)?,
+                    None => None,
+                };
+                Ok((policy, own, previous))
+            })
+        })
+        .await?;
+
+    if let Some(Err(e)) = rotation {
+        return Ok(PublisherTrust {
+            public_key,
+            publisher: own,
+            policy,
+            allowed: false,
+            reason: Some(format!("Invalid key rotation statement: {e}")),
+            rotated_from: None,
+        });
+    }
+
+    // Ein blockierter alter Schlüssel kann sich nicht über einen Wechsel freischalten
+    let publisher = match (own, previous) {
+        (_, Some(previous)) if previous.trust_level == TrustLevel::Blocked => Some(previous),
+        (Some(own), _) => Some(own),
+        (None, previous) => previous,
+    };
+
+    let (allowed, reason) = match evaluate(policy, publisher.as_ref().map(|p| p.trust_level)) {
+        TrustDecision::Allowed => (true, None),
+        TrustDecision::Warn(reason) => (true, Some(reason)),
+        TrustDecision::Rejected(reason) => (false, Some(reason)),
+    };
+
+    Ok(PublisherTrust {
+        public_key,
+        publisher,
+        policy,
+        allowed,
+        reason,
+        rotated_from: rotation.and_then(Result::ok),
+    })
+}
+
+impl PublisherTrust {
+    /// Fehler, wenn die Policy die Installation verbietet
+    pub fn ensure_allowed(&self) -> Result<(), ExtensionError> {
+        if self.allowed {
+            return Ok(());
+        }
+        Err(ExtensionError::UntrustedPublisher {
+            public_key: self.public_key.clone(),
+            reason: self.reason.clone().unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    #[test]
+    fn blocked_publishers_are_always_rejected() {
+        for policy in [
+            SignaturePolicy::AllowAnySigned,
+            SignaturePolicy::TrustedOnly,
+            SignaturePolicy::Warn,
+        ] {
+            assert!(matches!(
+                evaluate(policy, Some(TrustLevel::Blocked)),
+                TrustDecision::Rejected(_)
+            ));
+            assert_eq!(
+                evaluate(policy, Some(TrustLevel::Trusted)),
+                TrustDecision::Allowed
+            );
+        }
+    }
+
+    #[test]
+    fn policy_decides_for_untrusted_publishers() {
+        assert!(matches!(
+            evaluate(SignaturePolicy::TrustedOnly, None),
+            TrustDecision::Rejected(_)
+        ));
+        assert!(matches!(
+            evaluate(SignaturePolicy::TrustedOnly, Some(TrustLevel::Known)),
+            TrustDecision::Rejected(_)
+        ));
+        assert!(matches!(
+            evaluate(SignaturePolicy::Warn, None),
+            TrustDecision::Warn(_)
+        ));
+        assert_eq!(
+            evaluate(SignaturePolicy::AllowAnySigned, Some(TrustLevel::Known)),
+            TrustDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn parses_stored_values() {
+        assert_eq!("trusted_only".parse(), Ok(SignaturePolicy::TrustedOnly));
+        assert_eq!(
+            SignaturePolicy::AllowAnySigned.as_str().parse(),
+            Ok(SignaturePolicy::AllowAnySigned)
+        );
+        assert_eq!("blocked".parse(), Ok(TrustLevel::Blocked));
+        assert!("everyone".parse::<SignaturePolicy>().is_err());
+    }
+
+    #[test]
+    fn verifies_key_rotation_statement() {
+        let previous = SigningKey::from_bytes(&[1u8; 32]);
+        let next = SigningKey::from_bytes(&[2u8; 32]);
+        let previous_hex = hex::encode(previous.verifying_key().to_bytes());
+        let next_hex = hex::encode(next.verifying_key().to_bytes());
+
+        let message = ExtensionCrypto::key_rotation_message(&previous_hex, &next_hex);
+        let signature = hex::encode(previous.sign(message.as_bytes()).to_bytes());
+        assert!(ExtensionCrypto::verify_key_rotation(&previous_hex, &next_hex, &signature).is_ok());
+
+        // Der neue Schlüssel kann sich nicht selbst als Nachfolger bestätigen
+        let forged = hex::encode(next.sign(message.as_bytes()).to_bytes());
+        assert!(ExtensionCrypto::verify_key_rotation(&previous_hex, &next_hex, &forged).is_err());
+    }
+}
@@ -17,21 +17,25 @@ impl ExtensionCrypto {
         content_hash_hex: &str,
         signature_hex: &str,
     ) -> Result<(), String> {
-        let public_key_bytes =
-            hex::decode(public_key_hex).map_err(|e| format!("Invalid public key: {e}"))?;
-        let public_key = VerifyingKey::from_bytes(&public_key_bytes.try_into().unwrap())
-            .map_err(|e| format!("Invalid public key: {e}"))?;
-
-        let signature_bytes =
-            hex::decode(signature_hex).map_err(|e| format!("Invalid signature: {e}"))?;
-        let signature = Signature::from_bytes(&signature_bytes.try_into().unwrap());
-
         let content_hash =
             hex::decode(content_hash_hex).map_err(|e| format!("Invalid content hash: {e}"))?;
+        Self::verify_bytes(public_key_hex, &content_hash, signature_hex)
+    }
 
-        public_key
-            .verify(&content_hash, &signature)
-            .map_err(|e| format!("Signature verification failed: {e}"))
+    /// Nachricht, die der bisherige Publisher-Schlüssel beim Schlüsselwechsel signiert
+    pub fn key_rotation_message(previous_public_key: &str, new_public_key: &str) -> String {
+        format!("haex-key-rotation:{previous_public_key}:{new_public_key}")
+    }
+
+    /// Verifiziert eine Schlüsselwechsel-Erklärung: `signature_hex` muss vom bisherigen
+    /// Schlüssel über `key_rotation_message` erstellt worden sein
+    pub fn verify_key_rotation(
+        previous_public_key_hex: &str,
+        new_public_key_hex: &str,
+        signature_hex: &str,
+    ) -> Result<(), String> {
+        let message = Self::key_rotation_message(previous_public_key_hex, new_public_key_hex);
+        Self::verify_bytes(previous_public_key_hex, message.as_bytes(), signature_hex)
     }
 
     /// Berechnet Hash eines Verzeichnisses (für Verifikation)
@@ -127,6 +131,32 @@ impl ExtensionCrypto {
         Ok(hex::encode(hasher.finalize()))
     }
 
+    /// Prüft eine Ed25519-Signatur über beliebige Bytes
+    fn verify_bytes(
+        public_key_hex: &str,
+        message: &[u8],
+        signature_hex: &str,
+    ) -> Result<(), String> {
+        let public_key_bytes =
+            hex::decode(public_key_hex).map_err(|e| format!("Invalid public key: {e}"))?;
+        let public_key_bytes: [u8; 32] = public_key_bytes
+            .try_into()
+            .map_err(|_| "Invalid public key: expected 32 bytes".to_string())?;
+        let public_key = VerifyingKey::from_bytes(&public_key_bytes)
+            .map_err(|e| format!("Invalid public key: {e}"))?;
+
+        let signature_bytes =
+            hex::decode(signature_hex).map_err(|e| format!("Invalid signature: {e}"))?;
+        let signature_bytes: [u8; 64] = signature_bytes
+            .try_into()
+            .map_err(|_| "Invalid signature: expected 64 bytes".to_string())?;
+        let signature = Signature::from_bytes(&signature_bytes);
+
+        public_key
+            .verify(message, &signature)
+            .map_err(|e| format!("Signature verification failed: {e}"))
+    }
+
     fn collect_files_recursively(dir: &Path, file_list: &mut Vec<PathBuf>) -> std::io::Result<()> {
         if dir.is_dir() {
             for entry in fs::read_dir(dir)? {
@@ -3,13 +3,44 @@
 use crate::crdt::hlc::HlcService;
 use crate::crdt::transformer::CrdtTransformer;
 use crate::crdt::trigger;
-use crate::database::core::{convert_value_ref_to_json, parse_sql_statements};
+use crate::database::core::{
+    convert_value_ref_to_json, parse_sql_statements, SqlParams, ValueConverter,
+};
 use crate::database::error::DatabaseError;
-use rusqlite::{params_from_iter, types::Value as SqliteValue, ToSql, Transaction};
+use crate::logging::redact;
+use rusqlite::{Statement as SqliteStatement, ToSql, Transaction};
 use serde_json::Value as JsonValue;
 use sqlparser::ast::Statement;
 use std::collections::HashSet;
 
+/// Parameter für die internen Executor-Funktionen: typisiert aus Rust-Code oder als JSON vom Frontend
+enum Bindings<'a> {
+    Typed(&'a [&'a dyn ToSql]),
+    Json(&'a SqlParams),
+}
+
+impl Bindings<'_> {
+    fn bind(&self, stmt: &mut SqliteStatement, sql: &str) -> Result<(), DatabaseError> {
+        match self {
+            Bindings::Typed(params) => {
+                let expected = stmt.parameter_count();
+                if params.len() != expected {
+                    return Err(DatabaseError::ParameterMismatchError {
+                        expected,
+                        provided: params.len(),
+                        sql: sql.to_string(),
+                    });
+                }
+                for (i, param) in params.iter().enumerate() {
+                    stmt.raw_bind_parameter(i + 1, *param)?;
+                }
+                Ok(())
+            }
+            Bindings::Json(params) => ValueConverter::bind_params(stmt, params, sql),
+        }
+    }
+}
+
 /// SQL-Executor OHNE Berechtigungsprüfung - für interne Nutzung
 pub struct SqlExecutor;
 
@@ -21,6 +52,26 @@ impl SqlExecutor {
         hlc_service: &HlcService,
         sql: &str,
         params: &[&dyn ToSql],
+    ) -> Result<HashSet<String>, DatabaseError> {
+        Self::execute_with_bindings(tx, hlc_service, sql, Bindings::Typed(params))
+    }
+
+    /// Führt ein SQL Statement MIT RETURNING aus (mit CRDT)
+    /// Returns: (modified_schema_tables, returning_results)
+    pub fn query_internal_typed(
+        tx: &Transaction,
+        hlc_service: &HlcService,
+        sql: &str,
+        params: &[&dyn ToSql],
+    ) -> Result<(HashSet<String>, Vec<Vec<JsonValue>>), DatabaseError> {
+        Self::query_with_bindings(tx, hlc_service, sql, Bindings::Typed(params))
+    }
+
+    fn execute_with_bindings(
+        tx: &Transaction,
+        hlc_service: &HlcService,
+        sql: &str,
+        bindings: Bindings,
     ) -> Result<HashSet<String>, DatabaseError> {
         let mut ast_vec = parse_sql_statements(sql)?;
 
@@ -52,15 +103,16 @@ impl SqlExecutor {
         }
 
         let sql_str = statement.to_string();
-        eprintln!("DEBUG: Transformed execute SQL: {sql_str}");
+        tracing::debug!(sql = %redact::sql(&sql_str), "Transformed execute SQL");
 
         // Führe Statement aus
-        tx.execute(&sql_str, params)
-            .map_err(|e| DatabaseError::ExecutionError {
-                sql: sql_str.clone(),
-                table: None,
-                reason: format!("Execute failed: {e}"),
-            })?;
+        let mut stmt = tx
+            .prepare(&sql_str)
+            .map_err(|e| DatabaseError::execution(e, &sql_str, None))?;
+        bindings.bind(&mut stmt, &sql_str)?;
+        stmt.raw_execute()
+            .map_err(|e| DatabaseError::execution(e, &sql_str, None))?;
+        drop(stmt);
 
         // Trigger-Logik für CREATE TABLE
         if let Statement::CreateTable(create_table_details) = statement {
@@ -70,20 +122,18 @@ impl SqlExecutor {
                 .trim_matches('"')
                 .trim_matches('`')
                 .to_string();
-            eprintln!("DEBUG: Setting up triggers for table: {table_name_str}");
+            tracing::debug!("Setting up triggers for table: {table_name_str}");
             trigger::setup_triggers_for_table(tx, &table_name_str, false)?;
         }
 
         Ok(modified_schema_tables)
     }
 
-    /// Führt ein SQL Statement MIT RETURNING aus (mit CRDT)
-    /// Returns: (modified_schema_tables, returning_results)
-    pub fn query_internal_typed(
+    fn query_with_bindings(
         tx: &Transaction,
         hlc_service: &HlcService,
         sql: &str,
-        params: &[&dyn ToSql],
+        bindings: Bindings,
     ) -> Result<(HashSet<String>, Vec<Vec<JsonValue>>), DatabaseError> {
         let mut ast_vec = parse_sql_statements(sql)?;
 
@@ -115,16 +165,12 @@ impl SqlExecutor {
         }
 
         let sql_str = statement.to_string();
-        eprintln!("DEBUG: Transformed SQL (with RETURNING): {sql_str}");
+        tracing::debug!(sql = %redact::sql(&sql_str), "Transformed SQL (with RETURNING)");
 
         // Prepare und query ausführen
         let mut stmt = tx
             .prepare(&sql_str)
-            .map_err(|e| DatabaseError::ExecutionError {
-                sql: sql_str.clone(),
-                table: None,
-                reason: e.to_string(),
-            })?;
+            .map_err(|e| DatabaseError::execution(e, &sql_str, None))?;
 
         let column_names: Vec<String> = stmt
             .column_names()
@@ -133,29 +179,21 @@ impl SqlExecutor {
             .collect();
         let num_columns = column_names.len();
 
-        let mut rows = stmt
-            .query(params_from_iter(params.iter()))
-            .map_err(|e| DatabaseError::ExecutionError {
-                sql: sql_str.clone(),
-                table: None,
-                reason: e.to_string(),
-            })?;
+        bindings.bind(&mut stmt, &sql_str)?;
+        let mut rows = stmt.raw_query();
 
         let mut result_vec: Vec<Vec<JsonValue>> = Vec::new();
 
         // Lese alle RETURNING Zeilen
-        while let Some(row) = rows.next().map_err(|e| DatabaseError::ExecutionError {
-            sql: sql_str.clone(),
-            table: None,
-            reason: e.to_string(),
-        })? {
+        while let Some(row) = rows
+            .next()
+            .map_err(|e| DatabaseError::execution(e, &sql_str, None))?
+        {
             let mut row_values: Vec<JsonValue> = Vec::new();
             for i in 0..num_columns {
-                let value_ref = row.get_ref(i).map_err(|e| DatabaseError::ExecutionError {
-                    sql: sql_str.clone(),
-                    table: None,
-                    reason: e.to_string(),
-                })?;
+                let value_ref = row
+                    .get_ref(i)
+                    .map_err(|e| DatabaseError::execution(e, &sql_str, None))?;
                 let json_value = convert_value_ref_to_json(value_ref)?;
                 row_values.push(json_value);
             }
@@ -170,7 +208,7 @@ impl SqlExecutor {
                 .trim_matches('"')
                 .trim_matches('`')
                 .to_string();
-            eprintln!("DEBUG: Setting up triggers for table (RETURNING): {table_name_str}");
+            tracing::debug!("Setting up triggers for table (RETURNING): {table_name_str}");
             trigger::setup_triggers_for_table(tx, &table_name_str, false)?;
         }
 
@@ -182,14 +220,9 @@ impl SqlExecutor {
         tx: &Transaction,
         hlc_service: &HlcService,
         sql: &str,
-        params: &[JsonValue],
+        params: &SqlParams,
     ) -> Result<HashSet<String>, DatabaseError> {
-        let sql_params: Vec<SqliteValue> = params
-            .iter()
-            .map(crate::database::core::ValueConverter::json_to_rusqlite_value)
-            .collect::<Result<Vec<_>, _>>()?;
-        let param_refs: Vec<&dyn ToSql> = sql_params.iter().map(|p| p as &dyn ToSql).collect();
-        Self::execute_internal_typed(tx, hlc_service, sql, &param_refs)
+        Self::execute_with_bindings(tx, hlc_service, sql, Bindings::Json(params))
     }
 
     /// Query-Variante (mit RETURNING) OHNE Typinformationen (JSON params)
@@ -197,14 +230,9 @@ impl SqlExecutor {
         tx: &Transaction,
         hlc_service: &HlcService,
         sql: &str,
-        params: &[JsonValue],
+        params: &SqlParams,
     ) -> Result<(HashSet<String>, Vec<Vec<JsonValue>>), DatabaseError> {
-        let sql_params: Vec<SqliteValue> = params
-            .iter()
-            .map(crate::database::core::ValueConverter::json_to_rusqlite_value)
-            .collect::<Result<Vec<_>, _>>()?;
-        let param_refs: Vec<&dyn ToSql> = sql_params.iter().map(|p| p as &dyn ToSql).collect();
-        Self::query_internal_typed(tx, hlc_service, sql, &param_refs)
+        Self::query_with_bindings(tx, hlc_service, sql, Bindings::Json(params))
     }
 
     /// Führt mehrere SQL Statements als Batch aus
@@ -212,7 +240,7 @@ impl SqlExecutor {
         tx: &Transaction,
         hlc_service: &HlcService,
         sqls: &[String],
-        params: &[Vec<JsonValue>],
+        params: &[SqlParams],
     ) -> Result<HashSet<String>, DatabaseError> {
         if sqls.len() != params.len() {
             return Err(DatabaseError::ExecutionError {
@@ -236,7 +264,7 @@ impl SqlExecutor {
     pub fn query_select(
         conn: &rusqlite::Connection,
         sql: &str,
-        params: &[JsonValue],
+        params: &SqlParams,
     ) -> Result<Vec<Vec<JsonValue>>, DatabaseError> {
         let mut ast_vec = parse_sql_statements(sql)?;
 
@@ -252,21 +280,14 @@ impl SqlExecutor {
         let stmt_to_execute = ast_vec.pop().unwrap();
         let transformed_sql = stmt_to_execute.to_string();
 
-        eprintln!("DEBUG: SELECT (no transformation): {transformed_sql}");
+        tracing::debug!(sql = %redact::sql(&transformed_sql), "SELECT (no transformation)");
 
-        // Convert JSON params to SQLite values
-        let sql_params: Vec<SqliteValue> = params
-            .iter()
-            .map(crate::database::core::ValueConverter::json_to_rusqlite_value)
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let mut prepared_stmt = conn.prepare(&transformed_sql)?;
+        let mut prepared_stmt = conn.prepare_cached(&transformed_sql)?;
+        ValueConverter::bind_params(&mut prepared_stmt, params, &transformed_sql)?;
 
         let num_columns = prepared_stmt.column_count();
 
-        let param_refs: Vec<&dyn ToSql> = sql_params.iter().map(|p| p as &dyn ToSql).collect();
-
-        let mut rows = prepared_stmt.query(params_from_iter(param_refs.iter()))?;
+        let mut rows = prepared_stmt.raw_query();
 
         let mut result: Vec<Vec<JsonValue>> = Vec::new();
         while let Some(row) = rows.next()? {
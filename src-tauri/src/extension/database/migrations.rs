@@ -0,0 +1,467 @@
+// src-tauri/src/extension/database/migrations.rs
+// Versionierte Schema-Migrationen für die Tabellen einer Extension. Das Manifest deklariert
+// einen Ordner mit `NNNN_name.sql`-Dateien (optional mit `NNNN_name.down.sql` zum Zurücknehmen),
+// die beim Installieren/Aktualisieren in Dateinamen-Reihenfolge angewendet werden.
+// Angewendete Migrationen werden lokal pro Tabellen-Präfix (public_key + name) festgehalten,
+// da auch die Tabellen eine Deinstallation überleben. Die Tracking-Tabelle (Vault-Migration
+// 0006) wird nicht synchronisiert - jedes Gerät migriert sein eigenes Schema.
+
+use crate::crdt::hlc::HlcService;
+use crate::crdt::trigger;
+use crate::database::core::{extract_table_names_from_statement, parse_sql_statements, SqlParams};
+use crate::database::error::DatabaseError;
+use crate::database::fts;
+use crate::extension::database::executor::SqlExecutor;
+use crate::extension::error::ExtensionError;
+use crate::table_names::{TABLE_CRDT_LOGS, TABLE_EXTENSION_MIGRATIONS};
+use rusqlite::{params, Transaction};
+use sqlparser::ast::{visit_relations, Statement};
+use std::ops::ControlFlow;
+use std::path::Path;
+
+const DOWN_SUFFIX: &str = ".down.sql";
+
+/// Eine Migration aus dem Migrations-Ordner einer Extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionMigration {
+    /// Dateiname ohne `.sql`, z.B. `0001_init`
+    pub name: String,
+    pub up: String,
+    pub down: Option<String>,
+}
+
+/// Präfix der Tabellen einer Extension: `{public_key}__{name}__`
+pub fn table_prefix(public_key: &str, name: &str) -> String {
+    format!("{public_key}__{name}__")
+}
+
+/// Liest alle Migrationen eines Ordners, sortiert nach Dateiname
+pub fn read_migrations(dir: &Path) -> Result<Vec<ExtensionMigration>, ExtensionError> {
+    let read_file = |path: &Path| {
+        std::fs::read_to_string(path)
+            .map_err(|e| ExtensionError::filesystem_with_path(path.display().to_string(), e))
+    };
+
+    let mut migrations = Vec::new();
+    for entry in std::fs::read_dir(dir)
+        .map_err(|e| ExtensionError::filesystem_with_path(dir.display().to_string(), e))?
+    {
+        let path = entry
+            .map_err(|e| ExtensionError::Filesystem { source: e })?
+            .path();
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        if !path.is_file() || file_name.ends_with(DOWN_SUFFIX) {
+            continue;
+        }
+        let Some(name) = file_name.strip_suffix(".sql") else {
+            continue;
+        };
+
+        let down_path = dir.join(format!("{name}{DOWN_SUFFIX}"));
+        let down = if down_path.is_file() {
+            Some(read_file(&down_path)?)
+        } else {
+            None
+        };
+
+        migrations.push(ExtensionMigration {
+            name: name.to_string(),
+            up: read_file(&path)?,
+            down,
+        });
+    }
+
+    migrations.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(migrations)
+}
+
+/// Namen der bereits angewendeten Migrationen, sortiert
+pub fn applied_migrations(tx: &Transaction, prefix: &str) -> Result<Vec<String>, DatabaseError> {
+    let mut stmt = tx.prepare(&format!(
+        "SELECT name FROM {TABLE_EXTENSION_MIGRATIONS} WHERE table_prefix = ? ORDER BY name"
+    ))?;
+    let names = stmt
+        .query_map(params![prefix], |row| row.get(0))?
+        .collect::<Result<Vec<String>, _>>()?;
+    Ok(names)
+}
+
+/// Wendet alle noch nicht angewendeten Migrationen an und gibt deren Namen zurück
+pub fn apply_pending(
+    tx: &Transaction,
+    hlc_service: &HlcService,
+    prefix: &str,
+    migrations: &[ExtensionMigration],
+) -> Result<Vec<String>, DatabaseError> {
+    let applied = applied_migrations(tx, prefix)?;
+    let insert_sql =
+        format!("INSERT INTO {TABLE_EXTENSION_MIGRATIONS} (table_prefix, name) VALUES (?, ?)");
+
+    let mut newly_applied = Vec::new();
+    for migration in migrations.iter().filter(|m| !applied.contains(&m.name)) {
+        tracing::debug!("Applying extension migration {}", migration.name);
+        execute_script(tx, hlc_service, prefix, &migration.name, &migration.up)?;
+        tx.execute(&insert_sql, params![prefix, migration.name])?;
+        newly_applied.push(migration.name.clone());
+    }
+
+    Ok(newly_applied)
+}
+
+/// Nimmt angewendete Migrationen zurück, die `target` nicht mehr kennt (neueste zuerst).
+/// Migrationen ohne Down-Skript bleiben angewendet, damit ihr Schema nicht doppelt angelegt wird.
+pub fn revert_unknown(
+    tx: &Transaction,
+    hlc_service: &HlcService,
+    prefix: &str,
+    available: &[ExtensionMigration],
+    target: &[ExtensionMigration],
+) -> Result<Vec<String>, DatabaseError> {
+    let applied = applied_migrations(tx, prefix)?;
+    let delete_sql =
+        format!("DELETE FROM {TABLE_EXTENSION_MIGRATIONS} WHERE table_prefix = ? AND name = ?");
+
+    let mut reverted = Vec::new();
+    for name in applied.iter().rev() {
+        if target.iter().any(|m| &m.name == name) {
+            continue;
+        }
+
+        match available
+            .iter()
+            .find(|m| &m.name == name)
+            .and_then(|m| m.down.as_ref())
+        {
+            Some(down) => {
+                tracing::debug!("Reverting extension migration {name}");
+                execute_script(tx, hlc_service, prefix, name, down)?;
+                tx.execute(&delete_sql, params![prefix, name])?;
+                reverted.push(name.clone());
+            }
+            None => tracing::warn!("Migration {name} has no down script, keeping its schema"),
+        }
+    }
+
+    Ok(reverted)
+}
+
+/// Prüft ein Skript, ohne es auszuführen: nur Schema- und Datenänderungen
+/// auf den eigenen Tabellen der Extension sind erlaubt.
+pub fn validate_script(
+    prefix: &str,
+    migration: &str,
+    script: &str,
+) -> Result<Vec<Statement>, DatabaseError> {
+    let invalid = |reason: String| DatabaseError::MigrationError {
+        migration: migration.to_string(),
+        reason,
+    };
+
+    let statements = parse_sql_statements(script)?;
+
+    for statement in &statements {
+        match statement {
+            Statement::CreateTable(_)
+            | Statement::AlterTable { .. }
+            | Statement::Drop { .. }
+            | Statement::CreateIndex(_)
+            | Statement::Insert(_)
+            | Statement::Update { .. }
+            | Statement::Delete(_) => {}
+            _ => return Err(invalid(format!("Statement type not allowed: {statement}"))),
+        }
+
+        if extract_table_names_from_statement(statement).is_empty() {
+            return Err(invalid(format!(
+                "Cannot determine table of statement: {statement}"
+            )));
+        }
+
+        for table in referenced_tables(statement) {
+            let clean_name = table.trim_matches('"').trim_matches('`');
+            if !clean_name.starts_with(prefix) {
+                return Err(invalid(format!(
+                    "Only the extension's own tables may be used, got '{clean_name}'"
+                )));
+            }
+        }
+    }
+
+    Ok(statements)
+}
+
+/// Alle Tabellen, die ein Statement schreibt oder liest - auch aus `INSERT ... SELECT`,
+/// `CREATE TABLE ... AS SELECT`, Unterabfragen und CTEs. CTE-Namen zählen ebenfalls als
+/// Tabellen und brauchen daher das Präfix. Namen mit Schema (`main.x`) werden vollständig
+/// zurückgegeben und fallen so durch die Präfix-Prüfung.
+fn referenced_tables(statement: &Statement) -> Vec<String> {
+    let mut tables = extract_table_names_from_statement(statement);
+    let _ = visit_relations(statement, |relation| {
+        tables.push(match relation.0.as_slice() {
+            [part] => part
+                .as_ident()
+                .map(|ident| ident.value.clone())
+                .unwrap_or_else(|| part.to_string()),
+            _ => relation.to_string(),
+        });
+        ControlFlow::<()>::Continue(())
+    });
+    tables
+}
+
+/// Führt ein SQL-Skript einer Extension innerhalb der übergebenen Transaktion aus.
+/// CRDT-Trigger werden für neue Tabellen angelegt und nach ALTER TABLE neu erzeugt.
+pub fn execute_script(
+    tx: &Transaction,
+    hlc_service: &HlcService,
+    prefix: &str,
+    migration: &str,
+    script: &str,
+) -> Result<(), DatabaseError> {
+    let statements = validate_script(prefix, migration, script)?;
+
+    for statement in statements {
+        let sql = statement.to_string();
+        SqlExecutor::execute_internal(tx, hlc_service, &sql, &SqlParams::default())?;
+
+        // Geänderte Spalten brauchen neue Trigger (CREATE TABLE erledigt der Executor)
+        if let Statement::AlterTable { name, .. } = &statement {
+            let raw_name = name.to_string();
+            let table_name = raw_name.trim_matches('"').trim_matches('`');
+            trigger::setup_triggers_for_table(tx, table_name, true)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Benennt alle Tabellen einer Extension auf ein neues Präfix um (Schlüsselwechsel des Publishers).
+/// CRDT-Trigger und Suchindizes werden für die neuen Namen neu angelegt, die angewendeten
+/// Migrationen und die Einträge im CRDT-Log wandern mit, damit Änderungen unter dem neuen Namen
+/// synchronisiert werden. Das Umbenennen selbst ist eine lokale Schemaänderung - jedes Gerät
+/// führt sie beim Installieren der Version mit dem neuen Schlüssel selbst aus.
+pub fn rename_table_prefix(
+    tx: &Transaction,
+    old_prefix: &str,
+    new_prefix: &str,
+) -> Result<Vec<String>, DatabaseError> {
+    let tables = tx
+        .prepare(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND substr(name, 1, ?1) = ?2",
+        )?
+        .query_map(params![old_prefix.len(), old_prefix], |row| row.get(0))?
+        .collect::<Result<Vec<String>, _>>()?;
+
+    let mut renamed = Vec::new();
+    for old_name in tables {
+        let new_name = format!("{new_prefix}{}", &old_name[old_prefix.len()..]);
+
+        let search_index = fts::get_search_index(tx, &old_name)?;
+        if search_index.is_some() {
+            fts::drop_search_index(tx, &old_name)?;
+        }
+        let had_triggers = trigger::triggers_exist_for_table(tx, &old_name)?;
+        if had_triggers {
+            trigger::drop_triggers_for_table(tx, &old_name)?;
+        }
+
+        tx.execute_batch(&format!(
+            "ALTER TABLE \"{old_name}\" RENAME TO \"{new_name}\";"
+        ))?;
+        tx.execute(
+            &format!("UPDATE {TABLE_CRDT_LOGS} SET table_name = ?1 WHERE table_name = ?2"),
+            params![new_name, old_name],
+        )?;
+
+        if had_triggers {
+            trigger::setup_triggers_for_table(tx, &new_name, true)?;
+        }
+        if let Some(index) = search_index {
+            fts::declare_search_index(tx, &new_name, &index.columns)?;
+        }
+
+        tracing::debug!("Renamed extension table {old_name} to {new_name}");
+        renamed.push(new_name);
+    }
+
+    tx.execute(
+        &format!("UPDATE {TABLE_EXTENSION_MIGRATIONS} SET table_prefix = ? WHERE table_prefix = ?"),
+        params![new_prefix, old_prefix],
+    )?;
+
+    Ok(renamed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    const PREFIX: &str = "abc__notes__";
+
+    /// Die Tracking-Tabelle legt im Vault die Drizzle-Migration 0006 an
+    fn create_migrations_table(tx: &Transaction) {
+        tx.execute_batch(include_str!(
+            "../../../database/migrations/0006_quiet_ledger.sql"
+        ))
+        .unwrap();
+    }
+
+    fn migration(name: &str, up: &str, down: Option<&str>) -> ExtensionMigration {
+        ExtensionMigration {
+            name: name.to_string(),
+            up: up.to_string(),
+            down: down.map(String::from),
+        }
+    }
+
+    #[test]
+    fn accepts_statements_on_own_tables() {
+        let script = "CREATE TABLE abc__notes__items (id TEXT PRIMARY KEY, title TEXT); \
+                      ALTER TABLE abc__notes__items ADD COLUMN body TEXT; \
+                      DROP TABLE \"abc__notes__old\";";
+        assert_eq!(validate_script(PREFIX, "0001", script).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn rejects_foreign_tables() {
+        let script = "DROP TABLE haex_extensions;";
+        assert!(validate_script(PREFIX, "0001", script).is_err());
+    }
+
+    #[test]
+    fn rejects_reading_foreign_tables() {
+        for script in [
+            "INSERT INTO abc__notes__items SELECT * FROM haex_passwords;",
+            "CREATE TABLE abc__notes__copy AS SELECT * FROM haex_passwords;",
+            "UPDATE abc__notes__items SET title = (SELECT title FROM def__other__t LIMIT 1);",
+            "DELETE FROM abc__notes__items WHERE id IN (SELECT id FROM haex_extensions);",
+            "WITH abc__notes__src AS (SELECT * FROM haex_passwords) \
+             INSERT INTO abc__notes__items SELECT * FROM abc__notes__src;",
+            "INSERT INTO abc__notes__items SELECT * FROM main.abc__notes__old;",
+            "INSERT INTO abc__notes__items SELECT name FROM pragma_table_info('haex_passwords');",
+        ] {
+            assert!(
+                validate_script(PREFIX, "0001", script).is_err(),
+                "{script} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn accepts_reading_own_tables() {
+        let script = "INSERT INTO abc__notes__items SELECT * FROM abc__notes__old; \
+                      UPDATE abc__notes__items SET title = (SELECT title FROM abc__notes__old);";
+        assert_eq!(validate_script(PREFIX, "0001", script).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn rejects_other_statement_types() {
+        assert!(validate_script(PREFIX, "0001", "SELECT * FROM abc__notes__items;").is_err());
+        assert!(validate_script(PREFIX, "0001", "PRAGMA foreign_keys = OFF;").is_err());
+    }
+
+    #[test]
+    fn reads_migrations_sorted_with_down_scripts() {
+        let dir = std::env::temp_dir().join(format!("haex_migrations_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("0002_body.sql"),
+            "ALTER TABLE x ADD COLUMN b TEXT;",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("0001_init.sql"),
+            "CREATE TABLE x (id TEXT PRIMARY KEY);",
+        )
+        .unwrap();
+        std::fs::write(dir.join("0001_init.down.sql"), "DROP TABLE x;").unwrap();
+        std::fs::write(dir.join("README.md"), "ignored").unwrap();
+
+        let migrations = read_migrations(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<_> = migrations.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["0001_init", "0002_body"]);
+        assert_eq!(migrations[0].down.as_deref(), Some("DROP TABLE x;"));
+        assert_eq!(migrations[1].down, None);
+    }
+
+    #[test]
+    fn tracks_applied_migrations_per_prefix() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        let tx = conn.transaction().unwrap();
+        create_migrations_table(&tx);
+        tx.execute(
+            &format!("INSERT INTO {TABLE_EXTENSION_MIGRATIONS} (table_prefix, name) VALUES (?, ?)"),
+            params![PREFIX, "0001_init"],
+        )
+        .unwrap();
+
+        assert_eq!(applied_migrations(&tx, PREFIX).unwrap(), ["0001_init"]);
+        assert!(applied_migrations(&tx, "other__ext__").unwrap().is_empty());
+
+        // Ohne Down-Skript bleibt eine unbekannte Migration angewendet
+        let available = [migration("0001_init", "", None)];
+        let reverted = revert_unknown(&tx, &HlcService::default(), PREFIX, &available, &[]);
+        assert!(reverted.unwrap().is_empty());
+        assert_eq!(applied_migrations(&tx, PREFIX).unwrap(), ["0001_init"]);
+    }
+
+    #[test]
+    fn renames_tables_and_tracking_to_new_prefix() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        let tx = conn.transaction().unwrap();
+        tx.execute_batch(
+            "CREATE TABLE abc__notes__items (id TEXT PRIMARY KEY, title TEXT);
+             INSERT INTO abc__notes__items VALUES ('1', 'hello');
+             CREATE TABLE abc__other__items (id TEXT PRIMARY KEY);
+             CREATE TABLE haex_crdt_logs (id TEXT PRIMARY KEY, table_name TEXT);
+             INSERT INTO haex_crdt_logs VALUES ('l1', 'abc__notes__items');
+             INSERT INTO haex_crdt_logs VALUES ('l2', 'abc__other__items');",
+        )
+        .unwrap();
+        create_migrations_table(&tx);
+        tx.execute(
+            &format!("INSERT INTO {TABLE_EXTENSION_MIGRATIONS} (table_prefix, name) VALUES (?, ?)"),
+            params![PREFIX, "0001_init"],
+        )
+        .unwrap();
+
+        let renamed = rename_table_prefix(&tx, PREFIX, "def__notes__").unwrap();
+        assert_eq!(renamed, ["def__notes__items"]);
+
+        let title: String = tx
+            .query_row("SELECT title FROM def__notes__items", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(title, "hello");
+        assert!(applied_migrations(&tx, PREFIX).unwrap().is_empty());
+        assert_eq!(
+            applied_migrations(&tx, "def__notes__").unwrap(),
+            ["0001_init"]
+        );
+        // Tabellen anderer Extensions bleiben unberührt
+        let untouched: i64 = tx
+            .query_row(
+                "SELECT count(*) FROM sqlite_master WHERE name = 'abc__other__items'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(untouched, 1);
+
+        // Log-Einträge folgen der umbenannten Tabelle
+        let logged: Vec<String> = tx
+            .prepare("SELECT table_name FROM haex_crdt_logs ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(logged, ["def__notes__items", "abc__other__items"]);
+    }
+}
@@ -1,13 +1,25 @@
 // src-tauri/src/extension/database/mod.rs
 
 pub mod executor;
+pub mod migrations;
 use crate::crdt::transformer::CrdtTransformer;
 use crate::crdt::trigger;
-use crate::database::core::{parse_sql_statements, with_connection, ValueConverter};
+use crate::database::core::{
+    extract_table_names_from_statement, parse_sql_statements, with_connection, SqlParams,
+    ValueConverter,
+};
 use crate::database::error::DatabaseError;
+use crate::database::explain::{self, QueryDiagnostics, SlowQueryEntry};
+use crate::database::fts::{self, SearchHit, SearchIndexInfo, SearchOptions};
+use crate::database::live::{LiveQueryOwner, LiveQuerySubscription};
+use crate::database::vault::VaultSession;
+use crate::extension::core::types::ExtensionSource;
 use crate::extension::database::executor::SqlExecutor;
 use crate::extension::error::ExtensionError;
+use crate::extension::permissions::manager::PermissionManager;
+use crate::extension::permissions::types::{Action, DbAction};
 use crate::extension::permissions::validator::SqlPermissionValidator;
+use crate::logging::redact;
 use crate::AppState;
 
 use rusqlite::params_from_iter;
@@ -15,6 +27,8 @@ use rusqlite::types::Value as SqlValue;
 use rusqlite::Transaction;
 use serde_json::Value as JsonValue;
 use sqlparser::ast::{Statement, TableFactor, TableObject};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tauri::State;
 
 /// Führt Statements mit korrekter Parameter-Bindung aus
@@ -34,7 +48,8 @@ impl<'a> StatementExecutor<'a> {
         params: &[SqlValue],
     ) -> Result<(), DatabaseError> {
         let sql = statement.to_string();
-        let expected_params = count_sql_placeholders(&sql);
+        let mut stmt = self.transaction.prepare(&sql)?;
+        let expected_params = stmt.parameter_count();
 
         if expected_params != params.len() {
             return Err(DatabaseError::ParameterMismatchError {
@@ -44,16 +59,16 @@ impl<'a> StatementExecutor<'a> {
             });
         }
 
-        self.transaction
-            .execute(&sql, params_from_iter(params.iter()))
-            .map_err(|e| DatabaseError::ExecutionError {
-                sql,
-                table: Some(
+        stmt.execute(params_from_iter(params.iter())).map_err(|e| {
+            DatabaseError::execution(
+                e,
+                &sql,
+                Some(
                     self.extract_table_name_from_statement(statement)
                         .unwrap_or_else(|| "unknown".to_string()),
                 ),
-                reason: e.to_string(),
-            })?;
+            )
+        })?;
 
         Ok(())
     }
@@ -103,27 +118,34 @@ impl<'a> StatementExecutor<'a> {
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(
+        extension_id = tracing::field::Empty,
+        %vault_id,
+        sql = %redact::sql(sql),
+        params = %redact::params(&params),
+    ),
+)]
 pub async fn extension_sql_execute(
     sql: &str,
-    params: Vec<JsonValue>,
+    params: SqlParams,
     public_key: String,
     name: String,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<Vec<JsonValue>>, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+
     // Get extension to retrieve its ID
-    let extension = state
+    let extension = vault
         .extension_manager
-        .get_extension_by_public_key_and_name(&public_key, &name)?
-        .ok_or_else(|| ExtensionError::NotFound {
-            public_key: public_key.clone(),
-            name: name.clone(),
-        })?;
+        .get_active_extension(&public_key, &name)?;
 
     // Permission check
-    SqlPermissionValidator::validate_sql(&state, &extension.id, sql).await?;
-
-    // Parameter validation
-    validate_params(sql, &params)?;
+    SqlPermissionValidator::validate_sql(&vault, &extension.id, sql).await?;
 
     // SQL parsing
     let mut ast_vec = parse_sql_statements(sql)?;
@@ -143,122 +165,431 @@ pub async fn extension_sql_execute(
 
     // If this is a SELECT statement, delegate to extension_sql_select
     if matches!(statement, Statement::Query(_)) {
-        return extension_sql_select(sql, params, public_key, name, state).await;
+        return extension_sql_select(sql, params, public_key, name, vault_id, state).await;
     }
 
     // Check if statement has RETURNING clause
     let has_returning = crate::database::core::statement_has_returning(&statement);
 
-    // Database operation
-    with_connection(&state.db, |conn| {
-        let tx = conn.transaction().map_err(DatabaseError::from)?;
+    // Database operation - auf dem Schreib-Thread der Vault, nicht auf der Tokio-Runtime
+    let started = Instant::now();
+    let result = vault
+        .write(move |vault| {
+            with_connection(&vault.db, |conn| {
+                let tx = conn.transaction().map_err(DatabaseError::from)?;
 
-        let transformer = CrdtTransformer::new();
+                let transformer = CrdtTransformer::new();
 
-        // Get HLC service reference
-        let hlc_service = state.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
-            reason: "Failed to lock HLC service".to_string(),
-        })?;
+                // Get HLC service reference
+                let hlc_service = vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                    reason: "Failed to lock HLC service".to_string(),
+                })?;
 
-        // Generate HLC timestamp
-        let hlc_timestamp =
-            hlc_service
-                .new_timestamp_and_persist(&tx)
-                .map_err(|e| DatabaseError::HlcError {
-                    reason: e.to_string(),
+                // Generate HLC timestamp
+                let hlc_timestamp = hlc_service.new_timestamp_and_persist(&tx).map_err(|e| {
+                    DatabaseError::HlcError {
+                        reason: e.to_string(),
+                    }
                 })?;
 
-        // Transform statement
-        transformer.transform_execute_statement(&mut statement, &hlc_timestamp)?;
-
-        // Convert parameters to references
-        let sql_values = ValueConverter::convert_params(&params)?;
-        let param_refs: Vec<&dyn rusqlite::ToSql> = sql_values
-            .iter()
-            .map(|v| v as &dyn rusqlite::ToSql)
-            .collect();
-
-        let result = if has_returning {
-            // Use query_internal for statements with RETURNING
-            let (_, rows) = SqlExecutor::query_internal_typed(
-                &tx,
-                &hlc_service,
-                &statement.to_string(),
-                &param_refs,
-            )?;
-            rows
-        } else {
-            // Use execute_internal for statements without RETURNING
-            SqlExecutor::execute_internal_typed(
-                &tx,
-                &hlc_service,
-                &statement.to_string(),
-                &param_refs,
-            )?;
-            vec![]
-        };
-
-        // Handle CREATE TABLE trigger setup
-        if let Statement::CreateTable(ref create_table_details) = statement {
-            // Extract table name and remove quotes (both " and `)
-            let raw_name = create_table_details.name.to_string();
-            println!("DEBUG: Raw table name from AST: {raw_name:?}");
-            println!(
-                "DEBUG: Raw table name chars: {:?}",
-                raw_name.chars().collect::<Vec<_>>()
-            );
-
-            let table_name_str = raw_name.trim_matches('"').trim_matches('`').to_string();
-
-            println!("DEBUG: Cleaned table name: {table_name_str:?}");
-            println!(
-                "DEBUG: Cleaned table name chars: {:?}",
-                table_name_str.chars().collect::<Vec<_>>()
-            );
-
-            println!("Table '{table_name_str}' created by extension, setting up CRDT triggers...");
-            trigger::setup_triggers_for_table(&tx, &table_name_str, false)?;
-            println!("Triggers for table '{table_name_str}' successfully created.");
-        }
+                // Transform statement
+                transformer.transform_execute_statement(&mut statement, &hlc_timestamp)?;
+
+                let result = if has_returning {
+                    // Use query_internal for statements with RETURNING
+                    let (_, rows) = SqlExecutor::query_internal(
+                        &tx,
+                        &hlc_service,
+                        &statement.to_string(),
+                        &params,
+                    )?;
+                    rows
+                } else {
+                    // Use execute_internal for statements without RETURNING
+                    SqlExecutor::execute_internal(
+                        &tx,
+                        &hlc_service,
+                        &statement.to_string(),
+                        &params,
+                    )?;
+                    vec![]
+                };
+
+                // Handle CREATE TABLE trigger setup
+                if let Statement::CreateTable(ref create_table_details) = statement {
+                    // Extract table name and remove quotes (both " and `)
+                    let raw_name = create_table_details.name.to_string();
+                    let table_name_str = raw_name.trim_matches('"').trim_matches('`').to_string();
+
+                    tracing::debug!(
+                        raw_name,
+                        "Table '{table_name_str}' created by extension, setting up CRDT triggers"
+                    );
+                    trigger::setup_triggers_for_table(&tx, &table_name_str, false)?;
+                }
+
+                // Commit transaction
+                tx.commit().map_err(DatabaseError::from)?;
 
-        // Commit transaction
-        tx.commit().map_err(DatabaseError::from)?;
+                Ok(result)
+            })
+        })
+        .await?;
 
-        Ok(result)
-    })
-    .map_err(ExtensionError::from)
+    record_if_slow(&vault, &extension.id, sql, started.elapsed()).await;
+    Ok(result)
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(
+        extension_id = tracing::field::Empty,
+        %vault_id,
+        sql = %redact::sql(sql),
+        params = %redact::params(&params),
+    ),
+)]
 pub async fn extension_sql_select(
     sql: &str,
-    params: Vec<JsonValue>,
+    params: SqlParams,
     public_key: String,
     name: String,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<Vec<JsonValue>>, ExtensionError> {
-    // Get extension to retrieve its ID
-    let extension = state
+    let vault = state.vaults.get(&vault_id)?;
+    let (extension_id, mut ast_vec) =
+        validate_extension_select(&vault, sql, &public_key, &name).await?;
+
+    if ast_vec.is_empty() {
+        return Ok(vec![]);
+    }
+
+    // Database operation - return Vec<Vec<JsonValue>> like sql_select_with_crdt
+    // Läuft auf einer Lese-Verbindung, damit lange Extension-Queries keine Writes blockieren
+    let started = Instant::now();
+    let result = vault
+        .read(move |vault| {
+            vault.readers.with_reader(|conn| {
+                let stmt_to_execute = ast_vec.pop().unwrap();
+                let transformed_sql = stmt_to_execute.to_string();
+
+                // Prepare and execute query
+                let mut prepared_stmt = conn
+                    .prepare(&transformed_sql)
+                    .map_err(|e| DatabaseError::execution(e, &transformed_sql, None))?;
+
+                ValueConverter::bind_params(&mut prepared_stmt, &params, &transformed_sql)?;
+
+                let num_columns = prepared_stmt.column_count();
+                let mut rows = prepared_stmt.raw_query();
+
+                let mut result_vec: Vec<Vec<JsonValue>> = Vec::new();
+
+                while let Some(row) = rows
+                    .next()
+                    .map_err(|e| DatabaseError::execution(e, &transformed_sql, None))?
+                {
+                    let mut row_values: Vec<JsonValue> = Vec::new();
+                    for i in 0..num_columns {
+                        let value_ref = row
+                            .get_ref(i)
+                            .map_err(|e| DatabaseError::execution(e, &transformed_sql, None))?;
+                        let json_value =
+                            crate::database::core::convert_value_ref_to_json(value_ref)?;
+                        row_values.push(json_value);
+                    }
+                    result_vec.push(row_values);
+                }
+
+                Ok(result_vec)
+            })
+        })
+        .await?;
+
+    record_if_slow(&vault, &extension_id, sql, started.elapsed()).await;
+    Ok(result)
+}
+
+/// Query-Plan, Laufzeit und gescannte Zeilen einer lesenden Extension-Query
+#[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(
+        extension_id = tracing::field::Empty,
+        %vault_id,
+        sql = %redact::sql(sql),
+        params = %redact::params(&params),
+    ),
+)]
+pub async fn extension_sql_explain(
+    sql: &str,
+    params: SqlParams,
+    public_key: String,
+    name: String,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<QueryDiagnostics, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    let (_, mut ast_vec) = validate_extension_select(&vault, sql, &public_key, &name).await?;
+
+    if ast_vec.len() != 1 {
+        return Err(ExtensionError::Database {
+            source: DatabaseError::ExecutionError {
+                sql: sql.to_string(),
+                reason: "extension_sql_explain should only receive a single SELECT statement"
+                    .to_string(),
+                table: None,
+            },
+        });
+    }
+
+    // Dasselbe SQL wie in extension_sql_select ausführen
+    let statement_sql = ast_vec.pop().unwrap().to_string();
+
+    vault
+        .read(move |vault| {
+            vault
+                .readers
+                .with_reader(|conn| explain::profile_query(conn, &statement_sql, &params))
+        })
+        .await
+        .map_err(ExtensionError::from)
+}
+
+/// Aktiviert (Schwellwert in ms) bzw. deaktiviert (`None`) das Slow-Query-Log einer Dev-Extension
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%extension_id, %vault_id))]
+pub fn set_extension_slow_query_log(
+    extension_id: String,
+    threshold_ms: Option<u32>,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    let extension = vault
         .extension_manager
-        .get_extension_by_public_key_and_name(&public_key, &name)?
-        .ok_or_else(|| ExtensionError::NotFound {
-            public_key: public_key.clone(),
-            name: name.clone(),
+        .get_extension(&extension_id)
+        .ok_or_else(|| ExtensionError::ValidationError {
+            reason: format!("Extension with ID {extension_id} not found"),
         })?;
 
-    // Permission check
-    SqlPermissionValidator::validate_sql(&state, &extension.id, sql).await?;
+    if !matches!(extension.source, ExtensionSource::Development { .. }) {
+        return Err(ExtensionError::ValidationError {
+            reason: "Slow query logging is only available for dev extensions".to_string(),
+        });
+    }
 
-    // Parameter validation
-    validate_params(sql, &params)?;
+    vault
+        .slow_queries
+        .set_threshold(&extension_id, threshold_ms)?;
+    Ok(())
+}
 
-    // SQL parsing
-    let mut ast_vec = parse_sql_statements(sql)?;
+#[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(%extension_id, %vault_id),
+)]
+pub fn get_extension_slow_queries(
+    extension_id: String,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<SlowQueryEntry>, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    Ok(vault.slow_queries.entries(&extension_id)?)
+}
 
-    if ast_vec.is_empty() {
-        return Ok(vec![]);
+/// Live-Query einer Extension: gleiche Prüfung wie `extension_sql_select`, Ergebnisse gehen
+/// später nur an die Fenster dieser Extension
+pub async fn subscribe_extension_live_query(
+    vault: &Arc<VaultSession>,
+    sql: &str,
+    params: SqlParams,
+    public_key: &str,
+    name: &str,
+) -> Result<LiveQuerySubscription, ExtensionError> {
+    let (extension_id, mut ast_vec) =
+        validate_extension_select(vault, sql, public_key, name).await?;
+
+    if ast_vec.len() != 1 {
+        return Err(ExtensionError::Database {
+            source: DatabaseError::ExecutionError {
+                sql: sql.to_string(),
+                reason: "Live queries must consist of a single SELECT statement".to_string(),
+                table: None,
+            },
+        });
     }
 
+    let statement = ast_vec.pop().unwrap();
+    let tables = extract_table_names_from_statement(&statement);
+    let statement_sql = statement.to_string();
+
+    vault
+        .read(move |vault| {
+            vault.readers.with_reader(|conn| {
+                vault.live_queries.subscribe(
+                    conn,
+                    statement_sql,
+                    params,
+                    tables,
+                    LiveQueryOwner::Extension(extension_id),
+                )
+            })
+        })
+        .await
+        .map_err(ExtensionError::from)
+}
+
+/// Deklariert einen Suchindex auf einer eigenen Tabelle der Extension
+#[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty, %vault_id, %table),
+)]
+pub async fn extension_declare_search_index(
+    table: String,
+    columns: Vec<String>,
+    public_key: String,
+    name: String,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<SearchIndexInfo, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    validate_own_table(&vault, &table, &public_key, &name)?;
+
+    vault
+        .write(move |vault| {
+            with_connection(&vault.db, |conn| {
+                let tx = conn.transaction()?;
+                let index = fts::declare_search_index(&tx, &table, &columns)?;
+                tx.commit()?;
+                Ok(index)
+            })
+        })
+        .await
+        .map_err(ExtensionError::from)
+}
+
+#[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty, %vault_id, %table),
+)]
+pub async fn extension_drop_search_index(
+    table: String,
+    public_key: String,
+    name: String,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    validate_own_table(&vault, &table, &public_key, &name)?;
+
+    vault
+        .write(move |vault| {
+            with_connection(&vault.db, |conn| {
+                let tx = conn.transaction()?;
+                fts::drop_search_index(&tx, &table)?;
+                tx.commit()?;
+                Ok(())
+            })
+        })
+        .await
+        .map_err(ExtensionError::from)
+}
+
+/// Volltextsuche für Extensions - benötigt Leserechte auf die Tabelle (eigene Tabellen immer)
+#[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty, %vault_id, %table),
+)]
+pub async fn extension_search(
+    table: String,
+    query: String,
+    options: Option<SearchOptions>,
+    public_key: String,
+    name: String,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<SearchHit>, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    let extension = vault
+        .extension_manager
+        .get_active_extension(&public_key, &name)?;
+
+    PermissionManager::check_database_permission(
+        &vault,
+        &extension.id,
+        Action::Database(DbAction::Read),
+        &table,
+    )
+    .await?;
+
+    let options = options.unwrap_or_default();
+    vault
+        .read(move |vault| {
+            vault
+                .readers
+                .with_reader(|conn| fts::search(conn, &table, &query, &options))
+        })
+        .await
+        .map_err(ExtensionError::from)
+}
+
+/// Suchindizes dürfen Extensions nur auf ihren eigenen Tabellen (`{public_key}__{name}__*`) verwalten
+fn validate_own_table(
+    vault: &VaultSession,
+    table: &str,
+    public_key: &str,
+    name: &str,
+) -> Result<(), ExtensionError> {
+    let extension = vault
+        .extension_manager
+        .get_active_extension(public_key, name)?;
+
+    if !table.starts_with(&format!("{public_key}__{name}__")) {
+        return Err(ExtensionError::permission_denied(
+            &extension.id,
+            "manage search index",
+            table,
+        ));
+    }
+    Ok(())
+}
+
+/// Gemeinsame Prüfung lesender Extension-Queries: Extension auflösen, Berechtigung prüfen
+/// und sicherstellen, dass nur SELECTs enthalten sind. Liefert die Extension-ID und die Statements.
+async fn validate_extension_select(
+    vault: &Arc<VaultSession>,
+    sql: &str,
+    public_key: &str,
+    name: &str,
+) -> Result<(String, Vec<Statement>), ExtensionError> {
+    // Get extension to retrieve its ID
+    let extension = vault
+        .extension_manager
+        .get_active_extension(public_key, name)?;
+
+    // Permission check
+    SqlPermissionValidator::validate_sql(vault, &extension.id, sql).await?;
+
+    // SQL parsing
+    let ast_vec = parse_sql_statements(sql)?;
+
     // Validate that all statements are queries
     for stmt in &ast_vec {
         if !matches!(stmt, Statement::Query(_)) {
@@ -273,83 +604,45 @@ pub async fn extension_sql_select(
         }
     }
 
-    // Database operation - return Vec<Vec<JsonValue>> like sql_select_with_crdt
-    with_connection(&state.db, |conn| {
-        let sql_params = ValueConverter::convert_params(&params)?;
-        let stmt_to_execute = ast_vec.pop().unwrap();
-        let transformed_sql = stmt_to_execute.to_string();
-
-        // Prepare and execute query
-        let mut prepared_stmt =
-            conn.prepare(&transformed_sql)
-                .map_err(|e| DatabaseError::ExecutionError {
-                    sql: transformed_sql.clone(),
-                    reason: e.to_string(),
-                    table: None,
-                })?;
-
-        let num_columns = prepared_stmt.column_count();
-        let mut rows = prepared_stmt
-            .query(params_from_iter(sql_params.iter()))
-            .map_err(|e| DatabaseError::QueryError {
-                reason: e.to_string(),
-            })?;
-
-        let mut result_vec: Vec<Vec<JsonValue>> = Vec::new();
-
-        while let Some(row) = rows.next().map_err(|e| DatabaseError::QueryError {
-            reason: e.to_string(),
-        })? {
-            let mut row_values: Vec<JsonValue> = Vec::new();
-            for i in 0..num_columns {
-                let value_ref = row.get_ref(i).map_err(|e| DatabaseError::QueryError {
-                    reason: e.to_string(),
-                })?;
-                let json_value = crate::database::core::convert_value_ref_to_json(value_ref)?;
-                row_values.push(json_value);
-            }
-            result_vec.push(row_values);
-        }
-
-        Ok(result_vec)
-    })
-    .map_err(ExtensionError::from)
+    Ok((extension.id, ast_vec))
 }
 
-/// Validiert Parameter gegen SQL-Platzhalter
-fn validate_params(sql: &str, params: &[JsonValue]) -> Result<(), DatabaseError> {
-    let total_placeholders = count_sql_placeholders(sql);
-
-    if total_placeholders != params.len() {
-        return Err(DatabaseError::ParameterMismatchError {
-            expected: total_placeholders,
-            provided: params.len(),
-            sql: sql.to_string(),
-        });
+/// Protokolliert die Query samt Plan, falls sie das Slow-Query-Log der Extension überschreitet
+async fn record_if_slow(
+    vault: &Arc<VaultSession>,
+    extension_id: &str,
+    sql: &str,
+    elapsed: Duration,
+) {
+    let Ok(Some(threshold)) = vault.slow_queries.threshold(extension_id) else {
+        return;
+    };
+    if elapsed < threshold {
+        return;
     }
 
-    Ok(())
-}
+    let plan_sql = sql.to_string();
+    let plan = vault
+        .read(move |vault| {
+            vault
+                .readers
+                .with_reader(|conn| explain::explain_query_plan(conn, &plan_sql))
+        })
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Could not explain slow query: {e}");
+            Vec::new()
+        });
 
-/// Zählt SQL-Platzhalter (verbesserte Version)
-fn count_sql_placeholders(sql: &str) -> usize {
-    sql.matches('?').count()
-}
+    tracing::warn!(
+        extension_id,
+        sql = %redact::sql(sql),
+        "Slow query ({:.1} ms)\n{}",
+        elapsed.as_secs_f64() * 1000.0,
+        explain::format_plan(&plan)
+    );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_count_sql_placeholders() {
-        assert_eq!(
-            count_sql_placeholders("SELECT * FROM users WHERE id = ?"),
-            1
-        );
-        assert_eq!(
-            count_sql_placeholders("SELECT * FROM users WHERE id = ? AND name = ?"),
-            2
-        );
-        assert_eq!(count_sql_placeholders("SELECT * FROM users"), 0);
+    if let Err(e) = vault.slow_queries.record(extension_id, sql, elapsed, plan) {
+        tracing::warn!("Could not record slow query: {e}");
     }
 }
@@ -2,7 +2,7 @@
 use thiserror::Error;
 use ts_rs::TS;
 
-use crate::database::error::DatabaseError;
+use crate::database::error::{DatabaseError, SerializedDatabaseError};
 
 /// Error codes for frontend handling
 #[derive(Debug, Clone, Copy, PartialEq, Eq, TS)]
@@ -12,20 +12,32 @@ pub enum ExtensionErrorCode {
     NotFound = 1001,
     PermissionDenied = 1002,
     MutexPoisoned = 1003,
+    Disabled = 1004,
     Database = 2000,
     Filesystem = 2001,
     FilesystemWithPath = 2004,
     Http = 2002,
     Web = 2005,
+    Registry = 2006,
     Shell = 2003,
     Manifest = 3000,
     Validation = 3001,
+    Dependency = 3002,
+    DependencyConflict = 3003,
     InvalidPublicKey = 4000,
     InvalidSignature = 4001,
     InvalidActionString = 4004,
     SignatureVerificationFailed = 4002,
     CalculateHash = 4003,
+    UntrustedPublisher = 4005,
     Installation = 5000,
+    ArchiveTooLarge = 5001,
+    ArchiveTooManyFiles = 5002,
+    ArchiveCompressionRatio = 5003,
+    ArchiveSymlink = 5004,
+    ArchiveUnsafePath = 5005,
+    ArchiveDuplicateEntry = 5006,
+    ArchiveFileTypeNotAllowed = 5007,
 }
 
 /// Serialized representation of ExtensionError for TypeScript
@@ -37,6 +49,11 @@ pub struct SerializedExtensionError {
     pub error_type: String,
     pub message: String,
     pub extension_id: Option<String>,
+    /// `DatabaseErrorCode`, wenn `code` = `ExtensionErrorCode::Database`
+    pub database_code: Option<u16>,
+    /// Der `DatabaseError` samt Details (Tabelle, Spalten, Constraint), wenn
+    /// `code` = `ExtensionErrorCode::Database`
+    pub database_error: Option<SerializedDatabaseError<'static>>,
 }
 
 impl serde::Serialize for ExtensionErrorCode {
@@ -56,6 +73,9 @@ pub enum ExtensionError {
     #[error("Extension not found: {name} (public_key: {public_key})")]
     NotFound { public_key: String, name: String },
 
+    #[error("Extension {extension_id} is disabled")]
+    Disabled { extension_id: String },
+
     #[error("Permission denied: {extension_id} cannot {operation} on {resource}")]
     PermissionDenied {
         extension_id: String,
@@ -87,6 +107,9 @@ pub enum ExtensionError {
     #[error("Web request failed: {reason}")]
     WebError { reason: String },
 
+    #[error("Extension registry error: {reason}")]
+    Registry { reason: String },
+
     #[error("Shell command failed: {reason}")]
     Shell {
         reason: String,
@@ -99,6 +122,16 @@ pub enum ExtensionError {
     #[error("Validation error: {reason}")]
     ValidationError { reason: String },
 
+    #[error("Dependency {name} ({requirement}) is not satisfied: {reason}")]
+    DependencyNotSatisfied {
+        name: String,
+        requirement: String,
+        reason: String,
+    },
+
+    #[error("Dependency conflict for {name}: {reason}")]
+    DependencyConflict { name: String, reason: String },
+
     #[error("Invalid Public Key: {reason}")]
     InvalidPublicKey { reason: String },
 
@@ -117,9 +150,37 @@ pub enum ExtensionError {
     #[error("Signature verification failed: {reason}")]
     SignatureVerificationFailed { reason: String },
 
+    #[error("Publisher {public_key} is not allowed: {reason}")]
+    UntrustedPublisher { public_key: String, reason: String },
+
     #[error("Extension installation failed: {reason}")]
     InstallationFailed { reason: String },
 
+    #[error("Archive exceeds the maximum uncompressed size of {limit} bytes")]
+    ArchiveTooLarge { limit: u64 },
+
+    #[error("Archive contains more than {limit} files")]
+    ArchiveTooManyFiles { limit: usize },
+
+    #[error("Suspicious compression ratio {ratio}:1 for '{path}' (limit {limit}:1)")]
+    ArchiveCompressionRatio {
+        path: String,
+        ratio: u64,
+        limit: u64,
+    },
+
+    #[error("Archive entry '{path}' is a symlink")]
+    ArchiveSymlink { path: String },
+
+    #[error("Unsafe archive path '{path}': {reason}")]
+    ArchiveUnsafePath { path: String, reason: String },
+
+    #[error("Duplicate archive entry '{path}'")]
+    ArchiveDuplicateEntry { path: String },
+
+    #[error("File type of '{path}' is not allowed in extensions")]
+    ArchiveFileTypeNotAllowed { path: String },
+
     #[error("A mutex was poisoned: {reason}")]
     MutexPoisoned { reason: String },
 }
@@ -131,20 +192,38 @@ impl ExtensionError {
             ExtensionError::SecurityViolation { .. } => ExtensionErrorCode::SecurityViolation,
             ExtensionError::NotFound { .. } => ExtensionErrorCode::NotFound,
             ExtensionError::PermissionDenied { .. } => ExtensionErrorCode::PermissionDenied,
+            ExtensionError::Disabled { .. } => ExtensionErrorCode::Disabled,
             ExtensionError::Database { .. } => ExtensionErrorCode::Database,
             ExtensionError::Filesystem { .. } => ExtensionErrorCode::Filesystem,
             ExtensionError::FilesystemWithPath { .. } => ExtensionErrorCode::FilesystemWithPath,
             ExtensionError::Http { .. } => ExtensionErrorCode::Http,
             ExtensionError::WebError { .. } => ExtensionErrorCode::Web,
+            ExtensionError::Registry { .. } => ExtensionErrorCode::Registry,
             ExtensionError::Shell { .. } => ExtensionErrorCode::Shell,
             ExtensionError::ManifestError { .. } => ExtensionErrorCode::Manifest,
             ExtensionError::ValidationError { .. } => ExtensionErrorCode::Validation,
+            ExtensionError::DependencyNotSatisfied { .. } => ExtensionErrorCode::Dependency,
+            ExtensionError::DependencyConflict { .. } => ExtensionErrorCode::DependencyConflict,
             ExtensionError::InvalidPublicKey { .. } => ExtensionErrorCode::InvalidPublicKey,
             ExtensionError::InvalidSignature { .. } => ExtensionErrorCode::InvalidSignature,
             ExtensionError::SignatureVerificationFailed { .. } => {
                 ExtensionErrorCode::SignatureVerificationFailed
             }
+            ExtensionError::UntrustedPublisher { .. } => ExtensionErrorCode::UntrustedPublisher,
             ExtensionError::InstallationFailed { .. } => ExtensionErrorCode::Installation,
+            ExtensionError::ArchiveTooLarge { .. } => ExtensionErrorCode::ArchiveTooLarge,
+            ExtensionError::ArchiveTooManyFiles { .. } => ExtensionErrorCode::ArchiveTooManyFiles,
+            ExtensionError::ArchiveCompressionRatio { .. } => {
+                ExtensionErrorCode::ArchiveCompressionRatio
+            }
+            ExtensionError::ArchiveSymlink { .. } => ExtensionErrorCode::ArchiveSymlink,
+            ExtensionError::ArchiveUnsafePath { .. } => ExtensionErrorCode::ArchiveUnsafePath,
+            ExtensionError::ArchiveDuplicateEntry { .. } => {
+                ExtensionErrorCode::ArchiveDuplicateEntry
+            }
+            ExtensionError::ArchiveFileTypeNotAllowed { .. } => {
+                ExtensionErrorCode::ArchiveFileTypeNotAllowed
+            }
             ExtensionError::CalculateHashError { .. } => ExtensionErrorCode::CalculateHash,
             ExtensionError::MutexPoisoned { .. } => ExtensionErrorCode::MutexPoisoned,
             ExtensionError::InvalidActionString { .. } => ExtensionErrorCode::InvalidActionString,
@@ -169,6 +248,7 @@ impl ExtensionError {
     pub fn extension_id(&self) -> Option<&str> {
         match self {
             ExtensionError::PermissionDenied { extension_id, .. } => Some(extension_id),
+            ExtensionError::Disabled { extension_id } => Some(extension_id),
             _ => None,
         }
     }
@@ -189,7 +269,7 @@ impl serde::Serialize for ExtensionError {
     {
         use serde::ser::SerializeStruct;
 
-        let mut state = serializer.serialize_struct("ExtensionError", 4)?;
+        let mut state = serializer.serialize_struct("ExtensionError", 6)?;
 
         state.serialize_field("code", &self.code())?;
         state.serialize_field("type", &format!("{self:?}"))?;
@@ -201,6 +281,17 @@ impl serde::Serialize for ExtensionError {
             state.serialize_field("extension_id", &Option::<String>::None)?;
         }
 
+        match self {
+            ExtensionError::Database { source } => {
+                state.serialize_field("database_code", &Some(source.code()))?;
+                state.serialize_field("database_error", &Some(source))?;
+            }
+            _ => {
+                state.serialize_field("database_code", &Option::<u16>::None)?;
+                state.serialize_field("database_error", &Option::<DatabaseError>::None)?;
+            }
+        }
+
         state.end()
     }
 }
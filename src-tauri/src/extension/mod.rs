@@ -1,11 +1,17 @@
 /// src-tauri/src/extension/mod.rs
 use crate::{
     extension::{
-        core::{manager::ExtensionManager, EditablePermissions, ExtensionInfoResponse, ExtensionPreview},
+        core::{
+            manager::ExtensionManager,
+            progress::InstallProgressReporter,
+            trust::{self, TrustLevel, TrustedPublisher},
+            EditablePermissions, ExtensionInfoResponse, ExtensionPreview,
+        },
         error::ExtensionError,
     },
     AppState,
 };
+use std::path::PathBuf;
 use tauri::{AppHandle, State};
 pub mod core;
 pub mod crypto;
@@ -13,18 +19,27 @@ pub mod database;
 pub mod error;
 pub mod filesystem;
 pub mod permissions;
+pub mod registry;
 pub mod web;
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub mod webview;
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty, %vault_id),
+)]
 pub fn get_extension_info(
     public_key: String,
     name: String,
+    vault_id: String,
     state: State<AppState>,
 ) -> Result<ExtensionInfoResponse, ExtensionError> {
-    let extension = state
+    let vault = state.vaults.get(&vault_id)?;
+    let extension = vault
         .extension_manager
         .get_extension_by_public_key_and_name(&public_key, &name)?
         .ok_or_else(|| ExtensionError::NotFound {
@@ -36,25 +51,28 @@ pub fn get_extension_info(
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"), fields(%vault_id))]
 pub async fn get_all_extensions(
     app_handle: AppHandle,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<ExtensionInfoResponse>, String> {
+    let vault = state.vaults.get(&vault_id).map_err(|e| e.to_string())?;
     // Check if extensions are loaded, if not load them first
     /*  let needs_loading = {
-        let prod_exts = state
+        let prod_exts = vault
             .extension_manager
             .production_extensions
             .lock()
             .unwrap();
-        let dev_exts = state.extension_manager.dev_extensions.lock().unwrap();
+        let dev_exts = vault.extension_manager.dev_extensions.lock().unwrap();
         prod_exts.is_empty() && dev_exts.is_empty()
     }; */
 
     /* if needs_loading { */
-    state
+    vault
         .extension_manager
-        .load_installed_extensions(&app_handle, &state)
+        .load_installed_extensions(&app_handle, &vault)
         .await
         .map_err(|e| format!("Failed to load extensions: {e:?}"))?;
     /* } */
@@ -63,7 +81,7 @@ pub async fn get_all_extensions(
 
     // Production Extensions
     {
-        let prod_exts = state
+        let prod_exts = vault
             .extension_manager
             .production_extensions
             .lock()
@@ -75,7 +93,7 @@ pub async fn get_all_extensions(
 
     // Dev Extensions
     {
-        let dev_exts = state.extension_manager.dev_extensions.lock().unwrap();
+        let dev_exts = vault.extension_manager.dev_extensions.lock().unwrap();
         for ext in dev_exts.values() {
             extensions.push(ExtensionInfoResponse::from_extension(ext)?);
         }
@@ -85,31 +103,88 @@ pub async fn get_all_extensions(
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"))]
 pub async fn preview_extension(
     app_handle: AppHandle,
-    state: State<'_, AppState>,
     file_bytes: Vec<u8>,
+    vault_id: Option<String>,
+    state: State<'_, AppState>,
 ) -> Result<ExtensionPreview, ExtensionError> {
-    state
-        .extension_manager
-        .preview_extension_internal(&app_handle, file_bytes)
-        .await
+    // Mit geöffnetem Vault wird erkannt, ob es sich um ein Update handelt
+    let vault = match vault_id {
+        Some(vault_id) => Some(state.vaults.get(&vault_id)?),
+        None => None,
+    };
+    ExtensionManager::preview_extension_internal(&app_handle, file_bytes, vault.as_ref()).await
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_id))]
 pub async fn install_extension_with_permissions(
     app_handle: AppHandle,
     file_bytes: Vec<u8>,
     custom_permissions: EditablePermissions,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<String, ExtensionError> {
-    state
+    let vault = state.vaults.get(&vault_id)?;
+    vault
         .extension_manager
         .install_extension_with_permissions_internal(
             app_handle,
             file_bytes,
             custom_permissions,
-            &state,
+            &vault,
+        )
+        .await
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"))]
+pub async fn preview_extension_from_path(
+    app_handle: AppHandle,
+    file_path: String,
+    vault_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ExtensionPreview, ExtensionError> {
+    let vault = match vault_id {
+        Some(vault_id) => Some(state.vaults.get(&vault_id)?),
+        None => None,
+    };
+    ExtensionManager::preview_extension_from_path_internal(
+        &app_handle,
+        &PathBuf::from(file_path),
+        vault.as_ref(),
+    )
+    .await
+}
+
+/// Installiert ein Paket direkt aus der gewählten Datei, statt die Bytes über IPC zu schicken.
+/// Mit `install_id` wird der Fortschritt als `extension-install-progress` gemeldet.
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_id))]
+pub async fn install_extension_from_path(
+    app_handle: AppHandle,
+    file_path: String,
+    custom_permissions: EditablePermissions,
+    install_id: Option<String>,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    let progress = match install_id {
+        Some(install_id) => InstallProgressReporter::new(&app_handle, install_id),
+        None => InstallProgressReporter::silent(),
+    };
+
+    vault
+        .extension_manager
+        .install_extension_from_path_internal(
+            app_handle,
+            &PathBuf::from(file_path),
+            custom_permissions,
+            progress,
+            &vault,
         )
         .await
 }
@@ -189,27 +264,136 @@ pub async fn install_extension(
 }
  */
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty, %vault_id),
+)]
 pub async fn remove_extension(
     app_handle: AppHandle,
     public_key: String,
     name: String,
     version: String,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<(), ExtensionError> {
-    state
+    let vault = state.vaults.get(&vault_id)?;
+    vault
         .extension_manager
-        .remove_extension_internal(&app_handle, &public_key, &name, &version, &state)
+        .remove_extension_internal(&app_handle, &public_key, &name, &version, &vault)
         .await
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty, %vault_id),
+)]
+pub async fn rollback_extension(
+    app_handle: AppHandle,
+    public_key: String,
+    name: String,
+    version: Option<String>,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    vault
+        .extension_manager
+        .rollback_extension_internal(&app_handle, &public_key, &name, version.as_deref(), &vault)
+        .await
+}
+
+/// Aktiviert oder deaktiviert eine Extension. Beim Deaktivieren werden ihre nativen Fenster
+/// sofort geschlossen; iframes schließt das Frontend.
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%extension_id, %vault_id))]
+pub async fn set_extension_enabled(
+    app_handle: AppHandle,
+    extension_id: String,
+    enabled: bool,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    vault
+        .extension_manager
+        .set_extension_enabled_internal(&extension_id, enabled, &vault)
+        .await?;
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    if !enabled {
+        state
+            .extension_webview_manager
+            .close_windows_for_extension(&app_handle, &extension_id);
+    }
+    #[cfg(any(target_os = "android", target_os = "ios"))]
+    let _ = &app_handle;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"))]
+pub fn get_extension_versions(
+    app_handle: AppHandle,
+    public_key: String,
+    name: String,
+) -> Result<Vec<String>, ExtensionError> {
+    ExtensionManager::list_installed_versions(&app_handle, &public_key, &name)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"), fields(%vault_id))]
+pub async fn get_extension_publishers(
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<TrustedPublisher>, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    trust::list_publishers(&vault).await
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%public_key, %vault_id))]
+pub async fn set_extension_publisher(
+    public_key: String,
+    display_name: Option<String>,
+    trust_level: TrustLevel,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<TrustedPublisher, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    trust::set_publisher(&vault, &public_key, display_name.as_deref(), trust_level).await
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%public_key, %vault_id))]
+pub async fn remove_extension_publisher(
+    public_key: String,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    trust::remove_publisher(&vault, &public_key).await
+}
+
+#[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty, %vault_id),
+)]
 pub fn is_extension_installed(
     public_key: String,
     name: String,
     extension_version: String,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<bool, ExtensionError> {
-    if let Some(ext) = state
+    let vault = state.vaults.get(&vault_id)?;
+    if let Some(ext) = vault
         .extension_manager
         .get_extension_by_public_key_and_name(&public_key, &name)?
     {
@@ -278,8 +462,10 @@ async fn check_dev_server_health(url: &str) -> bool {
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%vault_id))]
 pub async fn load_dev_extension(
     extension_path: String,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<String, ExtensionError> {
     use crate::extension::core::{
@@ -289,6 +475,8 @@ pub async fn load_dev_extension(
     use std::path::PathBuf;
     use std::time::SystemTime;
 
+    let vault = state.vaults.get(&vault_id)?;
+
     let extension_path_buf = PathBuf::from(&extension_path);
 
     // 1. Read haextension.config.json to get dev server config and haextension directory
@@ -311,8 +499,7 @@ pub async fn load_dev_extension(
     };
 
     let dev_server_url = format!("http://{host}:{port}");
-    eprintln!("📡 Dev server URL: {dev_server_url}");
-    eprintln!("📁 Haextension directory: {haextension_dir}");
+    tracing::debug!(haextension_dir, "Dev server URL: {dev_server_url}");
 
     // 1.5. Check if dev server is running
     if !check_dev_server_health(&dev_server_url).await {
@@ -322,7 +509,7 @@ pub async fn load_dev_extension(
             ),
         });
     }
-    eprintln!("✅ Dev server is reachable");
+    tracing::debug!("Dev server is reachable");
 
     // 2. Validate and build path to manifest: <extension_path>/<haextension_dir>/manifest.json
     let manifest_relative_path = format!("{haextension_dir}/manifest.json");
@@ -349,13 +536,13 @@ pub async fn load_dev_extension(
     let extension_id = format!("dev_{}_{}", manifest.public_key, manifest.name);
 
     // 5. Check if dev extension already exists (allow reload)
-    if let Some(existing) = state
+    if let Some(existing) = vault
         .extension_manager
         .get_extension_by_public_key_and_name(&manifest.public_key, &manifest.name)?
     {
         // If it's already a dev extension, remove it first (to allow reload)
         if let ExtensionSource::Development { .. } = &existing.source {
-            state
+            vault
                 .extension_manager
                 .remove_extension(&manifest.public_key, &manifest.name)?;
         }
@@ -377,10 +564,10 @@ pub async fn load_dev_extension(
     };
 
     // 7. Add to dev extensions (no database entry for dev extensions)
-    state.extension_manager.add_dev_extension(extension)?;
+    vault.extension_manager.add_dev_extension(extension)?;
 
-    eprintln!(
-        "✅ Dev extension loaded: {} v{} ({})",
+    tracing::info!(
+        "Dev extension loaded: {} v{} ({})",
         manifest.name, manifest.version, dev_server_url
     );
 
@@ -388,13 +575,20 @@ pub async fn load_dev_extension(
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty, %vault_id),
+)]
 pub fn remove_dev_extension(
     public_key: String,
     name: String,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<(), ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
     // Only remove from dev_extensions, not production_extensions
-    let mut dev_exts = state.extension_manager.dev_extensions.lock().map_err(|e| {
+    let mut dev_exts = vault.extension_manager.dev_extensions.lock().map_err(|e| {
         ExtensionError::MutexPoisoned {
             reason: e.to_string(),
         }
@@ -408,7 +602,7 @@ pub fn remove_dev_extension(
 
     if let Some(id) = to_remove {
         dev_exts.remove(&id);
-        eprintln!("✅ Dev extension removed: {name}");
+        tracing::info!("Dev extension removed: {name}");
         Ok(())
     } else {
         Err(ExtensionError::NotFound { public_key, name })
@@ -416,10 +610,13 @@ pub fn remove_dev_extension(
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"), fields(%vault_id))]
 pub fn get_all_dev_extensions(
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<ExtensionInfoResponse>, ExtensionError> {
-    let dev_exts = state.extension_manager.dev_extensions.lock().map_err(|e| {
+    let vault = state.vaults.get(&vault_id)?;
+    let dev_exts = vault.extension_manager.dev_extensions.lock().map_err(|e| {
         ExtensionError::MutexPoisoned {
             reason: e.to_string(),
         }
@@ -439,8 +636,10 @@ pub fn get_all_dev_extensions(
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 #[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%extension_id, %vault_id))]
 pub fn open_extension_webview_window(
     app_handle: AppHandle,
+    vault_id: String,
     state: State<'_, AppState>,
     extension_id: String,
     title: String,
@@ -449,11 +648,12 @@ pub fn open_extension_webview_window(
     x: Option<f64>,
     y: Option<f64>,
 ) -> Result<String, ExtensionError> {
-    eprintln!("[open_extension_webview_window] Received extension_id: {}", extension_id);
+    let vault = state.vaults.get(&vault_id)?;
     // Returns the window_id (generated UUID without dashes)
     state.extension_webview_manager.open_extension_window(
         &app_handle,
-        &state.extension_manager,
+        &vault.extension_manager,
+        vault_id,
         extension_id,
         title,
         width,
@@ -465,6 +665,7 @@ pub fn open_extension_webview_window(
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 #[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%window_id))]
 pub fn close_extension_webview_window(
     app_handle: AppHandle,
     state: State<'_, AppState>,
@@ -477,6 +678,7 @@ pub fn close_extension_webview_window(
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 #[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"), fields(%window_id))]
 pub fn focus_extension_webview_window(
     app_handle: AppHandle,
     state: State<'_, AppState>,
@@ -489,6 +691,7 @@ pub fn focus_extension_webview_window(
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 #[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"), fields(%window_id))]
 pub fn update_extension_webview_window_position(
     app_handle: AppHandle,
     state: State<'_, AppState>,
@@ -503,6 +706,7 @@ pub fn update_extension_webview_window_position(
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 #[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"), fields(%window_id))]
 pub fn update_extension_webview_window_size(
     app_handle: AppHandle,
     state: State<'_, AppState>,
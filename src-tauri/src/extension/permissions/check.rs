@@ -7,21 +7,37 @@ use std::path::Path;
 use tauri::State;
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(%extension_id, %vault_id),
+)]
 pub async fn check_web_permission(
     extension_id: String,
     url: String,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<(), ExtensionError> {
-    PermissionManager::check_web_permission(&state, &extension_id, &url).await
+    let vault = state.vaults.get(&vault_id)?;
+    PermissionManager::check_web_permission(&vault, &extension_id, &url).await
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(%extension_id, %vault_id),
+)]
 pub async fn check_database_permission(
     extension_id: String,
     resource: String,
     operation: String,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<(), ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
     let action = match operation.as_str() {
         "read" => crate::extension::permissions::types::Action::Database(
             crate::extension::permissions::types::DbAction::Read,
@@ -36,16 +52,24 @@ pub async fn check_database_permission(
         }
     };
 
-    PermissionManager::check_database_permission(&state, &extension_id, action, &resource).await
+    PermissionManager::check_database_permission(&vault, &extension_id, action, &resource).await
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(%extension_id, %vault_id),
+)]
 pub async fn check_filesystem_permission(
     extension_id: String,
     path: String,
     operation: String,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<(), ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
     let action = match operation.as_str() {
         "read" => crate::extension::permissions::types::Action::Filesystem(
             crate::extension::permissions::types::FsAction::Read,
@@ -61,5 +85,5 @@ pub async fn check_filesystem_permission(
     };
 
     let file_path = Path::new(&path);
-    PermissionManager::check_filesystem_permission(&state, &extension_id, action, file_path).await
+    PermissionManager::check_filesystem_permission(&vault, &extension_id, action, file_path).await
 }
@@ -1,161 +1,190 @@
 use crate::table_names::TABLE_EXTENSION_PERMISSIONS;
-use crate::AppState;
+use crate::database::vault::VaultSession;
 use crate::database::core::with_connection;
 use crate::database::error::DatabaseError;
+use crate::extension::core::dependencies;
 use crate::extension::core::types::ExtensionSource;
 use crate::extension::database::executor::SqlExecutor;
 use crate::extension::error::ExtensionError;
 use crate::extension::permissions::types::{Action, ExtensionPermission, PermissionConstraints, PermissionStatus, ResourceType};
-use tauri::State;
 use crate::database::generated::HaexExtensionPermissions;
 use rusqlite::params;
 use std::path::Path;
+use std::sync::Arc;
 
 pub struct PermissionManager;
 
 impl PermissionManager {
     /// Speichert alle Permissions einer Extension
     pub async fn save_permissions(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         permissions: &[ExtensionPermission],
     ) -> Result<(), ExtensionError> {
-        with_connection(&app_state.db, |conn| {
-            let tx = conn.transaction().map_err(DatabaseError::from)?;
-
-            let hlc_service = app_state
-                .hlc
-                .lock()
-                .map_err(|_| DatabaseError::MutexPoisoned {
-                    reason: "Failed to lock HLC service".to_string(),
-                })?;
-
-            let sql = format!(
-                "INSERT INTO {TABLE_EXTENSION_PERMISSIONS} (id, extension_id, resource_type, action, target, constraints, status) VALUES (?, ?, ?, ?, ?, ?, ?)"
-            );
-
-            for perm in permissions {
-                // 1. Konvertiere App-Struct zu DB-Struct
-                let db_perm: HaexExtensionPermissions = perm.into();
-
-                // 2. Erstelle typsichere Parameter
-                let params = params![
-                    db_perm.id,
-                    db_perm.extension_id,
-                    db_perm.resource_type,
-                    db_perm.action,
-                    db_perm.target,
-                    db_perm.constraints,
-                    db_perm.status,
-                ];
-
-                // 3. Führe mit dem typsicheren Executor aus
-                // HINWEIS: Du musst eine `execute_internal_typed` Funktion erstellen!
-                SqlExecutor::execute_internal_typed(&tx, &hlc_service, &sql, params)?;
-            }
+        let permissions = permissions.to_vec();
+
+        vault
+            .write(move |vault| {
+                with_connection(&vault.db, |conn| {
+                    let tx = conn.transaction().map_err(DatabaseError::from)?;
+
+                    let hlc_service =
+                        vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                            reason: "Failed to lock HLC service".to_string(),
+                        })?;
+
+                    let sql = format!(
+                        "INSERT INTO {TABLE_EXTENSION_PERMISSIONS} (id, extension_id, resource_type, action, target, constraints, status) VALUES (?, ?, ?, ?, ?, ?, ?)"
+                    );
+
+                    for perm in &permissions {
+                        // 1. Konvertiere App-Struct zu DB-Struct
+                        let db_perm: HaexExtensionPermissions = perm.into();
+
+                        // 2. Erstelle typsichere Parameter
+                        let params = params![
+                            db_perm.id,
+                            db_perm.extension_id,
+                            db_perm.resource_type,
+                            db_perm.action,
+                            db_perm.target,
+                            db_perm.constraints,
+                            db_perm.status,
+                        ];
+
+                        // 3. Führe mit dem typsicheren Executor aus
+                        SqlExecutor::execute_internal_typed(&tx, &hlc_service, &sql, params)?;
+                    }
 
-            tx.commit().map_err(DatabaseError::from)?;
-            Ok(())
-        })
-        .map_err(ExtensionError::from)
+                    tx.commit().map_err(DatabaseError::from)?;
+                    Ok(())
+                })
+            })
+            .await
+            .map_err(ExtensionError::from)
     }
 
     /// Aktualisiert eine Permission
     pub async fn update_permission(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         permission: &ExtensionPermission,
     ) -> Result<(), ExtensionError> {
-        with_connection(&app_state.db, |conn| {
-            let tx = conn.transaction().map_err(DatabaseError::from)?;
-
-            let hlc_service = app_state
-                .hlc
-                .lock()
-                .map_err(|_| DatabaseError::MutexPoisoned {
-                    reason: "Failed to lock HLC service".to_string(),
-                })?;
-
-            let db_perm: HaexExtensionPermissions = permission.into();
-            
-            let sql = format!(
-                "UPDATE {TABLE_EXTENSION_PERMISSIONS} SET resource_type = ?, action = ?, target = ?, constraints = ?, status = ? WHERE id = ?"
-            );
-
-            let params = params![
-                db_perm.resource_type,
-                db_perm.action,
-                db_perm.target,
-                db_perm.constraints,
-                db_perm.status,
-                db_perm.id,
-            ];
-
-            SqlExecutor::execute_internal_typed(&tx, &hlc_service, &sql, params)?;
-            tx.commit().map_err(DatabaseError::from)
-        })
-        .map_err(ExtensionError::from)
+        let db_perm: HaexExtensionPermissions = permission.into();
+
+        vault
+            .write(move |vault| {
+                with_connection(&vault.db, |conn| {
+                    let tx = conn.transaction().map_err(DatabaseError::from)?;
+
+                    let hlc_service =
+                        vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                            reason: "Failed to lock HLC service".to_string(),
+                        })?;
+
+                    let sql = format!(
+                        "UPDATE {TABLE_EXTENSION_PERMISSIONS} SET resource_type = ?, action = ?, target = ?, constraints = ?, status = ? WHERE id = ?"
+                    );
+
+                    let params = params![
+                        db_perm.resource_type,
+                        db_perm.action,
+                        db_perm.target,
+                        db_perm.constraints,
+                        db_perm.status,
+                        db_perm.id,
+                    ];
+
+                    SqlExecutor::execute_internal_typed(&tx, &hlc_service, &sql, params)?;
+                    tx.commit().map_err(DatabaseError::from)
+                })
+            })
+            .await
+            .map_err(ExtensionError::from)
     }
 
     /// Ändert den Status einer Permission
     pub async fn update_permission_status(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         permission_id: &str,
         new_status: PermissionStatus,
     ) -> Result<(), ExtensionError> {
-        with_connection(&app_state.db, |conn| {
-            let tx = conn.transaction().map_err(DatabaseError::from)?;
-
-            let hlc_service = app_state
-                .hlc
-                .lock()
-                .map_err(|_| DatabaseError::MutexPoisoned {
-                    reason: "Failed to lock HLC service".to_string(),
-                })?;
-
-            let sql = format!("UPDATE {TABLE_EXTENSION_PERMISSIONS} SET status = ? WHERE id = ?");
-            let params = params![new_status.as_str(), permission_id];
-            SqlExecutor::execute_internal_typed(&tx, &hlc_service, &sql, params)?;
-            tx.commit().map_err(DatabaseError::from)
-        })
-        .map_err(ExtensionError::from)
+        let permission_id = permission_id.to_string();
+
+        vault
+            .write(move |vault| {
+                with_connection(&vault.db, |conn| {
+                    let tx = conn.transaction().map_err(DatabaseError::from)?;
+
+                    let hlc_service =
+                        vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                            reason: "Failed to lock HLC service".to_string(),
+                        })?;
+
+                    let sql =
+                        format!("UPDATE {TABLE_EXTENSION_PERMISSIONS} SET status = ? WHERE id = ?");
+                    let params = params![new_status.as_str(), permission_id];
+                    SqlExecutor::execute_internal_typed(&tx, &hlc_service, &sql, params)?;
+                    tx.commit().map_err(DatabaseError::from)
+                })
+            })
+            .await
+            .map_err(ExtensionError::from)
     }
 
     /// Löscht alle Permissions einer Extension
-   pub async fn delete_permission(
-        app_state: &State<'_, AppState>,
+    pub async fn delete_permission(
+        vault: &Arc<VaultSession>,
         permission_id: &str,
     ) -> Result<(), ExtensionError> {
-        with_connection(&app_state.db, |conn| {
-            let tx = conn.transaction().map_err(DatabaseError::from)?;
-            
-            let hlc_service = app_state.hlc.lock()
-                .map_err(|_| DatabaseError::MutexPoisoned {
-                    reason: "Failed to lock HLC service".to_string(),
-                })?;
-            
-            // Echtes DELETE - wird vom CrdtTransformer zu UPDATE umgewandelt
-            let sql = format!("DELETE FROM {TABLE_EXTENSION_PERMISSIONS} WHERE id = ?");
-            SqlExecutor::execute_internal_typed(&tx, &hlc_service, &sql, params![permission_id])?;
-            tx.commit().map_err(DatabaseError::from)
-        }).map_err(ExtensionError::from)
+        let permission_id = permission_id.to_string();
+
+        vault
+            .write(move |vault| {
+                with_connection(&vault.db, |conn| {
+                    let tx = conn.transaction().map_err(DatabaseError::from)?;
+
+                    let hlc_service =
+                        vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                            reason: "Failed to lock HLC service".to_string(),
+                        })?;
+
+                    // Echtes DELETE - wird vom CrdtTransformer zu UPDATE umgewandelt
+                    let sql = format!("DELETE FROM {TABLE_EXTENSION_PERMISSIONS} WHERE id = ?");
+                    SqlExecutor::execute_internal_typed(
+                        &tx,
+                        &hlc_service,
+                        &sql,
+                        params![permission_id],
+                    )?;
+                    tx.commit().map_err(DatabaseError::from)
+                })
+            })
+            .await
+            .map_err(ExtensionError::from)
     }
-    
+
     /// Löscht alle Permissions einer Extension (Soft-Delete)
     pub async fn delete_permissions(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
     ) -> Result<(), ExtensionError> {
-        with_connection(&app_state.db, |conn| {
-            let tx = conn.transaction().map_err(DatabaseError::from)?;
-
-            let hlc_service = app_state.hlc.lock()
-                .map_err(|_| DatabaseError::MutexPoisoned {
-                    reason: "Failed to lock HLC service".to_string(),
-                })?;
-
-             let sql = format!("DELETE FROM {TABLE_EXTENSION_PERMISSIONS} WHERE extension_id = ?");
-            SqlExecutor::execute_internal_typed(&tx, &hlc_service, &sql, params![extension_id])?;
-            tx.commit().map_err(DatabaseError::from)
-        }).map_err(ExtensionError::from)
+        let extension_id = extension_id.to_string();
+
+        vault
+            .write(move |vault| {
+                with_connection(&vault.db, |conn| {
+                    let tx = conn.transaction().map_err(DatabaseError::from)?;
+
+                    let hlc_service =
+                        vault.hlc.lock().map_err(|_| DatabaseError::MutexPoisoned {
+                            reason: "Failed to lock HLC service".to_string(),
+                        })?;
+
+                    Self::delete_permissions_in_transaction(&tx, &hlc_service, &extension_id)?;
+                    tx.commit().map_err(DatabaseError::from)
+                })
+            })
+            .await
+            .map_err(ExtensionError::from)
     }
 
     /// Löscht alle Permissions einer Extension innerhalb einer bestehenden Transaktion
@@ -168,35 +197,45 @@ impl PermissionManager {
         SqlExecutor::execute_internal_typed(tx, hlc_service, &sql, params![extension_id])?;
         Ok(())
     }
-    /// Lädt alle Permissions einer Extension
+
+    /// Lädt alle Permissions einer Extension. Liest über den Reader-Pool, damit
+    /// Permission-Checks auf dem Select-Pfad nicht auf den Writer warten
     pub async fn get_permissions(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
     ) -> Result<Vec<ExtensionPermission>, ExtensionError> {
-        with_connection(&app_state.db, |conn| {
-             let sql = format!("SELECT * FROM {TABLE_EXTENSION_PERMISSIONS} WHERE extension_id = ?");
-            let mut stmt = conn.prepare(&sql).map_err(DatabaseError::from)?;
-            
-            let perms_iter = stmt.query_map(params![extension_id], |row| {
-                HaexExtensionPermissions::from_row(row)
-            })?;
-            
-            let permissions = perms_iter
-                .filter_map(Result::ok) 
-                .map(Into::into) 
-                .collect();
-            
-            Ok(permissions)
-        }).map_err(ExtensionError::from)
+        let extension_id = extension_id.to_string();
+
+        vault
+            .read(move |vault| {
+                vault.readers.with_reader(|conn| {
+                    let sql = format!(
+                        "SELECT * FROM {TABLE_EXTENSION_PERMISSIONS} WHERE extension_id = ?"
+                    );
+                    let mut stmt = conn.prepare(&sql).map_err(DatabaseError::from)?;
+
+                    let perms_iter = stmt.query_map(params![extension_id], |row| {
+                        HaexExtensionPermissions::from_row(row)
+                    })?;
+
+                    let permissions = perms_iter.filter_map(Result::ok).map(Into::into).collect();
+
+                    Ok(permissions)
+                })
+            })
+            .await
+            .map_err(ExtensionError::from)
     }
 
     /// Prüft Datenbankberechtigungen
    pub async fn check_database_permission(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         action: Action,
         table_name: &str,
     ) -> Result<(), ExtensionError> {
+        vault.extension_manager.ensure_enabled(extension_id)?;
+
         // Remove quotes from table name if present (from SDK's getTableName())
         // Support both double quotes and backticks (Drizzle uses backticks by default)
         let clean_table_name = table_name.trim_matches('"').trim_matches('`');
@@ -206,7 +245,7 @@ impl PermissionManager {
         // Extension ID format: dev_{publicKey}_{extensionName} or {publicKey}_{extensionName}
 
         // Get the extension to check if this is its own table
-        let extension = app_state
+        let extension = vault
             .extension_manager
             .get_extension(extension_id)
             .ok_or_else(|| ExtensionError::ValidationError {
@@ -221,8 +260,21 @@ impl PermissionManager {
             return Ok(());
         }
 
+        // Von Abhängigkeiten exportierte Tabellen sind ohne eigene Freigabe zugänglich
+        if let Action::Database(db_action) = &action {
+            if dependencies::exported_table_access(
+                &vault.extension_manager,
+                &extension,
+                clean_table_name,
+            )?
+            .is_some_and(|access| access.allows(db_action))
+            {
+                return Ok(());
+            }
+        }
+
         // Not own table - check explicit permissions
-        let permissions = Self::get_permissions(app_state, extension_id).await?;
+        let permissions = Self::get_permissions(vault, extension_id).await?;
 
         let has_permission = permissions
             .iter()
@@ -250,12 +302,14 @@ impl PermissionManager {
     /// Prüft Web-Berechtigungen für Requests
     /// Method/operation is not checked - only protocol, domain, port, and path
     pub async fn check_web_permission(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         url: &str,
     ) -> Result<(), ExtensionError> {
+        vault.extension_manager.ensure_enabled(extension_id)?;
+
         // Load permissions - for dev extensions, get from manifest; for production, from database
-        let permissions = if let Some(extension) = app_state.extension_manager.get_extension(extension_id) {
+        let permissions = if let Some(extension) = vault.extension_manager.get_extension(extension_id) {
             match &extension.source {
                 ExtensionSource::Development { .. } => {
                     // Dev extension - get web permissions from manifest
@@ -272,23 +326,28 @@ impl PermissionManager {
                 }
                 ExtensionSource::Production { .. } => {
                     // Production extension - load from database
-                    with_connection(&app_state.db, |conn| {
-                        let sql = format!(
-                            "SELECT * FROM {TABLE_EXTENSION_PERMISSIONS} WHERE extension_id = ? AND resource_type = 'web'"
-                        );
-                        let mut stmt = conn.prepare(&sql).map_err(DatabaseError::from)?;
-
-                        let perms_iter = stmt.query_map(params![extension_id], |row| {
-                            crate::database::generated::HaexExtensionPermissions::from_row(row)
-                        })?;
-
-                        let permissions: Vec<ExtensionPermission> = perms_iter
-                            .filter_map(Result::ok)
-                            .map(Into::into)
-                            .collect();
-
-                        Ok(permissions)
-                    })?
+                    let owner_id = extension_id.to_string();
+                    vault
+                        .read(move |vault| {
+                            vault.readers.with_reader(|conn| {
+                                let sql = format!(
+                                    "SELECT * FROM {TABLE_EXTENSION_PERMISSIONS} WHERE extension_id = ? AND resource_type = 'web'"
+                                );
+                                let mut stmt = conn.prepare(&sql).map_err(DatabaseError::from)?;
+
+                                let perms_iter = stmt.query_map(params![owner_id], |row| {
+                                    HaexExtensionPermissions::from_row(row)
+                                })?;
+
+                                let permissions: Vec<ExtensionPermission> = perms_iter
+                                    .filter_map(Result::ok)
+                                    .map(Into::into)
+                                    .collect();
+
+                                Ok(permissions)
+                            })
+                        })
+                        .await?
                 }
             }
         } else {
@@ -339,12 +398,14 @@ impl PermissionManager {
 
     /// Prüft Dateisystem-Berechtigungen
     pub async fn check_filesystem_permission(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         action: Action,
         file_path: &Path,
     ) -> Result<(), ExtensionError> {
-        let permissions = Self::get_permissions(app_state, extension_id).await?;
+        vault.extension_manager.ensure_enabled(extension_id)?;
+
+        let permissions = Self::get_permissions(vault, extension_id).await?;
 
         let file_path_str = file_path.to_string_lossy();
 
@@ -387,12 +448,14 @@ impl PermissionManager {
 
     /// Prüft Shell-Berechtigungen
     pub async fn check_shell_permission(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         command: &str,
         args: &[String],
     ) -> Result<(), ExtensionError> {
-        let permissions = Self::get_permissions(app_state, extension_id).await?;
+        vault.extension_manager.ensure_enabled(extension_id)?;
+
+        let permissions = Self::get_permissions(vault, extension_id).await?;
 
         let has_permission = permissions
             .iter()
@@ -599,88 +662,88 @@ impl PermissionManager {
 /* impl PermissionManager {
     // Convenience-Methoden
     pub async fn can_read_file(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         file_path: &Path,
     ) -> Result<(), ExtensionError> {
-        Self::check_filesystem_permission(app_state, extension_id, Action::Read, file_path).await
+        Self::check_filesystem_permission(vault, extension_id, Action::Read, file_path).await
     }
 
     pub async fn can_write_file(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         file_path: &Path,
     ) -> Result<(), ExtensionError> {
-        Self::check_filesystem_permission(app_state, extension_id, Action::Write, file_path).await
+        Self::check_filesystem_permission(vault, extension_id, Action::Write, file_path).await
     }
 
     pub async fn can_read_table(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         table_name: &str,
     ) -> Result<(), ExtensionError> {
-        Self::check_database_permission(app_state, extension_id, Action::Read, table_name).await
+        Self::check_database_permission(vault, extension_id, Action::Read, table_name).await
     }
 
     pub async fn can_write_table(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         table_name: &str,
     ) -> Result<(), ExtensionError> {
-        Self::check_database_permission(app_state, extension_id, Action::Write, table_name).await
+        Self::check_database_permission(vault, extension_id, Action::Write, table_name).await
     }
 
     pub async fn can_http_get(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         url: &str,
     ) -> Result<(), ExtensionError> {
-        Self::check_http_permission(app_state, extension_id, "GET", url).await
+        Self::check_http_permission(vault, extension_id, "GET", url).await
     }
 
     pub async fn can_http_post(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         url: &str,
     ) -> Result<(), ExtensionError> {
-        Self::check_http_permission(app_state, extension_id, "POST", url).await
+        Self::check_http_permission(vault, extension_id, "POST", url).await
     }
 
     pub async fn can_execute_command(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         command: &str,
         args: &[String],
     ) -> Result<(), ExtensionError> {
-        Self::check_shell_permission(app_state, extension_id, command, args).await
+        Self::check_shell_permission(vault, extension_id, command, args).await
     }
 
     pub async fn grant_permission(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         permission_id: &str,
     ) -> Result<(), ExtensionError> {
-        Self::update_permission_status(app_state, permission_id, PermissionStatus::Granted).await
+        Self::update_permission_status(vault, permission_id, PermissionStatus::Granted).await
     }
 
     pub async fn deny_permission(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         permission_id: &str,
     ) -> Result<(), ExtensionError> {
-        Self::update_permission_status(app_state, permission_id, PermissionStatus::Denied).await
+        Self::update_permission_status(vault, permission_id, PermissionStatus::Denied).await
     }
 
     pub async fn ask_permission(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         permission_id: &str,
     ) -> Result<(), ExtensionError> {
-        Self::update_permission_status(app_state, permission_id, PermissionStatus::Ask).await
+        Self::update_permission_status(vault, permission_id, PermissionStatus::Ask).await
     }
 
     pub async fn get_ask_permissions(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
     ) -> Result<Vec<ExtensionPermission>, ExtensionError> {
-        let all_permissions = Self::get_permissions(app_state, extension_id).await?;
+        let all_permissions = Self::get_permissions(vault, extension_id).await?;
         Ok(all_permissions
             .into_iter()
             .filter(|perm| perm.status == PermissionStatus::Ask)
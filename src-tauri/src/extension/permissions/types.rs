@@ -168,6 +168,16 @@ pub struct ExtensionPermission {
     pub haex_timestamp: Option<String>,
 }
 
+impl ExtensionPermission {
+    /// Ob beide Einträge dasselbe Recht beschreiben (Ressource, Aktion, Ziel) - unabhängig
+    /// von ID, Status und Constraints. Wird beim Update genutzt, um Entscheidungen zu übernehmen.
+    pub fn is_same_grant(&self, other: &ExtensionPermission) -> bool {
+        self.resource_type == other.resource_type
+            && self.action == other.action
+            && self.target == other.target
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, TS)]
 #[serde(rename_all = "lowercase")]
 #[ts(export)]
@@ -5,9 +5,9 @@ use crate::database::error::DatabaseError;
 use crate::extension::error::ExtensionError;
 use crate::extension::permissions::manager::PermissionManager;
 use crate::extension::permissions::types::Action;
-use crate::AppState;
+use crate::database::vault::VaultSession;
+use std::sync::Arc;
 use sqlparser::ast::{Statement, TableFactor, TableObject};
-use tauri::State;
 
 pub struct SqlPermissionValidator;
 
@@ -22,7 +22,7 @@ impl SqlPermissionValidator {
 
     /// Validiert ein SQL-Statement gegen die Permissions einer Extension
     pub async fn validate_sql(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         sql: &str,
     ) -> Result<(), ExtensionError> {
@@ -33,16 +33,16 @@ impl SqlPermissionValidator {
 
         match &statement {
             Statement::Query(_) => {
-                Self::validate_read_statement(app_state, extension_id, sql).await
+                Self::validate_read_statement(vault, extension_id, sql).await
             }
             Statement::Insert(_) | Statement::Update { .. } | Statement::Delete(_) => {
-                Self::validate_write_statement(app_state, extension_id, &statement).await
+                Self::validate_write_statement(vault, extension_id, &statement).await
             }
             Statement::CreateTable(_) => {
-                Self::validate_create_statement(app_state, extension_id, &statement).await
+                Self::validate_create_statement(vault, extension_id, &statement).await
             }
             Statement::AlterTable { .. } | Statement::Drop { .. } => {
-                Self::validate_schema_statement(app_state, extension_id, &statement).await
+                Self::validate_schema_statement(vault, extension_id, &statement).await
             }
             _ => Err(ExtensionError::ValidationError {
                 reason: format!("Statement type not allowed: {sql}"),
@@ -52,7 +52,7 @@ impl SqlPermissionValidator {
 
     /// Validiert READ-Operationen (SELECT)
     async fn validate_read_statement(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         sql: &str,
     ) -> Result<(), ExtensionError> {
@@ -60,7 +60,7 @@ impl SqlPermissionValidator {
 
         for table_name in tables {
             PermissionManager::check_database_permission(
-                app_state,
+                vault,
                 extension_id,
                 Action::Database(super::types::DbAction::Read),
                 &table_name,
@@ -73,7 +73,7 @@ impl SqlPermissionValidator {
 
     /// Validiert WRITE-Operationen (INSERT, UPDATE, DELETE)
     async fn validate_write_statement(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         statement: &Statement,
     ) -> Result<(), ExtensionError> {
@@ -81,7 +81,7 @@ impl SqlPermissionValidator {
 
         for table_name in table_names {
             PermissionManager::check_database_permission(
-                app_state,
+                vault,
                 extension_id,
                 Action::Database(super::types::DbAction::ReadWrite),
                 &table_name,
@@ -94,7 +94,7 @@ impl SqlPermissionValidator {
 
     /// Validiert CREATE TABLE
     async fn validate_create_statement(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         statement: &Statement,
     ) -> Result<(), ExtensionError> {
@@ -103,7 +103,7 @@ impl SqlPermissionValidator {
 
             // Prüfe ob Extension überhaupt CREATE-Rechte hat (z.B. auf "*")
             PermissionManager::check_database_permission(
-                app_state,
+                vault,
                 extension_id,
                 Action::Database(super::types::DbAction::Create),
                 &table_name,
@@ -116,7 +116,7 @@ impl SqlPermissionValidator {
 
     /// Validiert Schema-Änderungen (ALTER, DROP)
     async fn validate_schema_statement(
-        app_state: &State<'_, AppState>,
+        vault: &Arc<VaultSession>,
         extension_id: &str,
         statement: &Statement,
     ) -> Result<(), ExtensionError> {
@@ -125,7 +125,7 @@ impl SqlPermissionValidator {
         for table_name in table_names {
             // ALTER/DROP benötigen WRITE-Rechte
             PermissionManager::check_database_permission(
-                app_state,
+                vault,
                 extension_id,
                 Action::Database(super::types::DbAction::AlterDrop),
                 &table_name,
@@ -0,0 +1,505 @@
+// src-tauri/src/extension/registry/client.rs
+// Client für Extension-Kataloge. Ein Katalog ist eine statische `index.json` mit allen
+// Extensions und ihren Versionen; die Pakete liegen daneben (relative URLs werden gegen die
+// Index-URL aufgelöst). Dadurch genügt ein einfacher Static-File-Server oder ein lokaler Ordner
+// (`file://` bzw. absoluter Pfad) für private Kataloge.
+// Heruntergeladene Pakete werden gegen den SHA-256 aus dem Index geprüft, bevor sie entpackt werden.
+
+use crate::extension::error::ExtensionError;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri_plugin_http::reqwest;
+use ts_rs::TS;
+use url::Url;
+
+/// Dateiname des Index, wenn nur das Katalog-Verzeichnis angegeben ist
+pub const INDEX_FILE: &str = "index.json";
+
+const MAX_INDEX_BYTES: u64 = 10 * 1024 * 1024;
+const MAX_PACKAGE_BYTES: u64 = 512 * 1024 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryIndex {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub extensions: Vec<RegistryExtension>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryExtension {
+    pub name: String,
+    pub public_key: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    /// URL des Icons, relativ zur Index-URL möglich
+    #[serde(default)]
+    pub icon: Option<String>,
+    pub versions: Vec<RegistryVersion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryVersion {
+    pub version: String,
+    /// Download-URL des `.haextension`-Pakets, relativ zur Index-URL möglich
+    pub url: String,
+    /// SHA-256 des Pakets (hex)
+    pub sha256: String,
+    #[serde(default)]
+    pub size: Option<u64>,
+    #[serde(default)]
+    pub published_at: Option<String>,
+}
+
+/// Verfügbare neuere Version einer installierten Extension
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtensionUpdate {
+    pub public_key: String,
+    pub name: String,
+    pub installed_version: String,
+    pub latest_version: String,
+}
+
+fn registry_error(reason: impl Into<String>) -> ExtensionError {
+    ExtensionError::Registry {
+        reason: reason.into(),
+    }
+}
+
+impl RegistryIndex {
+    /// Extensions, deren Name, Beschreibung oder Autor `query` enthält (ohne Groß-/Kleinschreibung)
+    pub fn search(&self, query: &str) -> Vec<&RegistryExtension> {
+        let query = query.trim().to_lowercase();
+        self.extensions
+            .iter()
+            .filter(|ext| {
+                query.is_empty()
+                    || [
+                        Some(&ext.name),
+                        ext.description.as_ref(),
+                        ext.author.as_ref(),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .any(|field| field.to_lowercase().contains(&query))
+            })
+            .collect()
+    }
+
+    pub fn find(&self, public_key: &str, name: &str) -> Option<&RegistryExtension> {
+        self.extensions
+            .iter()
+            .find(|ext| ext.public_key == public_key && ext.name == name)
+    }
+
+    /// Neuere Versionen für installierte Extensions (`public_key`, `name`, `version`)
+    pub fn find_updates<'a>(
+        &self,
+        installed: impl IntoIterator<Item = (&'a str, &'a str, &'a str)>,
+    ) -> Vec<ExtensionUpdate> {
+        installed
+            .into_iter()
+            .filter_map(|(public_key, name, version)| {
+                let latest = self.find(public_key, name)?.latest()?;
+                (compare_versions(&latest.version, version) == Ordering::Greater).then(|| {
+                    ExtensionUpdate {
+                        public_key: public_key.to_string(),
+                        name: name.to_string(),
+                        installed_version: version.to_string(),
+                        latest_version: latest.version.clone(),
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+impl RegistryExtension {
+    pub fn latest(&self) -> Option<&RegistryVersion> {
+        self.versions
+            .iter()
+            .max_by(|a, b| compare_versions(&a.version, &b.version))
+    }
+
+    /// Bestimmte Version bzw. die neueste, wenn `version` fehlt
+    pub fn version(&self, version: Option<&str>) -> Option<&RegistryVersion> {
+        match version {
+            Some(version) => self.versions.iter().find(|v| v.version == version),
+            None => self.latest(),
+        }
+    }
+}
+
+/// Vergleicht Versionen nach SemVer-Regeln (`1.10.0` > `1.9.0`, `1.0.0` > `1.0.0-beta.2`).
+/// Nicht-numerische Bestandteile der Kernversion zählen als 0.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    fn split(version: &str) -> (Vec<u64>, Option<&str>) {
+        // Build-Metadaten spielen für die Reihenfolge keine Rolle
+        let version = version.trim().trim_start_matches('v');
+        let version = version.split('+').next().unwrap_or(version);
+        let (core, pre) = match version.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (version, None),
+        };
+        let core = core
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect();
+        (core, pre)
+    }
+
+    let (core_a, pre_a) = split(a);
+    let (core_b, pre_b) = split(b);
+
+    let len = core_a.len().max(core_b.len());
+    for i in 0..len {
+        let ordering = core_a.get(i).unwrap_or(&0).cmp(core_b.get(i).unwrap_or(&0));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    match (pre_a, pre_b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(pre_a), Some(pre_b)) => {
+            let mut ids_a = pre_a.split('.');
+            let mut ids_b = pre_b.split('.');
+            loop {
+                match (ids_a.next(), ids_b.next()) {
+                    (None, None) => return Ordering::Equal,
+                    (None, Some(_)) => return Ordering::Less,
+                    (Some(_), None) => return Ordering::Greater,
+                    (Some(id_a), Some(id_b)) => {
+                        let ordering = match (id_a.parse::<u64>(), id_b.parse::<u64>()) {
+                            (Ok(num_a), Ok(num_b)) => num_a.cmp(&num_b),
+                            (Ok(_), Err(_)) => Ordering::Less,
+                            (Err(_), Ok(_)) => Ordering::Greater,
+                            (Err(_), Err(_)) => id_a.cmp(id_b),
+                        };
+                        if ordering != Ordering::Equal {
+                            return ordering;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+pub struct RegistryClient {
+    index_url: Url,
+    http: reqwest::Client,
+}
+
+impl RegistryClient {
+    /// `registry_url` ist die URL der `index.json`, ein Verzeichnis (endet auf `/`)
+    /// oder ein absoluter lokaler Pfad
+    pub fn new(registry_url: &str) -> Result<Self, ExtensionError> {
+        let registry_url = registry_url.trim();
+        let mut index_url = match Url::parse(registry_url) {
+            Ok(url) => url,
+            Err(_) if Path::new(registry_url).is_absolute() => {
+                let path = Path::new(registry_url);
+                let url = if path.is_dir() {
+                    Url::from_directory_path(path)
+                } else {
+                    Url::from_file_path(path)
+                };
+                url.map_err(|_| registry_error(format!("Invalid registry path: {registry_url}")))?
+            }
+            Err(e) => return Err(registry_error(format!("Invalid registry URL: {e}"))),
+        };
+
+        if !["http", "https", "file"].contains(&index_url.scheme()) {
+            return Err(registry_error(format!(
+                "Unsupported registry scheme: {}",
+                index_url.scheme()
+            )));
+        }
+        if index_url.path().ends_with('/') {
+            index_url = index_url
+                .join(INDEX_FILE)
+                .map_err(|e| registry_error(e.to_string()))?;
+        }
+
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| registry_error(format!("Failed to create HTTP client: {e}")))?;
+
+        Ok(Self { index_url, http })
+    }
+
+    pub fn index_url(&self) -> &Url {
+        &self.index_url
+    }
+
+    /// Löst eine (ggf. relative) URL aus dem Index gegen die Index-URL auf
+    pub fn resolve(&self, url: &str) -> Result<Url, ExtensionError> {
+        let resolved = self
+            .index_url
+            .join(url)
+            .map_err(|e| registry_error(format!("Invalid URL '{url}': {e}")))?;
+
+        // Ein entfernter Katalog darf nicht auf lokale Dateien verweisen
+        if resolved.scheme() == "file" && self.index_url.scheme() != "file" {
+            return Err(registry_error(format!(
+                "Remote registry must not reference local files: {url}"
+            )));
+        }
+        Ok(resolved)
+    }
+
+    pub async fn fetch_index(&self) -> Result<RegistryIndex, ExtensionError> {
+        let bytes = self.fetch(&self.index_url, MAX_INDEX_BYTES).await?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| registry_error(format!("Invalid registry index: {e}")))
+    }
+
+    /// Lädt ein Paket (oder nimmt es aus `cache_dir`) und prüft es gegen den Hash aus dem Index
+    pub async fn download(
+        &self,
+        version: &RegistryVersion,
+        cache_dir: &Path,
+    ) -> Result<Vec<u8>, ExtensionError> {
+        let expected = version.sha256.trim().to_lowercase();
+        if expected.len() != 64 || hex::decode(&expected).is_err() {
+            return Err(registry_error(format!(
+                "Invalid sha256 for version {}",
+                version.version
+            )));
+        }
+
+        let cache_path = cache_dir.join(format!("{expected}.haextension"));
+        if let Ok(bytes) = tokio::fs::read(&cache_path).await {
+            if sha256_hex(&bytes) == expected {
+                tracing::debug!("Using cached package {}", cache_path.display());
+                return Ok(bytes);
+            }
+            let _ = tokio::fs::remove_file(&cache_path).await;
+        }
+
+        let url = self.resolve(&version.url)?;
+        let limit = version
+            .size
+            .unwrap_or(MAX_PACKAGE_BYTES)
+            .min(MAX_PACKAGE_BYTES);
+        let bytes = self.fetch(&url, limit).await?;
+
+        let actual = sha256_hex(&bytes);
+        if actual != expected {
+            return Err(registry_error(format!(
+                "Hash mismatch for {url}: expected {expected}, got {actual}"
+            )));
+        }
+
+        let cached = async {
+            tokio::fs::create_dir_all(cache_dir).await?;
+            tokio::fs::write(&cache_path, &bytes).await
+        };
+        if let Err(e) = cached.await {
+            tracing::warn!("Cannot cache package {}: {e}", cache_path.display());
+        }
+
+        Ok(bytes)
+    }
+
+    async fn fetch(&self, url: &Url, limit: u64) -> Result<Vec<u8>, ExtensionError> {
+        tracing::debug!("Fetching {url}");
+
+        if url.scheme() == "file" {
+            let path: PathBuf = url
+                .to_file_path()
+                .map_err(|_| registry_error(format!("Invalid file URL: {url}")))?;
+            let size = tokio::fs::metadata(&path)
+                .await
+                .map_err(|e| ExtensionError::filesystem_with_path(path.display().to_string(), e))?
+                .len();
+            if size > limit {
+                return Err(registry_error(format!("{url} exceeds {limit} bytes")));
+            }
+            return tokio::fs::read(&path)
+                .await
+                .map_err(|e| ExtensionError::filesystem_with_path(path.display().to_string(), e));
+        }
+
+        let mut response = self
+            .http
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(|e| registry_error(format!("Request to {url} failed: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(registry_error(format!(
+                "Request to {url} failed with status {}",
+                response.status()
+            )));
+        }
+        if response.content_length().is_some_and(|len| len > limit) {
+            return Err(registry_error(format!("{url} exceeds {limit} bytes")));
+        }
+
+        // Größe auch ohne bzw. mit falschem Content-Length begrenzen
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| registry_error(format!("Failed to read {url}: {e}")))?
+        {
+            if bytes.len() as u64 + chunk.len() as u64 > limit {
+                return Err(registry_error(format!("{url} exceeds {limit} bytes")));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(version: &str, url: &str, sha256: &str) -> RegistryVersion {
+        RegistryVersion {
+            version: version.to_string(),
+            url: url.to_string(),
+            sha256: sha256.to_string(),
+            size: None,
+            published_at: None,
+        }
+    }
+
+    fn index() -> RegistryIndex {
+        RegistryIndex {
+            name: None,
+            extensions: vec![RegistryExtension {
+                name: "notes".to_string(),
+                public_key: "abc".to_string(),
+                description: Some("Simple Markdown notes".to_string()),
+                author: None,
+                homepage: None,
+                icon: None,
+                versions: vec![
+                    version("1.9.0", "notes-1.9.0.haextension", ""),
+                    version("1.10.0", "notes-1.10.0.haextension", ""),
+                    version("2.0.0-beta.1", "notes-2.0.0-beta.1.haextension", ""),
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn compares_versions_like_semver() {
+        assert_eq!(compare_versions("1.10.0", "1.9.0"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0.0", "1.0.0-beta.2"), Ordering::Greater);
+        assert_eq!(
+            compare_versions("1.0.0-beta.2", "1.0.0-beta.10"),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_versions("1.0.0-alpha", "1.0.0-1"),
+            Ordering::Greater
+        );
+        assert_eq!(compare_versions("v2.0.0+build.5", "2.0.0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn finds_latest_version_and_updates() {
+        let index = index();
+        let notes = index.find("abc", "notes").unwrap();
+        assert_eq!(notes.latest().unwrap().version, "2.0.0-beta.1");
+        assert_eq!(
+            notes.version(Some("1.9.0")).unwrap().url,
+            "notes-1.9.0.haextension"
+        );
+
+        let updates = index.find_updates([("abc", "notes", "1.10.0"), ("xyz", "other", "1.0.0")]);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].latest_version, "2.0.0-beta.1");
+        assert!(index.find_updates([("abc", "notes", "2.0.0")]).is_empty());
+    }
+
+    #[test]
+    fn searches_name_and_description() {
+        let index = index();
+        assert_eq!(index.search("MARKDOWN").len(), 1);
+        assert_eq!(index.search("").len(), 1);
+        assert!(index.search("calendar").is_empty());
+    }
+
+    #[test]
+    fn resolves_relative_urls_against_index() {
+        let client = RegistryClient::new("https://example.com/catalog/").unwrap();
+        assert_eq!(
+            client.index_url().as_str(),
+            "https://example.com/catalog/index.json"
+        );
+        assert_eq!(
+            client.resolve("pkgs/notes.haextension").unwrap().as_str(),
+            "https://example.com/catalog/pkgs/notes.haextension"
+        );
+        assert!(client.resolve("file:///etc/passwd").is_err());
+        assert!(RegistryClient::new("ftp://example.com/index.json").is_err());
+    }
+
+    #[tokio::test]
+    async fn downloads_from_local_catalog_and_verifies_hash() {
+        let dir = std::env::temp_dir().join(format!("haex_registry_{}", uuid::Uuid::new_v4()));
+        let cache = dir.join("cache");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let package = b"not really a zip, but bytes are bytes".to_vec();
+        std::fs::write(dir.join("notes-1.0.0.haextension"), &package).unwrap();
+        let hash = sha256_hex(&package);
+        let index = format!(
+            r#"{{"extensions": [{{"name": "notes", "publicKey": "abc", "versions": [
+                {{"version": "1.0.0", "url": "notes-1.0.0.haextension", "sha256": "{hash}"}}
+            ]}}]}}"#
+        );
+        std::fs::write(dir.join(INDEX_FILE), index).unwrap();
+
+        let client = RegistryClient::new(dir.to_str().unwrap()).unwrap();
+        let fetched = client.fetch_index().await.unwrap();
+        let entry = fetched
+            .find("abc", "notes")
+            .unwrap()
+            .latest()
+            .unwrap()
+            .clone();
+        let downloaded = client.download(&entry, &cache).await;
+
+        let tampered = RegistryVersion {
+            sha256: sha256_hex(b"something else"),
+            ..entry.clone()
+        };
+        let rejected = client.download(&tampered, &cache).await;
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(downloaded.unwrap(), package);
+        assert!(matches!(rejected, Err(ExtensionError::Registry { .. })));
+    }
+}
@@ -0,0 +1,204 @@
+// src-tauri/src/extension/registry/mod.rs
+// Commands für Extension-Kataloge. Die Katalog-URL steht in den Vault-Einstellungen
+// (`extensionRegistryUrl`) und kann pro Aufruf überschrieben werden.
+
+pub mod client;
+
+use crate::database::core::with_connection;
+use crate::database::vault::VaultSession;
+use crate::extension::core::manager::ExtensionManager;
+use crate::extension::core::types::ExtensionSource;
+use crate::extension::core::{EditablePermissions, ExtensionPreview};
+use crate::extension::error::ExtensionError;
+use crate::table_names::TABLE_SETTINGS;
+use crate::AppState;
+use client::{ExtensionUpdate, RegistryClient, RegistryExtension, RegistryVersion};
+use rusqlite::OptionalExtension;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, State};
+
+/// Schlüssel der Katalog-URL in `haex_settings` (Typ `settings`)
+pub const REGISTRY_URL_KEY: &str = "extensionRegistryUrl";
+
+/// Katalog-Client für den Aufruf: explizite URL oder die aus den Vault-Einstellungen
+fn registry_client(
+    vault: &VaultSession,
+    registry_url: Option<String>,
+) -> Result<RegistryClient, ExtensionError> {
+    let registry_url = match registry_url {
+        Some(url) => url,
+        None => with_connection(&vault.db, |conn| {
+            let url: Option<Option<String>> = conn
+                .query_row(
+                    &format!("SELECT value FROM {TABLE_SETTINGS} WHERE key = ? AND type = ?"),
+                    rusqlite::params![REGISTRY_URL_KEY, "settings"],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            Ok(url.flatten())
+        })?
+        .filter(|url| !url.trim().is_empty())
+        .ok_or_else(|| ExtensionError::Registry {
+            reason: "No extension registry configured".to_string(),
+        })?,
+    };
+
+    RegistryClient::new(&registry_url)
+}
+
+fn download_cache_dir(app_handle: &AppHandle) -> Result<PathBuf, ExtensionError> {
+    app_handle
+        .path()
+        .app_cache_dir()
+        .map(|dir| dir.join("extension-registry"))
+        .map_err(|e| ExtensionError::InstallationFailed {
+            reason: format!("Cannot get app cache dir: {e}"),
+        })
+}
+
+/// Lädt Index-Eintrag und Paket einer Extension aus dem Katalog
+async fn download_package(
+    app_handle: &AppHandle,
+    client: &RegistryClient,
+    public_key: &str,
+    name: &str,
+    version: Option<&str>,
+) -> Result<(RegistryExtension, RegistryVersion, Vec<u8>), ExtensionError> {
+    let index = client.fetch_index().await?;
+    let entry = index
+        .find(public_key, name)
+        .cloned()
+        .ok_or_else(|| ExtensionError::NotFound {
+            public_key: public_key.to_string(),
+            name: name.to_string(),
+        })?;
+    let registry_version =
+        entry
+            .version(version)
+            .cloned()
+            .ok_or_else(|| ExtensionError::Registry {
+                reason: format!(
+                    "Version {} of {name} not found in registry",
+                    version.unwrap_or("latest")
+                ),
+            })?;
+
+    let bytes = client
+        .download(&registry_version, &download_cache_dir(app_handle)?)
+        .await?;
+    Ok((entry, registry_version, bytes))
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"), fields(%vault_id))]
+pub async fn get_registry_extensions(
+    query: Option<String>,
+    registry_url: Option<String>,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<RegistryExtension>, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    let client = registry_client(&vault, registry_url)?;
+    let index = client.fetch_index().await?;
+
+    Ok(index
+        .search(query.as_deref().unwrap_or_default())
+        .into_iter()
+        .cloned()
+        .collect())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%name, %vault_id))]
+pub async fn preview_registry_extension(
+    app_handle: AppHandle,
+    public_key: String,
+    name: String,
+    version: Option<String>,
+    registry_url: Option<String>,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<ExtensionPreview, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    let client = registry_client(&vault, registry_url)?;
+    let (entry, registry_version, bytes) =
+        download_package(&app_handle, &client, &public_key, &name, version.as_deref()).await?;
+
+    ExtensionManager::preview_registry_extension_internal(
+        &app_handle,
+        bytes,
+        &entry,
+        &registry_version,
+        Some(&vault),
+    )
+    .await
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(%name, %vault_id))]
+pub async fn install_registry_extension(
+    app_handle: AppHandle,
+    public_key: String,
+    name: String,
+    version: Option<String>,
+    custom_permissions: EditablePermissions,
+    registry_url: Option<String>,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    let client = registry_client(&vault, registry_url)?;
+    let (entry, registry_version, bytes) =
+        download_package(&app_handle, &client, &public_key, &name, version.as_deref()).await?;
+
+    vault
+        .extension_manager
+        .install_registry_extension_internal(
+            app_handle,
+            bytes,
+            &entry,
+            &registry_version,
+            custom_permissions,
+            &vault,
+        )
+        .await
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"), fields(%vault_id))]
+pub async fn check_extension_updates(
+    registry_url: Option<String>,
+    vault_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ExtensionUpdate>, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+    let client = registry_client(&vault, registry_url)?;
+
+    let installed: Vec<(String, String, String)> = {
+        let prod_exts = vault
+            .extension_manager
+            .production_extensions
+            .lock()
+            .map_err(|e| ExtensionError::MutexPoisoned {
+                reason: e.to_string(),
+            })?;
+        prod_exts
+            .values()
+            .filter_map(|ext| match &ext.source {
+                ExtensionSource::Production { version, .. } => Some((
+                    ext.manifest.public_key.clone(),
+                    ext.manifest.name.clone(),
+                    version.clone(),
+                )),
+                ExtensionSource::Development { .. } => None,
+            })
+            .collect()
+    };
+
+    let index = client.fetch_index().await?;
+    Ok(index.find_updates(
+        installed
+            .iter()
+            .map(|(pk, name, version)| (pk.as_str(), name.as_str(), version.as_str())),
+    ))
+}
@@ -34,20 +34,23 @@ pub struct WebFetchResponse {
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty, %vault_id),
+)]
 pub async fn extension_web_open(
     url: String,
     public_key: String,
     name: String,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<(), ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+
     // Get extension to validate it exists
-    let extension = state
-        .extension_manager
-        .get_extension_by_public_key_and_name(&public_key, &name)?
-        .ok_or_else(|| ExtensionError::NotFound {
-            public_key: public_key.clone(),
-            name: name.clone(),
-        })?;
+    let extension = vault.extension_manager.get_active_extension(&public_key, &name)?;
 
     // Validate URL format
     let parsed_url = url::Url::parse(&url).map_err(|e| ExtensionError::WebError {
@@ -64,7 +67,7 @@ pub async fn extension_web_open(
 
     // Check web permissions
     crate::extension::permissions::manager::PermissionManager::check_web_permission(
-        &state,
+        &vault,
         &extension.id,
         &url,
     )
@@ -79,6 +82,12 @@ pub async fn extension_web_open(
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty, %vault_id),
+)]
 pub async fn extension_web_fetch(
     url: String,
     method: Option<String>,
@@ -87,22 +96,19 @@ pub async fn extension_web_fetch(
     timeout: Option<u64>,
     public_key: String,
     name: String,
+    vault_id: String,
     state: State<'_, AppState>,
 ) -> Result<WebFetchResponse, ExtensionError> {
+    let vault = state.vaults.get(&vault_id)?;
+
     // Get extension to validate it exists
-    let extension = state
-        .extension_manager
-        .get_extension_by_public_key_and_name(&public_key, &name)?
-        .ok_or_else(|| ExtensionError::NotFound {
-            public_key: public_key.clone(),
-            name: name.clone(),
-        })?;
+    let extension = vault.extension_manager.get_active_extension(&public_key, &name)?;
 
     let method_str = method.as_deref().unwrap_or("GET");
 
     // Check web permissions before making request
     crate::extension::permissions::manager::PermissionManager::check_web_permission(
-        &state,
+        &vault,
         &extension.id,
         &url,
     )
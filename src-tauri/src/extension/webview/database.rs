@@ -1,4 +1,8 @@
-use crate::extension::database::{extension_sql_execute, extension_sql_select};
+use crate::database::core::SqlParams;
+use crate::database::live::{LiveQueryOwner, LiveQuerySubscription};
+use crate::extension::database::{
+    extension_sql_execute, extension_sql_select, subscribe_extension_live_query,
+};
 use crate::extension::error::ExtensionError;
 use crate::AppState;
 use tauri::{State, WebviewWindow};
@@ -6,23 +10,29 @@ use tauri::{State, WebviewWindow};
 use super::helpers::get_extension_id;
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty),
+)]
 pub async fn webview_extension_db_query(
     window: WebviewWindow,
     state: State<'_, AppState>,
     query: String,
-    params: Vec<serde_json::Value>,
+    params: SqlParams,
 ) -> Result<serde_json::Value, ExtensionError> {
-    let extension_id = get_extension_id(&window, &state)?;
+    let (vault, extension_id) = get_extension_id(&window, &state)?;
 
     // Get extension to retrieve public_key and name for existing database functions
-    let extension = state
+    let extension = vault
         .extension_manager
         .get_extension(&extension_id)
         .ok_or_else(|| ExtensionError::ValidationError {
             reason: format!("Extension with ID {} not found", extension_id),
         })?;
 
-    let rows = extension_sql_select(&query, params, extension.manifest.public_key.clone(), extension.manifest.name.clone(), state)
+    let rows = extension_sql_select(&query, params, extension.manifest.public_key.clone(), extension.manifest.name.clone(), vault.id.clone(), state)
         .await
         .map_err(|e| ExtensionError::ValidationError {
             reason: format!("Database query failed: {}", e),
@@ -36,23 +46,29 @@ pub async fn webview_extension_db_query(
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty),
+)]
 pub async fn webview_extension_db_execute(
     window: WebviewWindow,
     state: State<'_, AppState>,
     query: String,
-    params: Vec<serde_json::Value>,
+    params: SqlParams,
 ) -> Result<serde_json::Value, ExtensionError> {
-    let extension_id = get_extension_id(&window, &state)?;
+    let (vault, extension_id) = get_extension_id(&window, &state)?;
 
     // Get extension to retrieve public_key and name for existing database functions
-    let extension = state
+    let extension = vault
         .extension_manager
         .get_extension(&extension_id)
         .ok_or_else(|| ExtensionError::ValidationError {
             reason: format!("Extension with ID {} not found", extension_id),
         })?;
 
-    let rows = extension_sql_execute(&query, params, extension.manifest.public_key.clone(), extension.manifest.name.clone(), state)
+    let rows = extension_sql_execute(&query, params, extension.manifest.public_key.clone(), extension.manifest.name.clone(), vault.id.clone(), state)
         .await
         .map_err(|e| ExtensionError::ValidationError {
             reason: format!("Database execute failed: {}", e),
@@ -64,3 +80,55 @@ pub async fn webview_extension_db_execute(
         "lastInsertId": null
     }))
 }
+
+/// Registriert eine Live-Query; neue Ergebnisse kommen als `live-query-result`-Event an dieses Fenster
+#[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty),
+)]
+pub async fn webview_extension_db_subscribe(
+    window: WebviewWindow,
+    state: State<'_, AppState>,
+    query: String,
+    params: SqlParams,
+) -> Result<LiveQuerySubscription, ExtensionError> {
+    let (vault, extension_id) = get_extension_id(&window, &state)?;
+
+    let extension = vault
+        .extension_manager
+        .get_extension(&extension_id)
+        .ok_or_else(|| ExtensionError::ValidationError {
+            reason: format!("Extension with ID {} not found", extension_id),
+        })?;
+
+    subscribe_extension_live_query(
+        &vault,
+        &query,
+        params,
+        &extension.manifest.public_key,
+        &extension.manifest.name,
+    )
+    .await
+}
+
+#[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty, %subscription_id),
+)]
+pub async fn webview_extension_db_unsubscribe(
+    window: WebviewWindow,
+    state: State<'_, AppState>,
+    subscription_id: String,
+) -> Result<bool, ExtensionError> {
+    let (vault, extension_id) = get_extension_id(&window, &state)?;
+
+    Ok(vault
+        .live_queries
+        .unsubscribe(&subscription_id, &LiveQueryOwner::Extension(extension_id))?)
+}
@@ -18,6 +18,7 @@ pub struct SaveFileResult {
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(window = %window.label()))]
 pub async fn webview_extension_fs_save_file(
     window: WebviewWindow,
     _state: State<'_, AppState>,
@@ -26,9 +27,7 @@ pub async fn webview_extension_fs_save_file(
     title: Option<String>,
     filters: Option<Vec<FileFilter>>,
 ) -> Result<Option<SaveFileResult>, ExtensionError> {
-    eprintln!("[Filesystem] save_file called with {} bytes", data.len());
-    eprintln!("[Filesystem] save_file default_path: {:?}", default_path);
-    eprintln!("[Filesystem] save_file first 10 bytes: {:?}", &data[..data.len().min(10)]);
+    tracing::debug!(bytes = data.len(), ?default_path, "save_file called");
 
     // Build save dialog
     let mut dialog = window.dialog().file();
@@ -49,7 +48,6 @@ pub async fn webview_extension_fs_save_file(
     }
 
     // Show dialog (blocking_save_file is safe in async commands)
-    eprintln!("[Filesystem] Showing save dialog...");
     let file_path = dialog.blocking_save_file();
 
     if let Some(file_path) = file_path {
@@ -58,32 +56,29 @@ pub async fn webview_extension_fs_save_file(
             reason: "Failed to get file path".to_string(),
         })?;
 
-        eprintln!("[Filesystem] User selected path: {}", path_buf.display());
-        eprintln!("[Filesystem] Writing {} bytes to file...", data.len());
-
         // Write file using std::fs
         std::fs::write(path_buf, &data)
             .map_err(|e| {
-                eprintln!("[Filesystem] ERROR writing file: {}", e);
+                tracing::error!("Failed to write file {}: {}", path_buf.display(), e);
                 ExtensionError::ValidationError {
                     reason: format!("Failed to write file: {}", e),
                 }
             })?;
 
-        eprintln!("[Filesystem] File written successfully");
+        tracing::debug!("Wrote {} bytes to {}", data.len(), path_buf.display());
 
         Ok(Some(SaveFileResult {
             path: path_buf.to_string_lossy().to_string(),
             success: true,
         }))
     } else {
-        eprintln!("[Filesystem] User cancelled");
         // User cancelled
         Ok(None)
     }
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, err(level = "warn"), fields(window = %window.label()))]
 pub async fn webview_extension_fs_open_file(
     window: WebviewWindow,
     _state: State<'_, AppState>,
@@ -1,13 +1,17 @@
+use crate::database::vault::VaultSession;
 use crate::extension::core::protocol::ExtensionInfo;
 use crate::extension::error::ExtensionError;
+use crate::logging;
 use crate::AppState;
+use std::sync::Arc;
 use tauri::{State, WebviewWindow};
 
-/// Get extension_id from window (SECURITY: window_id from Tauri, cannot be spoofed)
-pub fn get_extension_id(window: &WebviewWindow, state: &State<AppState>) -> Result<String, ExtensionError> {
+/// Get extension_id and the vault it was launched from (SECURITY: window_id from Tauri, cannot be spoofed)
+pub fn get_extension_id(
+    window: &WebviewWindow,
+    state: &State<AppState>,
+) -> Result<(Arc<VaultSession>, String), ExtensionError> {
     let window_id = window.label();
-    eprintln!("[webview_api] Looking up extension_id for window: {}", window_id);
-
     let windows = state
         .extension_webview_manager
         .windows
@@ -16,17 +20,24 @@ pub fn get_extension_id(window: &WebviewWindow, state: &State<AppState>) -> Resu
             reason: e.to_string(),
         })?;
 
-    eprintln!("[webview_api] HashMap contents: {:?}", *windows);
-
-    let extension_id = windows
+    let extension_window = windows
         .get(window_id)
         .cloned()
         .ok_or_else(|| ExtensionError::ValidationError {
             reason: format!("Window {} is not registered as an extension window", window_id),
         })?;
+    drop(windows);
 
-    eprintln!("[webview_api] Found extension_id: {}", extension_id);
-    Ok(extension_id)
+    logging::record_extension_id(&extension_window.extension_id);
+
+    // Die Vault kann inzwischen geschlossen sein - dann darf das Fenster nichts mehr tun
+    let vault = state.vaults.get(&extension_window.vault_id)?;
+
+    // Ebenso, wenn die Extension inzwischen deaktiviert wurde
+    vault
+        .extension_manager
+        .ensure_enabled(&extension_window.extension_id)?;
+    Ok((vault, extension_window.extension_id))
 }
 
 /// Get full extension info (public_key, name, version) from window
@@ -34,10 +45,10 @@ pub fn get_extension_info_from_window(
     window: &WebviewWindow,
     state: &State<AppState>,
 ) -> Result<ExtensionInfo, ExtensionError> {
-    let extension_id = get_extension_id(window, state)?;
+    let (vault, extension_id) = get_extension_id(window, state)?;
 
     // Get extension from ExtensionManager using the database UUID
-    let extension = state
+    let extension = vault
         .extension_manager
         .get_extension(&extension_id)
         .ok_or_else(|| ExtensionError::ValidationError {
@@ -5,12 +5,19 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
 
+/// Ein geöffnetes Extension-Fenster und die Vault, aus der es gestartet wurde
+#[derive(Debug, Clone)]
+pub struct ExtensionWindow {
+    pub extension_id: String,
+    pub vault_id: String,
+}
+
 /// Verwaltet native WebviewWindows für Extensions (nur Desktop-Plattformen)
 pub struct ExtensionWebviewManager {
-    /// Map: window_id -> extension_id
+    /// Map: window_id -> Extension + Vault
     /// Das window_id ist ein eindeutiger Identifier (Tauri-kompatibel, keine Bindestriche)
     /// und wird gleichzeitig als Tauri WebviewWindow label verwendet
-    pub windows: Arc<Mutex<HashMap<String, String>>>,
+    pub windows: Arc<Mutex<HashMap<String, ExtensionWindow>>>,
 }
 
 impl ExtensionWebviewManager {
@@ -24,7 +31,8 @@ impl ExtensionWebviewManager {
     ///
     /// # Arguments
     /// * `app_handle` - Tauri AppHandle
-    /// * `extension_manager` - Extension Manager für Zugriff auf Extension-Daten
+    /// * `extension_manager` - Extension Manager der Vault, aus der die Extension gestartet wird
+    /// * `vault_id` - ID dieser Vault; alle Commands des Fensters laufen gegen sie
     /// * `extension_id` - ID der zu öffnenden Extension
     /// * `title` - Fenstertitel
     /// * `width` - Fensterbreite
@@ -38,6 +46,7 @@ impl ExtensionWebviewManager {
         &self,
         app_handle: &AppHandle,
         extension_manager: &ExtensionManager,
+        vault_id: String,
         extension_id: String,
         title: String,
         width: f64,
@@ -53,6 +62,10 @@ impl ExtensionWebviewManager {
                 name: extension_id.clone(),
             })?;
 
+        if !extension.enabled {
+            return Err(ExtensionError::Disabled { extension_id });
+        }
+
         // URL für Extension generieren (analog zum Frontend)
         use crate::extension::core::types::ExtensionSource;
         let url = match &extension.source {
@@ -96,7 +109,7 @@ impl ExtensionWebviewManager {
         // Eindeutige Window-ID generieren (wird auch als Tauri label verwendet, keine Bindestriche erlaubt)
         let window_id = format!("ext_{}", uuid::Uuid::new_v4().simple());
 
-        eprintln!("Opening extension window: {} with URL: {}", window_id, url);
+        tracing::debug!(%extension_id, "Opening extension window: {} with URL: {}", window_id, url);
 
         // WebviewWindow erstellen
         let webview_url = WebviewUrl::External(url.parse().map_err(|e| {
@@ -136,7 +149,7 @@ impl ExtensionWebviewManager {
 
         webview_window.on_window_event(move |event| {
             if let tauri::WindowEvent::Destroyed = event {
-                eprintln!("WebviewWindow destroyed: {}", window_id_for_event);
+                tracing::debug!("WebviewWindow destroyed: {}", window_id_for_event);
 
                 // Registry cleanup
                 if let Ok(mut windows) = windows_for_event.lock() {
@@ -152,9 +165,15 @@ impl ExtensionWebviewManager {
         let mut windows = self.windows.lock().map_err(|e| ExtensionError::MutexPoisoned {
             reason: e.to_string(),
         })?;
-        windows.insert(window_id.clone(), extension_id.clone());
-
-        eprintln!("Extension window opened successfully: {}", window_id);
+        windows.insert(
+            window_id.clone(),
+            ExtensionWindow {
+                extension_id: extension_id.clone(),
+                vault_id,
+            },
+        );
+
+        tracing::info!(%extension_id, "Extension window opened: {}", window_id);
         Ok(window_id)
     }
 
@@ -178,7 +197,7 @@ impl ExtensionWebviewManager {
                     reason: format!("Failed to close window: {}", e),
                 })?;
             }
-            eprintln!("Extension window closed: {}", window_id);
+            tracing::info!("Extension window closed: {}", window_id);
             Ok(())
         } else {
             Err(ExtensionError::NotFound {
@@ -188,6 +207,48 @@ impl ExtensionWebviewManager {
         }
     }
 
+    /// Schließt alle Fenster, die aus der angegebenen Vault gestartet wurden
+    pub fn close_windows_for_vault(&self, app_handle: &AppHandle, vault_id: &str) {
+        let window_ids: Vec<String> = match self.windows.lock() {
+            Ok(windows) => windows
+                .iter()
+                .filter(|(_, window)| window.vault_id == vault_id)
+                .map(|(window_id, _)| window_id.clone())
+                .collect(),
+            Err(e) => {
+                tracing::error!("Failed to lock extension windows: {}", e);
+                return;
+            }
+        };
+
+        for window_id in window_ids {
+            if let Err(e) = self.close_extension_window(app_handle, &window_id) {
+                tracing::warn!("Failed to close extension window {}: {}", window_id, e);
+            }
+        }
+    }
+
+    /// Schließt alle Fenster einer Extension, z.B. nachdem sie deaktiviert wurde
+    pub fn close_windows_for_extension(&self, app_handle: &AppHandle, extension_id: &str) {
+        let window_ids: Vec<String> = match self.windows.lock() {
+            Ok(windows) => windows
+                .iter()
+                .filter(|(_, window)| window.extension_id == extension_id)
+                .map(|(window_id, _)| window_id.clone())
+                .collect(),
+            Err(e) => {
+                tracing::error!("Failed to lock extension windows: {}", e);
+                return;
+            }
+        };
+
+        for window_id in window_ids {
+            if let Err(e) = self.close_extension_window(app_handle, &window_id) {
+                tracing::warn!("Failed to close extension window {}: {}", window_id, e);
+            }
+        }
+    }
+
     /// Fokussiert ein Extension-Fenster
     pub fn focus_extension_window(
         &self,
@@ -294,6 +355,34 @@ impl ExtensionWebviewManager {
         }
     }
 
+    /// Emits an event to each extension webview window with its own payload.
+    /// Windows for which `payload_for` returns `None` are skipped.
+    pub fn emit_to_extension_windows<S, F>(
+        &self,
+        app_handle: &AppHandle,
+        event: &str,
+        mut payload_for: F,
+    ) -> Result<(), ExtensionError>
+    where
+        S: serde::Serialize + Clone,
+        F: FnMut(&ExtensionWindow) -> Option<S>,
+    {
+        let windows = self.windows.lock().map_err(|e| ExtensionError::MutexPoisoned {
+            reason: e.to_string(),
+        })?;
+
+        for (window_id, window) in windows.iter() {
+            let Some(payload) = payload_for(window) else {
+                continue;
+            };
+            if let Err(e) = app_handle.emit_to(window_id.as_str(), event, payload) {
+                tracing::warn!("Failed to emit event {} to window {}: {}", event, window_id, e);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Emits an event to all extension webview windows
     pub fn emit_to_all_extensions<S: serde::Serialize + Clone>(
         &self,
@@ -305,20 +394,18 @@ impl ExtensionWebviewManager {
             reason: e.to_string(),
         })?;
 
-        eprintln!("[Manager] Emitting event '{}' to {} webview windows", event, windows.len());
+        tracing::debug!("Emitting event '{}' to {} webview windows", event, windows.len());
 
         // Iterate over all window IDs
         for window_id in windows.keys() {
-            eprintln!("[Manager] Trying to emit to window: {}", window_id);
             #[cfg(not(any(target_os = "android", target_os = "ios")))]
             if let Some(window) = app_handle.get_webview_window(window_id) {
                 // Emit event to this specific webview window
-                match window.emit(event, payload.clone()) {
-                    Ok(_) => eprintln!("[Manager] Successfully emitted event '{}' to window {}", event, window_id),
-                    Err(e) => eprintln!("[Manager] Failed to emit event {} to window {}: {}", event, window_id, e),
+                if let Err(e) = window.emit(event, payload.clone()) {
+                    tracing::warn!("Failed to emit event {} to window {}: {}", event, window_id, e);
                 }
             } else {
-                eprintln!("[Manager] Window not found: {}", window_id);
+                tracing::warn!("Window not found: {}", window_id);
             }
         }
 
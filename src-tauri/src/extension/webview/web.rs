@@ -27,6 +27,12 @@ pub struct ApplicationContext {
 // ============================================================================
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty),
+)]
 pub fn webview_extension_get_info(
     window: WebviewWindow,
     state: State<'_, AppState>,
@@ -39,6 +45,7 @@ pub fn webview_extension_get_info(
 // ============================================================================
 
 #[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"))]
 pub fn webview_extension_context_get(
     state: State<'_, AppState>,
 ) -> Result<ApplicationContext, ExtensionError> {
@@ -49,6 +56,7 @@ pub fn webview_extension_context_get(
 }
 
 #[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"))]
 pub fn webview_extension_context_set(
     state: State<'_, AppState>,
     context: ApplicationContext,
@@ -65,27 +73,39 @@ pub fn webview_extension_context_set(
 // ============================================================================
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty),
+)]
 pub async fn webview_extension_check_web_permission(
     window: WebviewWindow,
     state: State<'_, AppState>,
     url: String,
 ) -> Result<bool, ExtensionError> {
-    let extension_id = get_extension_id(&window, &state)?;
+    let (vault, extension_id) = get_extension_id(&window, &state)?;
 
-    match PermissionManager::check_web_permission(&state, &extension_id, &url).await {
+    match PermissionManager::check_web_permission(&vault, &extension_id, &url).await {
         Ok(_) => Ok(true),
         Err(_) => Ok(false),
     }
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty),
+)]
 pub async fn webview_extension_check_database_permission(
     window: WebviewWindow,
     state: State<'_, AppState>,
     resource: String,
     operation: String,
 ) -> Result<bool, ExtensionError> {
-    let extension_id = get_extension_id(&window, &state)?;
+    let (vault, extension_id) = get_extension_id(&window, &state)?;
 
     let action = match operation.as_str() {
         "read" => Action::Database(DbAction::Read),
@@ -93,20 +113,26 @@ pub async fn webview_extension_check_database_permission(
         _ => return Ok(false),
     };
 
-    match PermissionManager::check_database_permission(&state, &extension_id, action, &resource).await {
+    match PermissionManager::check_database_permission(&vault, &extension_id, action, &resource).await {
         Ok(_) => Ok(true),
         Err(_) => Ok(false),
     }
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty),
+)]
 pub async fn webview_extension_check_filesystem_permission(
     window: WebviewWindow,
     state: State<'_, AppState>,
     path: String,
     action_str: String,
 ) -> Result<bool, ExtensionError> {
-    let extension_id = get_extension_id(&window, &state)?;
+    let (vault, extension_id) = get_extension_id(&window, &state)?;
 
     let action = match action_str.as_str() {
         "read" => Action::Filesystem(FsAction::Read),
@@ -115,7 +141,7 @@ pub async fn webview_extension_check_filesystem_permission(
     };
 
     let path_buf = std::path::Path::new(&path);
-    match PermissionManager::check_filesystem_permission(&state, &extension_id, action, path_buf).await {
+    match PermissionManager::check_filesystem_permission(&vault, &extension_id, action, path_buf).await {
         Ok(_) => Ok(true),
         Err(_) => Ok(false),
     }
@@ -126,12 +152,18 @@ pub async fn webview_extension_check_filesystem_permission(
 // ============================================================================
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty),
+)]
 pub async fn webview_extension_web_open(
     window: WebviewWindow,
     state: State<'_, AppState>,
     url: String,
 ) -> Result<(), ExtensionError> {
-    let extension_id = get_extension_id(&window, &state)?;
+    let (vault, extension_id) = get_extension_id(&window, &state)?;
 
     // Validate URL format
     let parsed_url = url::Url::parse(&url).map_err(|e| ExtensionError::WebError {
@@ -147,7 +179,7 @@ pub async fn webview_extension_web_open(
     }
 
     // Check web permissions
-    PermissionManager::check_web_permission(&state, &extension_id, &url).await?;
+    PermissionManager::check_web_permission(&vault, &extension_id, &url).await?;
 
     // Open URL in default browser using tauri-plugin-opener
     tauri_plugin_opener::open_url(&url, None::<&str>).map_err(|e| ExtensionError::WebError {
@@ -158,6 +190,12 @@ pub async fn webview_extension_web_open(
 }
 
 #[tauri::command]
+#[tracing::instrument(
+    skip_all,
+    level = "debug",
+    err(level = "warn"),
+    fields(extension_id = tracing::field::Empty),
+)]
 pub async fn webview_extension_web_request(
     window: WebviewWindow,
     state: State<'_, AppState>,
@@ -166,10 +204,10 @@ pub async fn webview_extension_web_request(
     headers: Option<serde_json::Value>,
     body: Option<String>,
 ) -> Result<serde_json::Value, ExtensionError> {
-    let extension_id = get_extension_id(&window, &state)?;
+    let (vault, extension_id) = get_extension_id(&window, &state)?;
 
     // Check permission first
-    PermissionManager::check_web_permission(&state, &extension_id, &url).await?;
+    PermissionManager::check_web_permission(&vault, &extension_id, &url).await?;
 
     // Build request
     let method = method.unwrap_or_else(|| "GET".to_string());
@@ -247,6 +285,7 @@ pub async fn webview_extension_web_request(
 
 /// Broadcasts an event to all extension webview windows
 #[tauri::command]
+#[tracing::instrument(skip_all, level = "debug", err(level = "warn"))]
 pub async fn webview_extension_emit_to_all(
     app_handle: tauri::AppHandle,
     state: State<'_, AppState>,
@@ -1,16 +1,12 @@
 mod crdt;
 mod database;
 mod extension;
-use crate::{
-    crdt::hlc::HlcService,
-    database::DbConnection,
-    extension::core::ExtensionManager,
-};
+mod logging;
+use crate::database::vault::VaultRegistry;
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use crate::extension::webview::ExtensionWebviewManager;
 use std::sync::{Arc, Mutex};
-use tauri::Manager;
 
 pub mod table_names {
     include!(concat!(env!("OUT_DIR"), "/tableNames.rs"));
@@ -21,9 +17,8 @@ pub mod event_names {
 }
 
 pub struct AppState {
-    pub db: DbConnection,
-    pub hlc: Mutex<HlcService>,
-    pub extension_manager: ExtensionManager,
+    /// Alle geöffneten Vaults, adressiert über ihre Vault-ID
+    pub vaults: VaultRegistry,
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
     pub extension_webview_manager: ExtensionWebviewManager,
     pub context: Arc<Mutex<extension::webview::web::ApplicationContext>>,
@@ -34,20 +29,18 @@ pub fn run() {
     use extension::core::EXTENSION_PROTOCOL_NAME;
 
     tauri::Builder::default()
+        .setup(|app| {
+            logging::init(app.handle());
+            Ok(())
+        })
         .register_uri_scheme_protocol(EXTENSION_PROTOCOL_NAME, move |context, request| {
-            // Hole den AppState aus dem Context
             let app_handle = context.app_handle();
-            let state = app_handle.state::<AppState>();
 
             // Rufe den Handler mit allen benötigten Parametern auf
-            match extension::core::extension_protocol_handler(state, app_handle, &request) {
+            match extension::core::extension_protocol_handler(app_handle, &request) {
                 Ok(response) => response,
                 Err(e) => {
-                    eprintln!(
-                        "Fehler im Custom Protocol Handler für URI '{}': {}",
-                        request.uri(),
-                        e
-                    );
+                    tracing::error!(uri = %request.uri(), "Fehler im Custom Protocol Handler: {e}");
                     tauri::http::Response::builder()
                         .status(500)
                         .header("Content-Type", "text/plain")
@@ -55,7 +48,7 @@ pub fn run() {
                             "Interner Serverfehler im Protokollhandler: {e}"
                         )))
                         .unwrap_or_else(|build_err| {
-                            eprintln!("Konnte Fehler-Response nicht erstellen: {build_err}");
+                            tracing::error!("Konnte Fehler-Response nicht erstellen: {build_err}");
                             tauri::http::Response::builder()
                                 .status(500)
                                 .body(Vec::new())
@@ -65,9 +58,7 @@ pub fn run() {
             }
         })
         .manage(AppState {
-            db: DbConnection(Arc::new(Mutex::new(None))),
-            hlc: Mutex::new(HlcService::new()),
-            extension_manager: ExtensionManager::new(),
+            vaults: VaultRegistry::new(),
             #[cfg(not(any(target_os = "android", target_os = "ios")))]
             extension_webview_manager: ExtensionWebviewManager::new(),
             context: Arc::new(Mutex::new(extension::webview::web::ApplicationContext {
@@ -86,19 +77,45 @@ pub fn run() {
         .plugin(tauri_plugin_persisted_scope::init())
         .plugin(tauri_plugin_store::Builder::new().build())
         .invoke_handler(tauri::generate_handler![
+            database::check_vault,
+            database::close_vault,
             database::create_encrypted_database,
+            database::declare_search_index,
             database::delete_vault,
+            database::drop_search_index,
+            database::export_tables,
+            database::import_table,
             database::move_vault_to_trash,
+            database::list_open_vaults,
+            database::list_search_indexes,
             database::list_vaults,
             database::open_encrypted_database,
+            database::rebuild_search_indexes,
+            database::rename_vault,
+            database::search,
             database::sql_execute_with_crdt,
             database::sql_execute,
             database::sql_query_with_crdt,
             database::sql_select_with_crdt,
             database::sql_select,
+            database::sql_query_typed,
+            database::subscribe_live_query,
+            database::unsubscribe_live_query,
+            database::update_vault_metadata,
             database::vault_exists,
+            logging::get_recent_logs,
             extension::database::extension_sql_execute,
             extension::database::extension_sql_select,
+            extension::database::extension_sql_explain,
+            extension::database::set_extension_slow_query_log,
+            extension::database::get_extension_slow_queries,
+            extension::database::extension_declare_search_index,
+            extension::database::extension_drop_search_index,
+            extension::database::extension_search,
+            extension::registry::check_extension_updates,
+            extension::registry::get_registry_extensions,
+            extension::registry::install_registry_extension,
+            extension::registry::preview_registry_extension,
             extension::web::extension_web_fetch,
             extension::web::extension_web_open,
             extension::permissions::check::check_web_permission,
@@ -107,12 +124,20 @@ pub fn run() {
             extension::get_all_dev_extensions,
             extension::get_all_extensions,
             extension::get_extension_info,
+            extension::get_extension_publishers,
+            extension::get_extension_versions,
             extension::install_extension_with_permissions,
+            extension::install_extension_from_path,
             extension::is_extension_installed,
             extension::load_dev_extension,
             extension::preview_extension,
+            extension::preview_extension_from_path,
             extension::remove_dev_extension,
             extension::remove_extension,
+            extension::remove_extension_publisher,
+            extension::rollback_extension,
+            extension::set_extension_enabled,
+            extension::set_extension_publisher,
             #[cfg(not(any(target_os = "android", target_os = "ios")))]
             extension::open_extension_webview_window,
             #[cfg(not(any(target_os = "android", target_os = "ios")))]
@@ -133,6 +158,10 @@ pub fn run() {
             #[cfg(not(any(target_os = "android", target_os = "ios")))]
             extension::webview::database::webview_extension_db_execute,
             #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            extension::webview::database::webview_extension_db_subscribe,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            extension::webview::database::webview_extension_db_unsubscribe,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
             extension::webview::web::webview_extension_check_web_permission,
             #[cfg(not(any(target_os = "android", target_os = "ios")))]
             extension::webview::web::webview_extension_check_database_permission,
@@ -0,0 +1,118 @@
+// src-tauri/src/logging/mod.rs
+// Logging über `tracing`: Konsole, rotierende Log-Datei im App-Log-Verzeichnis und ein
+// Ringpuffer der letzten Zeilen, den das Frontend für Fehlerberichte abrufen kann.
+
+pub mod recent;
+pub mod redact;
+
+use recent::RecentLogs;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::EnvFilter;
+use ts_rs::TS;
+
+/// Umgebungsvariable für Filter-Direktiven, z.B. `HAEX_LOG=haex_hub_lib::database=trace`
+pub const LOG_FILTER_ENV: &str = "HAEX_LOG";
+
+const LOG_FILE_PREFIX: &str = "haex-hub";
+const LOG_FILE_SUFFIX: &str = "log";
+/// Anzahl der täglichen Log-Dateien, die aufbewahrt werden
+const MAX_LOG_FILES: usize = 7;
+
+#[cfg(debug_assertions)]
+const DEFAULT_FILTER: &str = "warn,haex_hub_lib=debug";
+#[cfg(not(debug_assertions))]
+const DEFAULT_FILTER: &str = "warn,haex_hub_lib=info";
+
+static RECENT_LOGS: OnceLock<RecentLogs> = OnceLock::new();
+/// Hält den Writer-Thread der Log-Datei am Leben, bis der Prozess endet
+static FILE_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+static LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn recent_logs() -> &'static RecentLogs {
+    RECENT_LOGS.get_or_init(|| RecentLogs::new(recent::DEFAULT_CAPACITY))
+}
+
+fn env_filter() -> EnvFilter {
+    EnvFilter::try_from_env(LOG_FILTER_ENV).unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER))
+}
+
+/// Richtet den globalen Subscriber ein. Ist das Log-Verzeichnis nicht verfügbar,
+/// wird nur auf die Konsole und in den Ringpuffer geloggt.
+pub fn init(app_handle: &AppHandle) {
+    let log_dir = app_handle.path().app_log_dir().ok();
+
+    let file_writer = log_dir.as_ref().and_then(|dir| {
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(LOG_FILE_PREFIX)
+            .filename_suffix(LOG_FILE_SUFFIX)
+            .max_log_files(MAX_LOG_FILES)
+            .build(dir)
+            .map_err(|e| eprintln!("Could not create log file in {}: {e}", dir.display()))
+            .ok()
+    });
+
+    let file_layer = file_writer.map(|appender| {
+        let (writer, guard) = tracing_appender::non_blocking(appender);
+        let _ = FILE_GUARD.set(guard);
+        tracing_subscriber::fmt::layer()
+            .with_writer(writer)
+            .with_ansi(false)
+            .with_span_events(FmtSpan::CLOSE)
+    });
+
+    let recent_layer = tracing_subscriber::fmt::layer()
+        .with_writer(recent_logs().clone())
+        .with_ansi(false)
+        .with_span_events(FmtSpan::CLOSE);
+
+    let console_layer = tracing_subscriber::fmt::layer().with_span_events(FmtSpan::CLOSE);
+
+    let result = tracing_subscriber::registry()
+        .with(env_filter())
+        .with(console_layer)
+        .with(file_layer)
+        .with(recent_layer)
+        .try_init();
+
+    match result {
+        Ok(()) => {
+            if let Some(dir) = log_dir {
+                tracing::info!(log_dir = %dir.display(), "Logging initialized");
+                let _ = LOG_DIR.set(dir);
+            }
+        }
+        Err(e) => eprintln!("Logging already initialized: {e}"),
+    }
+}
+
+/// Trägt die Extension-ID im Span des laufenden Commands nach, sobald sie aufgelöst wurde.
+/// Commands deklarieren dafür `extension_id = tracing::field::Empty`, sonst passiert nichts.
+pub fn record_extension_id(extension_id: &str) {
+    tracing::Span::current().record("extension_id", extension_id);
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentLogsResult {
+    pub lines: Vec<String>,
+    /// Verzeichnis der rotierenden Log-Dateien, falls verfügbar
+    pub log_dir: Option<String>,
+}
+
+/// Liefert die letzten Log-Zeilen (ohne ANSI-Farben) für Fehlerberichte
+#[tauri::command]
+pub fn get_recent_logs(limit: Option<usize>) -> RecentLogsResult {
+    RecentLogsResult {
+        lines: recent_logs().lines(limit),
+        log_dir: LOG_DIR.get().map(|dir| dir.display().to_string()),
+    }
+}
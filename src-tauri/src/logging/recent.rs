@@ -0,0 +1,111 @@
+// src-tauri/src/logging/recent.rs
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Anzahl der Zeilen, die im Speicher gehalten werden
+pub const DEFAULT_CAPACITY: usize = 2000;
+
+/// Ringpuffer der zuletzt geschriebenen Log-Zeilen
+#[derive(Clone)]
+pub struct RecentLogs {
+    inner: Arc<Mutex<VecDeque<String>>>,
+    capacity: usize,
+}
+
+impl RecentLogs {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity: capacity.max(1),
+        }
+    }
+
+    fn push(&self, line: String) {
+        let Ok(mut lines) = self.inner.lock() else {
+            return;
+        };
+        if lines.len() == self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    /// Die letzten `limit` Zeilen in chronologischer Reihenfolge, ohne Limit alle
+    pub fn lines(&self, limit: Option<usize>) -> Vec<String> {
+        let Ok(lines) = self.inner.lock() else {
+            return Vec::new();
+        };
+        let skip = limit.map_or(0, |limit| lines.len().saturating_sub(limit));
+        lines.iter().skip(skip).cloned().collect()
+    }
+}
+
+/// Sammelt die Bytes eines Events und übernimmt sie beim Drop zeilenweise in den Puffer
+pub struct RecentLogWriter {
+    logs: RecentLogs,
+    buffer: Vec<u8>,
+}
+
+impl Write for RecentLogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for RecentLogWriter {
+    fn drop(&mut self) {
+        let text = String::from_utf8_lossy(&self.buffer);
+        for line in text.lines().filter(|line| !line.is_empty()) {
+            self.logs.push(line.to_string());
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for RecentLogs {
+    type Writer = RecentLogWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RecentLogWriter {
+            logs: self.clone(),
+            buffer: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_only_newest_lines() {
+        let logs = RecentLogs::new(3);
+        for i in 0..5 {
+            let mut writer = logs.make_writer();
+            writeln!(writer, "line {i}").unwrap();
+        }
+
+        assert_eq!(logs.lines(None), vec!["line 2", "line 3", "line 4"]);
+        assert_eq!(logs.lines(Some(2)), vec!["line 3", "line 4"]);
+        assert_eq!(logs.lines(Some(10)).len(), 3);
+    }
+
+    #[test]
+    fn test_event_written_in_chunks_becomes_one_line() {
+        let logs = RecentLogs::new(10);
+        {
+            let mut writer = logs.make_writer();
+            writer.write_all(b"INFO ").unwrap();
+            writer.write_all(b"vault opened\n").unwrap();
+        }
+
+        assert_eq!(logs.lines(None), vec!["INFO vault opened"]);
+    }
+}
@@ -0,0 +1,92 @@
+// src-tauri/src/logging/redact.rs
+// Hilfen, damit Schlüssel, Parameter und Inhalte aus SQL-Literalen nicht im Log landen.
+
+use crate::database::core::SqlParams;
+use std::fmt;
+
+const REDACTED: &str = "<redacted>";
+
+/// Gibt den Wert beim Formatieren nie aus, z.B. für Vault-Schlüssel
+pub struct Secret<'a, T: ?Sized>(pub &'a T);
+
+impl<T: ?Sized> fmt::Display for Secret<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Secret<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+/// Beschreibt SQL-Parameter nur über ihre Anzahl bzw. Namen, nie über ihre Werte
+pub fn params(params: &SqlParams) -> String {
+    match params {
+        SqlParams::Positional(values) => format!("[{} positional]", values.len()),
+        SqlParams::Named(values) => {
+            let names: Vec<&str> = values.keys().map(String::as_str).collect();
+            format!("[named: {}]", names.join(", "))
+        }
+    }
+}
+
+/// Ersetzt String- und BLOB-Literale im SQL durch `'?'`, Struktur und Bezeichner bleiben lesbar
+pub fn sql(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\'' {
+            out.push(c);
+            continue;
+        }
+
+        // Literal bis zum schließenden Quote überspringen ('' ist ein escaptes Quote)
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() == Some(&'\'') {
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+        }
+        out.push_str("'?'");
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sql_literals_are_masked() {
+        assert_eq!(
+            sql("ATTACH DATABASE 'vault.db' AS encrypted KEY 's3cr''et';"),
+            "ATTACH DATABASE '?' AS encrypted KEY '?';"
+        );
+        assert_eq!(
+            sql("SELECT \"name\" FROM items WHERE id = ?1 AND data = X'00ff'"),
+            "SELECT \"name\" FROM items WHERE id = ?1 AND data = X'?'"
+        );
+    }
+
+    #[test]
+    fn test_params_and_secrets_hide_values() {
+        let positional = SqlParams::Positional(vec![json!("secret"), json!(42)]);
+        assert_eq!(params(&positional), "[2 positional]");
+
+        let named: SqlParams = serde_json::from_value(json!({ "id": "secret" })).unwrap();
+        assert_eq!(params(&named), "[named: id]");
+
+        assert_eq!(
+            format!("{} {:?}", Secret("key"), Secret("key")),
+            "<redacted> <redacted>"
+        );
+    }
+}
@@ -197,7 +197,11 @@ const registerGlobalMessageHandler = () => {
         request.method === HAEXTENSION_METHODS.database.execute ||
         request.method === HAEXTENSION_METHODS.database.transaction
       ) {
-        result = await handleDatabaseMethodAsync(request, instance.extension)
+        result = await handleDatabaseMethodAsync(
+          request,
+          instance.extension,
+          instance.vaultId,
+        )
       } else if (
         request.method === HAEXTENSION_METHODS.filesystem.saveFile ||
         request.method === HAEXTENSION_METHODS.filesystem.openFile ||
@@ -208,10 +212,18 @@ const registerGlobalMessageHandler = () => {
         request.method === HAEXTENSION_METHODS.web.fetch ||
         request.method === HAEXTENSION_METHODS.application.open
       ) {
-        result = await handleWebMethodAsync(request, instance.extension)
+        result = await handleWebMethodAsync(
+          request,
+          instance.extension,
+          instance.vaultId,
+        )
       } else if (request.method.startsWith('haextension:permissions:')) {
         // Permissions noch nicht migriert
-        result = await handlePermissionsMethodAsync(request, instance.extension)
+        result = await handlePermissionsMethodAsync(
+          request,
+          instance.extension,
+          instance.vaultId,
+        )
       } else {
         throw new Error(`Unknown method: ${request.method}`)
       }
@@ -255,6 +267,7 @@ export const useExtensionMessageHandler = (
   iframeRef: Ref<HTMLIFrameElement | undefined | null>,
   extension: ComputedRef<IHaexHubExtension | undefined | null>,
   windowId: Ref<string>,
+  vaultId: string,
 ) => {
   // Initialize context getters (can use composables here because we're in setup)
   const { currentTheme } = storeToRefs(useUiStore())
@@ -276,6 +289,7 @@ export const useExtensionMessageHandler = (
       iframeRegistry.set(iframeRef.value, {
         extension: extension.value,
         windowId: windowId.value,
+        vaultId,
       })
     }
   })
@@ -303,13 +317,14 @@ export const registerExtensionIFrame = (
   iframe: HTMLIFrameElement,
   extension: IHaexHubExtension,
   windowId: string,
+  vaultId: string,
 ) => {
   // Stelle sicher, dass der globale Handler registriert ist
   registerGlobalMessageHandler()
 
   // Note: Context getters should be initialized via useExtensionMessageHandler first
 
-  iframeRegistry.set(iframe, { extension, windowId })
+  iframeRegistry.set(iframe, { extension, windowId, vaultId })
 }
 
 export const unregisterExtensionIFrame = (iframe: HTMLIFrameElement) => {
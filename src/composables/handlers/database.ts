@@ -6,10 +6,12 @@ import type { ExtensionRequest } from './types'
 export async function handleDatabaseMethodAsync(
   request: ExtensionRequest,
   extension: IHaexHubExtension,
+  vaultId: string,
 ) {
   const params = request.params as {
     query?: string
-    params?: unknown[]
+    // Positionsbasiert als Array oder benannt (`:name`, `$name`) als Objekt
+    params?: unknown[] | Record<string, unknown>
   }
 
   switch (request.method) {
@@ -20,6 +22,7 @@ export async function handleDatabaseMethodAsync(
           params: params.params || [],
           publicKey: extension.publicKey,
           name: extension.name,
+          vaultId: vaultId,
         })
 
         return {
@@ -36,6 +39,7 @@ export async function handleDatabaseMethodAsync(
             params: params.params || [],
             publicKey: extension.publicKey,
             name: extension.name,
+            vaultId: vaultId,
           })
 
           return {
@@ -54,6 +58,7 @@ export async function handleDatabaseMethodAsync(
         params: params.params || [],
         publicKey: extension.publicKey,
         name: extension.name,
+        vaultId: vaultId,
       })
 
       return {
@@ -73,6 +78,7 @@ export async function handleDatabaseMethodAsync(
           params: [],
           publicKey: extension.publicKey,
           name: extension.name,
+          vaultId: vaultId,
         })
       }
 
@@ -5,6 +5,7 @@ import { invoke } from '@tauri-apps/api/core'
 export async function handlePermissionsMethodAsync(
   request: ExtensionRequest,
   extension: IHaexHubExtension,
+  vaultId: string,
 ) {
   if (!extension || !request) {
     throw new Error('Extension not found')
@@ -13,15 +14,15 @@ export async function handlePermissionsMethodAsync(
   const { method, params } = request
 
   if (method === 'permissions.web.check') {
-    return await checkWebPermissionAsync(params, extension)
+    return await checkWebPermissionAsync(params, extension, vaultId)
   }
 
   if (method === 'permissions.database.check') {
-    return await checkDatabasePermissionAsync(params, extension)
+    return await checkDatabasePermissionAsync(params, extension, vaultId)
   }
 
   if (method === 'permissions.filesystem.check') {
-    return await checkFilesystemPermissionAsync(params, extension)
+    return await checkFilesystemPermissionAsync(params, extension, vaultId)
   }
 
   throw new Error(`Unknown permission method: ${method}`)
@@ -30,6 +31,7 @@ export async function handlePermissionsMethodAsync(
 async function checkWebPermissionAsync(
   params: Record<string, unknown>,
   extension: IHaexHubExtension,
+  vaultId: string,
 ) {
   const url = params.url as string
   const method = (params.method as string) || 'GET'
@@ -41,6 +43,7 @@ async function checkWebPermissionAsync(
   try {
     await invoke<void>('check_web_permission', {
       extensionId: extension.id,
+      vaultId: vaultId,
       method,
       url,
     })
@@ -59,6 +62,7 @@ async function checkWebPermissionAsync(
 async function checkDatabasePermissionAsync(
   params: Record<string, unknown>,
   extension: IHaexHubExtension,
+  vaultId: string,
 ) {
   const resource = params.resource as string
   const operation = params.operation as string
@@ -70,6 +74,7 @@ async function checkDatabasePermissionAsync(
   try {
     await invoke<void>('check_database_permission', {
       extensionId: extension.id,
+      vaultId: vaultId,
       resource,
       operation,
     })
@@ -86,6 +91,7 @@ async function checkDatabasePermissionAsync(
 async function checkFilesystemPermissionAsync(
   params: Record<string, unknown>,
   extension: IHaexHubExtension,
+  vaultId: string,
 ) {
   const path = params.path as string
   const operation = params.operation as string
@@ -97,6 +103,7 @@ async function checkFilesystemPermissionAsync(
   try {
     await invoke<void>('check_filesystem_permission', {
       extensionId: extension.id,
+      vaultId: vaultId,
       path,
       operation,
     })
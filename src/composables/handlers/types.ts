@@ -11,4 +11,6 @@ export interface ExtensionRequest {
 export interface ExtensionInstance {
   extension: IHaexHubExtension
   windowId: string
+  // Vault, in der das Fenster geöffnet wurde - nicht zwingend die aktuell angezeigte
+  vaultId: string
 }
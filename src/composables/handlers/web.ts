@@ -6,6 +6,7 @@ import { HAEXTENSION_METHODS } from '@haexhub/sdk'
 export async function handleWebMethodAsync(
   request: ExtensionRequest,
   extension: IHaexHubExtension,
+  vaultId: string,
 ) {
   if (!extension || !request) {
     throw new Error('Extension not found')
@@ -14,11 +15,11 @@ export async function handleWebMethodAsync(
   const { method, params } = request
 
   if (method === HAEXTENSION_METHODS.web.fetch) {
-    return await handleWebFetchAsync(params, extension)
+    return await handleWebFetchAsync(params, extension, vaultId)
   }
 
   if (method === HAEXTENSION_METHODS.application.open) {
-    return await handleWebOpenAsync(params, extension)
+    return await handleWebOpenAsync(params, extension, vaultId)
   }
 
   throw new Error(`Unknown web method: ${method}`)
@@ -27,6 +28,7 @@ export async function handleWebMethodAsync(
 async function handleWebFetchAsync(
   params: Record<string, unknown>,
   extension: IHaexHubExtension,
+  vaultId: string,
 ) {
   const url = params.url as string
   const method = (params.method as string) || undefined
@@ -54,6 +56,7 @@ async function handleWebFetchAsync(
       timeout,
       publicKey: extension.publicKey,
       name: extension.name,
+      vaultId: vaultId,
     })
 
     return {
@@ -86,6 +89,7 @@ async function handleWebFetchAsync(
 async function handleWebOpenAsync(
   params: Record<string, unknown>,
   extension: IHaexHubExtension,
+  vaultId: string,
 ) {
   const url = params.url as string
 
@@ -99,6 +103,7 @@ async function handleWebOpenAsync(
       url,
       publicKey: extension.publicKey,
       name: extension.name,
+      vaultId: vaultId,
     })
   } catch (error) {
     if (error instanceof Error) {
@@ -7,3 +7,8 @@ import eventNames from './eventNames.json'
 
 // Extension Events
 export const EXTENSION_WINDOW_CLOSED = eventNames.extension.windowClosed
+export const EXTENSION_INSTALL_PROGRESS = eventNames.extension.installProgress
+
+// Database Events
+export const DATABASE_CHANGED = eventNames.database.changed
+export const DATABASE_LIVE_QUERY_RESULT = eventNames.database.liveQueryResult
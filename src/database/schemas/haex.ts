@@ -2,6 +2,7 @@ import { sql } from 'drizzle-orm'
 import {
   check,
   integer,
+  primaryKey,
   sqliteTable,
   text,
   unique,
@@ -89,6 +90,53 @@ export const haexExtensions = sqliteTable(
 export type InsertHaexExtensions = typeof haexExtensions.$inferInsert
 export type SelectHaexExtensions = typeof haexExtensions.$inferSelect
 
+export const haexExtensionPublishers = sqliteTable(
+  tableNames.haex.extension_publishers.name,
+  withCrdtColumns({
+    id: text()
+      .$defaultFn(() => crypto.randomUUID())
+      .primaryKey(),
+    publicKey: text(tableNames.haex.extension_publishers.columns.publicKey)
+      .notNull()
+      .unique(),
+    displayName: text(tableNames.haex.extension_publishers.columns.displayName),
+    trustLevel: text(tableNames.haex.extension_publishers.columns.trustLevel, {
+      enum: ['trusted', 'known', 'blocked'],
+    })
+      .notNull()
+      .default('known'),
+    createdAt: text(tableNames.haex.extension_publishers.columns.createdAt).default(
+      sql`(CURRENT_TIMESTAMP)`,
+    ),
+  }),
+)
+export type InsertHaexExtensionPublishers =
+  typeof haexExtensionPublishers.$inferInsert
+export type SelectHaexExtensionPublishers =
+  typeof haexExtensionPublishers.$inferSelect
+
+/**
+ * Applied schema migrations of extension tables (WITHOUT CRDT - local-only)
+ * Every device migrates its own schema, keyed by the extension's table prefix
+ */
+export const haexExtensionMigrations = sqliteTable(
+  tableNames.haex.extension_migrations.name,
+  {
+    tablePrefix: text(
+      tableNames.haex.extension_migrations.columns.tablePrefix,
+    ).notNull(),
+    name: text(tableNames.haex.extension_migrations.columns.name).notNull(),
+    appliedAt: text(tableNames.haex.extension_migrations.columns.appliedAt)
+      .notNull()
+      .default(sql`(CURRENT_TIMESTAMP)`),
+  },
+  (table) => [primaryKey({ columns: [table.tablePrefix, table.name] })],
+)
+export type InsertHaexExtensionMigrations =
+  typeof haexExtensionMigrations.$inferInsert
+export type SelectHaexExtensionMigrations =
+  typeof haexExtensionMigrations.$inferSelect
+
 export const haexExtensionPermissions = sqliteTable(
   tableNames.haex.extension_permissions.name,
   withCrdtColumns({
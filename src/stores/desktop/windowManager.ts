@@ -10,6 +10,7 @@ export interface IWindow {
   workspaceId: string // Window belongs to a specific workspace
   type: 'system' | 'extension'
   sourceId: string // extensionId or systemWindowId (depends on type)
+  vaultId: string // Vault the window was opened in - stays fixed when switching vaults
   title: string
   icon?: string | null
   x: number
@@ -157,6 +158,13 @@ export const useWindowManagerStore = defineStore('windowManager', () => {
     workspaceId?: string
   }) => {
     try {
+      // Extension-Aufrufe gehen immer an die Vault, in der das Fenster geöffnet wurde
+      const vaultId = useVaultStore().currentVaultId
+      if (!vaultId) {
+        console.error('Cannot open window: No open vault')
+        return
+      }
+
       // Desktop: Check extension's display_mode preference
       if (type === 'extension') {
         const extensionsStore = useExtensionsStore()
@@ -186,6 +194,7 @@ export const useWindowManagerStore = defineStore('windowManager', () => {
             console.log('[windowManager] Extension object:', extension)
             // Backend generates and returns the window_id
             const windowId = await invoke<string>('open_extension_webview_window', {
+              vaultId,
               extensionId: sourceId,
               title: finalTitle,
               width,
@@ -200,6 +209,7 @@ export const useWindowManagerStore = defineStore('windowManager', () => {
               workspaceId: '', // Not used on desktop
               type,
               sourceId,
+              vaultId,
               title: finalTitle,
               icon,
               x: 0,
@@ -317,6 +327,7 @@ export const useWindowManagerStore = defineStore('windowManager', () => {
         workspaceId: workspace.id,
         type,
         sourceId,
+        vaultId,
         title: title!,
         icon,
         x,
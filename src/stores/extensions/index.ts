@@ -1,12 +1,20 @@
 import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
 import { readFile } from '@tauri-apps/plugin-fs'
 import { getExtensionUrl } from '~/utils/extension'
+import { EXTENSION_INSTALL_PROGRESS } from '~/constants/events'
 
 import type {
   IHaexHubExtension,
   IHaexHubExtensionManifest,
 } from '~/types/haexhub'
 import type { ExtensionPreview } from '@bindings/ExtensionPreview'
+import type { InstallProgress } from '@bindings/InstallProgress'
+import type { InstallStage } from '@bindings/InstallStage'
+import type { TrustedPublisher } from '@bindings/TrustedPublisher'
+import type { RegistryExtension } from '@bindings/RegistryExtension'
+import type { ExtensionUpdate } from '@bindings/ExtensionUpdate'
+import type { TrustLevel } from '@bindings/TrustLevel'
 import type { ExtensionPermissions } from '~~/src-tauri/bindings/ExtensionPermissions'
 import type { ExtensionInfoResponse } from '~~/src-tauri/bindings/ExtensionInfoResponse'
 
@@ -16,6 +24,7 @@ const logoFileName = 'icon.svg' */
 export const useExtensionsStore = defineStore('extensionsStore', () => {
   const availableExtensions = ref<IHaexHubExtension[]>([])
   const currentRoute = useRouter().currentRoute
+  const { currentVaultId } = storeToRefs(useVaultStore())
 
   const currentExtensionId = computed(() =>
     getSingleRouteParam(currentRoute.value.params.extensionId),
@@ -88,7 +97,9 @@ export const useExtensionsStore = defineStore('extensionsStore', () => {
   const loadExtensionsAsync = async () => {
     try {
       const extensions =
-        await invoke<ExtensionInfoResponse[]>('get_all_extensions')
+        await invoke<ExtensionInfoResponse[]>('get_all_extensions', {
+          vaultId: currentVaultId.value,
+        })
 
       console.log('get_all_extensions', extensions)
       // ExtensionInfoResponse is now directly compatible with IHaexHubExtension
@@ -128,30 +139,76 @@ export const useExtensionsStore = defineStore('extensionsStore', () => {
     return true
   } */
 
+  // Content-URIs (Android) kann nur das Frontend lesen, alles andere liest Rust direkt von der Platte
+  const isContentUri = (path: string) => path.startsWith('content://')
+
   const installAsync = async (
     sourcePath: string | null,
     permissions?: ExtensionPermissions,
+    onProgress?: (stage: InstallStage) => void,
   ) => {
     if (!sourcePath) throw new Error('Kein Pfad angegeben')
 
     try {
-      // Read file as bytes (works with content URIs on Android)
-      const fileBytes = await readFile(sourcePath)
+      if (isContentUri(sourcePath)) {
+        // Read file as bytes (works with content URIs on Android)
+        const fileBytes = await readFile(sourcePath)
 
-      const extensionId = await invoke<string>(
-        'install_extension_with_permissions',
-        {
+        return await invoke<string>('install_extension_with_permissions', {
           fileBytes: Array.from(fileBytes),
           customPermissions: permissions,
-        },
-      )
-      return extensionId
+          vaultId: currentVaultId.value,
+        })
+      }
+
+      const installId = crypto.randomUUID()
+      const unlisten = onProgress
+        ? await listen<InstallProgress>(EXTENSION_INSTALL_PROGRESS, (event) => {
+            if (event.payload.installId === installId)
+              onProgress(event.payload.stage)
+          })
+        : undefined
+
+      try {
+        return await invoke<string>('install_extension_from_path', {
+          filePath: sourcePath,
+          customPermissions: permissions,
+          installId: onProgress ? installId : null,
+          vaultId: currentVaultId.value,
+        })
+      } finally {
+        unlisten?.()
+      }
     } catch (error) {
       console.error('Fehler bei Extension-Installation:', error)
       throw error
     }
   }
 
+  const setExtensionEnabledAsync = async (
+    extensionId: string,
+    enabled: boolean,
+  ) => {
+    await invoke('set_extension_enabled', {
+      extensionId,
+      enabled,
+      vaultId: currentVaultId.value,
+    })
+
+    // Native Fenster schließt das Backend, iframe-Fenster werden hier geschlossen
+    if (!enabled) {
+      const windowManagerStore = useWindowManagerStore()
+      const extensionWindows = windowManagerStore.windows.filter(
+        (win) => win.type === 'extension' && win.sourceId === extensionId,
+      )
+      for (const win of extensionWindows) {
+        await windowManagerStore.closeWindow(win.id)
+      }
+    }
+
+    await loadExtensionsAsync()
+  }
+
   const removeExtensionAsync = async (
     publicKey: string,
     name: string,
@@ -162,6 +219,7 @@ export const useExtensionsStore = defineStore('extensionsStore', () => {
         publicKey,
         name,
         version,
+        vaultId: currentVaultId.value,
       })
     } catch (error) {
       console.error('Fehler beim Entfernen der Extension:', error)
@@ -169,6 +227,110 @@ export const useExtensionsStore = defineStore('extensionsStore', () => {
     }
   }
 
+  const getExtensionVersionsAsync = async (publicKey: string, name: string) => {
+    return await invoke<string[]>('get_extension_versions', {
+      publicKey,
+      name,
+    })
+  }
+
+  const rollbackExtensionAsync = async (
+    publicKey: string,
+    name: string,
+    version?: string,
+  ) => {
+    try {
+      return await invoke<string>('rollback_extension', {
+        publicKey,
+        name,
+        version,
+        vaultId: currentVaultId.value,
+      })
+    } catch (error) {
+      console.error('Fehler beim Zurücksetzen der Extension:', error)
+      throw error
+    }
+  }
+
+  const getPublishersAsync = async () => {
+    return await invoke<TrustedPublisher[]>('get_extension_publishers', {
+      vaultId: currentVaultId.value,
+    })
+  }
+
+  const setPublisherAsync = async (
+    publicKey: string,
+    trustLevel: TrustLevel,
+    displayName?: string | null,
+  ) => {
+    return await invoke<TrustedPublisher>('set_extension_publisher', {
+      publicKey,
+      displayName,
+      trustLevel,
+      vaultId: currentVaultId.value,
+    })
+  }
+
+  const removePublisherAsync = async (publicKey: string) => {
+    await invoke('remove_extension_publisher', {
+      publicKey,
+      vaultId: currentVaultId.value,
+    })
+  }
+
+  const searchRegistryAsync = async (query?: string, registryUrl?: string) => {
+    return await invoke<RegistryExtension[]>('get_registry_extensions', {
+      query,
+      registryUrl,
+      vaultId: currentVaultId.value,
+    })
+  }
+
+  const previewRegistryExtensionAsync = async (
+    publicKey: string,
+    name: string,
+    version?: string,
+    registryUrl?: string,
+  ) => {
+    preview.value = await invoke<ExtensionPreview>(
+      'preview_registry_extension',
+      {
+        publicKey,
+        name,
+        version,
+        registryUrl,
+        vaultId: currentVaultId.value,
+      },
+    )
+    return preview.value
+  }
+
+  const installRegistryExtensionAsync = async (
+    publicKey: string,
+    name: string,
+    permissions: ExtensionPermissions,
+    version?: string,
+    registryUrl?: string,
+  ) => {
+    const extensionId = await invoke<string>('install_registry_extension', {
+      publicKey,
+      name,
+      version,
+      customPermissions: permissions,
+      registryUrl,
+      vaultId: currentVaultId.value,
+    })
+    await loadExtensionsAsync()
+    return extensionId
+  }
+
+  const checkForUpdatesAsync = async (registryUrl?: string) => {
+    return await invoke<ExtensionUpdate[]>('check_extension_updates', {
+      registryUrl,
+      vaultId: currentVaultId.value,
+    })
+  }
+
   /* const removeExtensionAsync = async (id: string, version: string) => {
     try {
       console.log('remove extension', id, version)
@@ -193,6 +355,7 @@ export const useExtensionsStore = defineStore('extensionsStore', () => {
         publicKey,
         name,
         extensionVersion: version,
+        vaultId: currentVaultId.value,
       })
     } catch (error) {
       console.error('Fehler beim Prüfen der Extension:', error)
@@ -257,12 +420,23 @@ export const useExtensionsStore = defineStore('extensionsStore', () => {
   const preview = ref<ExtensionPreview>()
 
   const previewManifestAsync = async (extensionPath: string) => {
-    // Read file as bytes (works with content URIs on Android)
-    const fileBytes = await readFile(extensionPath)
+    if (isContentUri(extensionPath)) {
+      // Read file as bytes (works with content URIs on Android)
+      const fileBytes = await readFile(extensionPath)
 
-    preview.value = await invoke<ExtensionPreview>('preview_extension', {
-      fileBytes: Array.from(fileBytes),
-    })
+      preview.value = await invoke<ExtensionPreview>('preview_extension', {
+        fileBytes: Array.from(fileBytes),
+        vaultId: currentVaultId.value,
+      })
+    } else {
+      preview.value = await invoke<ExtensionPreview>(
+        'preview_extension_from_path',
+        {
+          filePath: extensionPath,
+          vaultId: currentVaultId.value,
+        },
+      )
+    }
     return preview.value
   }
 
@@ -272,12 +446,22 @@ export const useExtensionsStore = defineStore('extensionsStore', () => {
     currentExtension,
     currentExtensionId,
     extensionEntry,
+    checkForUpdatesAsync,
+    getExtensionVersionsAsync,
+    getPublishersAsync,
     installAsync,
+    installRegistryExtensionAsync,
     //isActive,
     isExtensionInstalledAsync,
     loadExtensionsAsync,
     previewManifestAsync,
+    previewRegistryExtensionAsync,
     removeExtensionAsync,
+    setExtensionEnabledAsync,
+    removePublisherAsync,
+    rollbackExtensionAsync,
+    searchRegistryAsync,
+    setPublisherAsync,
   }
 })
 
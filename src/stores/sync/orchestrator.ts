@@ -4,6 +4,7 @@
  */
 
 import { eq, gt } from 'drizzle-orm'
+import { invoke } from '@tauri-apps/api/core'
 import type { RealtimeChannel } from '@supabase/supabase-js'
 import {
   haexCrdtLogs,
@@ -158,6 +159,14 @@ export const useSyncOrchestratorStore = defineStore(
 
         // TODO: Apply CRDT log entries to actual data tables
         // This requires replaying the operations from the log
+
+        // Suchindizes werden nicht synchronisiert, sondern lokal aus den Tabellen neu aufgebaut
+        if (logs.length && currentVaultId.value) {
+          await invoke('rebuild_search_indexes', {
+            vaultId: currentVaultId.value,
+          })
+        }
+
         console.log(`Applied ${logs.length} remote logs to local database`)
       } catch (error) {
         console.error('Failed to apply remote logs:', error)
@@ -2,7 +2,15 @@
 
 import { drizzle } from 'drizzle-orm/sqlite-proxy'
 import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
 import { schema } from '~/database'
+import type { QueryOptions } from '@bindings/QueryOptions'
+import type { QueryResult } from '@bindings/QueryResult'
+import type { LiveQueryResultEvent } from '@bindings/LiveQueryResultEvent'
+import type { LiveQuerySubscription } from '@bindings/LiveQuerySubscription'
+import type { SearchHit } from '@bindings/SearchHit'
+import type { SearchOptions } from '@bindings/SearchOptions'
+import { DATABASE_LIVE_QUERY_RESULT } from '~/constants/events'
 import type {
   AsyncRemoteCallback,
   SqliteRemoteDatabase,
@@ -46,20 +54,19 @@ export const useVaultStore = defineStore('vaultStore', () => {
     password: string
   }) => {
     try {
-      await invoke<string>('open_encrypted_database', {
+      // Das Backend hält jede geöffnete Vault unter ihrer ID
+      const vaultId = await invoke<string>('open_encrypted_database', {
         vaultPath: path,
         key: password,
       })
 
-      const vaultId = await getVaultIdAsync(path)
-
       const fileName = getFileName(path) ?? path
 
       openVaults.value = {
         ...openVaults.value,
         [vaultId]: {
           name: fileName,
-          drizzle: drizzle<typeof schema>(drizzleCallback, {
+          drizzle: drizzle<typeof schema>(createDrizzleCallback(vaultId), {
             schema: schema,
             logger: false,
           }),
@@ -90,9 +97,93 @@ export const useVaultStore = defineStore('vaultStore', () => {
   const closeAsync = async () => {
     if (!currentVaultId.value) return
 
+    await invoke('close_vault', { vaultId: currentVaultId.value })
     delete openVaults.value?.[currentVaultId.value]
   }
 
+  // Typisierte Query: Zeilen als Objekte mit Spaltennamen statt positionsbasierter Arrays
+  const queryTypedAsync = async <T = Record<string, unknown>>(
+    sql: string,
+    params: unknown[] | Record<string, unknown> = [],
+    options?: QueryOptions,
+  ) => {
+    if (!currentVaultId.value) throw new Error('Kein Vault geöffnet')
+
+    const result = await invoke<QueryResult>('sql_query_typed', {
+      sql,
+      params: encodeSqlParams(params),
+      options,
+      vaultId: currentVaultId.value,
+    })
+    return { columns: result.columns, rows: decodeTypedRows<T>(result) }
+  }
+
+  // Live-Query: `onResult` bekommt sofort das Ergebnis und danach jedes geänderte Ergebnis,
+  // sobald ein Commit eine der gelesenen Tabellen berührt. Liefert die Abmelde-Funktion.
+  const subscribeLiveQueryAsync = async <T = Record<string, unknown>>(
+    sql: string,
+    params: unknown[] | Record<string, unknown>,
+    onResult: (rows: T[]) => void,
+  ) => {
+    const vaultId = currentVaultId.value
+    if (!vaultId) throw new Error('Kein Vault geöffnet')
+
+    let subscriptionId: string | undefined
+    const unlisten = await listen<LiveQueryResultEvent>(
+      DATABASE_LIVE_QUERY_RESULT,
+      ({ payload }) => {
+        if (
+          payload.vaultId === vaultId &&
+          payload.subscriptionId === subscriptionId
+        ) {
+          onResult(decodeTypedRows<T>(payload.result))
+        }
+      },
+    )
+
+    try {
+      const subscription = await invoke<LiveQuerySubscription>(
+        'subscribe_live_query',
+        { sql, params: encodeSqlParams(params), vaultId },
+      )
+      subscriptionId = subscription.subscriptionId
+      onResult(decodeTypedRows<T>(subscription.result))
+    } catch (error) {
+      unlisten()
+      throw error
+    }
+
+    return async () => {
+      unlisten()
+      await invoke('unsubscribe_live_query', { subscriptionId, vaultId })
+    }
+  }
+
+  // Volltextsuche über einen mit `declare_search_index` angelegten FTS-Index
+  const searchAsync = async (
+    table: string,
+    query: string,
+    options?: SearchOptions,
+  ) => {
+    if (!currentVaultId.value) throw new Error('Kein Vault geöffnet')
+
+    const hits = await invoke<SearchHit[]>('search', {
+      table,
+      query,
+      options,
+      vaultId: currentVaultId.value,
+    })
+    return hits.map((hit) => ({
+      ...hit,
+      row: Object.fromEntries(
+        Object.entries(hit.row).map(([column, value]) => [
+          column,
+          decodeSqlValue(value),
+        ]),
+      ),
+    }))
+  }
+
   const existsVault = () => {
     if (!currentVault.value?.drizzle) {
       console.error('Kein Vault geöffnet')
@@ -109,18 +200,21 @@ export const useVaultStore = defineStore('vaultStore', () => {
     existsVault,
     openAsync,
     openVaults,
+    queryTypedAsync,
+    searchAsync,
+    subscribeLiveQueryAsync,
   }
 })
 
-const getVaultIdAsync = async (path: string) => {
-  const encoder = new TextEncoder()
-  const data = encoder.encode(path)
-
-  const hashBuffer = await crypto.subtle.digest('SHA-256', data)
-  const hashArray = Array.from(new Uint8Array(hashBuffer))
-  const hashHex = hashArray.map((b) => b.toString(16).padStart(2, '0')).join('')
-  return hashHex
-}
+const decodeTypedRows = <T>(result: QueryResult) =>
+  result.rows.map((row) =>
+    Object.fromEntries(
+      Object.entries(row).map(([column, value]) => [
+        column,
+        decodeSqlValue(value),
+      ]),
+    ),
+  ) as T[]
 
 const isSelectQuery = (sql: string) => {
   const selectRegex = /^\s*SELECT\b/i
@@ -132,7 +226,8 @@ const hasReturning = (sql: string) => {
   return returningRegex.test(sql)
 }
 
-const drizzleCallback = (async (
+// Jede Vault bekommt ihren eigenen Callback, damit Queries immer die richtige Vault treffen
+const createDrizzleCallback = (vaultId: string) => (async (
   sql: string,
   params: unknown[],
   method: 'get' | 'run' | 'all' | 'values',
@@ -146,7 +241,8 @@ const drizzleCallback = (async (
       // SELECT statements
       rows = await invoke<unknown[]>('sql_select_with_crdt', {
         sql,
-        params,
+        params: encodeSqlParams(params),
+        vaultId,
       }).catch((e) => {
         console.error('SQL select Error:', e, sql, params)
         return []
@@ -155,7 +251,8 @@ const drizzleCallback = (async (
       // INSERT/UPDATE/DELETE with RETURNING → use query
       rows = await invoke<unknown[]>('sql_query_with_crdt', {
         sql,
-        params,
+        params: encodeSqlParams(params),
+        vaultId,
       }).catch((e) => {
         console.error('SQL query with CRDT Error:', e, sql, params)
         return []
@@ -164,7 +261,8 @@ const drizzleCallback = (async (
       // INSERT/UPDATE/DELETE without RETURNING → use execute
       await invoke<unknown[]>('sql_execute_with_crdt', {
         sql,
-        params,
+        params: encodeSqlParams(params),
+        vaultId,
       }).catch((e) => {
         console.error('SQL execute with CRDT Error:', e, sql, params, rows)
         return []
@@ -181,6 +279,9 @@ const drizzleCallback = (async (
   /* console.log('drizzleCallback', method, sql, params)
   console.log('drizzleCallback rows', rows, rows.slice(0, 1)) */
 
+  // BLOBs und große Integer kommen getaggt aus dem Backend
+  rows = decodeSqlRows(rows)
+
   if (method === 'get') {
     return rows.length > 0 ? { rows: rows.at(0) } : { rows }
   }
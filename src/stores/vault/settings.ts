@@ -2,6 +2,7 @@ import { and, eq } from 'drizzle-orm'
 import { z } from 'zod'
 import * as schema from '~/database/schemas/haex'
 import type { Locale } from 'vue-i18n'
+import type { SignaturePolicy } from '@bindings/SignaturePolicy'
 
 export enum VaultSettingsTypeEnum {
   settings = 'settings',
@@ -13,6 +14,8 @@ export enum VaultSettingsKeyEnum {
   theme = 'theme',
   vaultName = 'vaultName',
   desktopIconSize = 'desktopIconSize',
+  extensionSignaturePolicy = 'extensionSignaturePolicy',
+  extensionRegistryUrl = 'extensionRegistryUrl',
 }
 
 export enum DesktopIconSizePreset {
@@ -232,6 +235,60 @@ export const useVaultSettingsStore = defineStore('vaultSettingsStore', () => {
       )
   }
 
+  const readVaultSettingAsync = async (key: VaultSettingsKeyEnum) => {
+    const row = await currentVault.value?.drizzle.query.haexSettings.findFirst({
+      where: and(
+        eq(schema.haexSettings.key, key),
+        eq(schema.haexSettings.type, VaultSettingsTypeEnum.settings),
+      ),
+    })
+    return row?.id ? row : undefined
+  }
+
+  const upsertVaultSettingAsync = async (
+    key: VaultSettingsKeyEnum,
+    value: string | null,
+  ) => {
+    const row = await readVaultSettingAsync(key)
+
+    if (!row) {
+      return await currentVault.value?.drizzle.insert(schema.haexSettings).values({
+        key,
+        type: VaultSettingsTypeEnum.settings,
+        value,
+      })
+    }
+
+    return await currentVault.value?.drizzle
+      .update(schema.haexSettings)
+      .set({ value })
+      .where(eq(schema.haexSettings.id, row.id))
+  }
+
+  const readExtensionSignaturePolicyAsync = async () => {
+    const row = await readVaultSettingAsync(
+      VaultSettingsKeyEnum.extensionSignaturePolicy,
+    )
+    // Ohne Eintrag gilt im Backend 'warn'
+    return (row?.value as SignaturePolicy | undefined) ?? 'warn'
+  }
+
+  const updateExtensionSignaturePolicyAsync = async (policy: SignaturePolicy) =>
+    upsertVaultSettingAsync(
+      VaultSettingsKeyEnum.extensionSignaturePolicy,
+      policy,
+    )
+
+  const readExtensionRegistryUrlAsync = async () => {
+    const row = await readVaultSettingAsync(
+      VaultSettingsKeyEnum.extensionRegistryUrl,
+    )
+    return row?.value ?? null
+  }
+
+  const updateExtensionRegistryUrlAsync = async (url: string | null) =>
+    upsertVaultSettingAsync(VaultSettingsKeyEnum.extensionRegistryUrl, url)
+
   return {
     addDeviceNameAsync,
     readDeviceNameAsync,
@@ -244,5 +301,9 @@ export const useVaultSettingsStore = defineStore('vaultSettingsStore', () => {
     updateVaultNameAsync,
     syncDesktopIconSizeAsync,
     updateDesktopIconSizeAsync,
+    readExtensionSignaturePolicyAsync,
+    updateExtensionSignaturePolicyAsync,
+    readExtensionRegistryUrlAsync,
+    updateExtensionRegistryUrlAsync,
   }
 })
@@ -0,0 +1,64 @@
+/**
+ * Getaggte SQL-Werte zwischen Frontend und Backend
+ *
+ * JSON kann weder BLOBs noch 64-bit Integer verlustfrei transportieren. Das Backend
+ * liefert sie daher als `{ $blob: base64 }` bzw. `{ $bigint: "123" }` und nimmt
+ * Parameter in derselben Form entgegen.
+ */
+
+type TaggedBlob = { $blob: string }
+type TaggedBigInt = { $bigint: string }
+
+const isTagged = <T extends object>(value: unknown, tag: keyof T): value is T =>
+  typeof value === 'object' &&
+  value !== null &&
+  Object.keys(value).length === 1 &&
+  tag in value
+
+const bytesToBase64 = (bytes: Uint8Array) => {
+  let binary = ''
+  bytes.forEach((byte) => (binary += String.fromCharCode(byte)))
+  return btoa(binary)
+}
+
+const base64ToBytes = (base64: string) =>
+  Uint8Array.from(atob(base64), (char) => char.charCodeAt(0))
+
+/**
+ * Wandelt einen Parameter in seine JSON-taugliche Form um
+ */
+export const encodeSqlParam = (value: unknown): unknown => {
+  if (value instanceof Uint8Array) {
+    return { $blob: bytesToBase64(value) } satisfies TaggedBlob
+  }
+  if (typeof value === 'bigint') {
+    return { $bigint: value.toString() } satisfies TaggedBigInt
+  }
+  return value
+}
+
+export const encodeSqlParams = (
+  params: unknown[] | Record<string, unknown>,
+) =>
+  Array.isArray(params)
+    ? params.map(encodeSqlParam)
+    : Object.fromEntries(
+        Object.entries(params).map(([key, value]) => [
+          key,
+          encodeSqlParam(value),
+        ]),
+      )
+
+/**
+ * Wandelt getaggte Werte aus dem Backend zurück in Uint8Array bzw. BigInt
+ */
+export const decodeSqlValue = (value: unknown): unknown => {
+  if (isTagged<TaggedBlob>(value, '$blob')) return base64ToBytes(value.$blob)
+  if (isTagged<TaggedBigInt>(value, '$bigint')) return BigInt(value.$bigint)
+  return value
+}
+
+export const decodeSqlRows = (rows: unknown[]) =>
+  rows.map((row) =>
+    Array.isArray(row) ? row.map(decodeSqlValue) : decodeSqlValue(row),
+  )